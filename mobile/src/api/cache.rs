@@ -0,0 +1,377 @@
+use std::{
+    collections::HashMap,
+    fs,
+    path::PathBuf,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use chrono::{DateTime, Utc};
+use reqwest::{
+    header::{HeaderMap, HeaderName, HeaderValue, CACHE_CONTROL, ETAG, LAST_MODIFIED},
+    StatusCode, Url,
+};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+/// Most responses kept at once. The oldest are evicted first.
+const MAX_ENTRIES: usize = 256;
+
+/// A response from the API, read in full
+#[derive(Clone, Debug)]
+pub struct ApiResponse {
+    pub status: StatusCode,
+    pub headers: HeaderMap,
+    pub body: Vec<u8>,
+}
+
+/// Responses are cached per URL and per user, so one user's data is never served to another
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct CacheKey {
+    url: String,
+    /// Hash of the token, to avoid keeping tokens around in memory or on disk
+    identity: Option<String>,
+}
+
+impl CacheKey {
+    pub fn new(url: &reqwest::Url, token: Option<&str>) -> Self {
+        Self {
+            url: url.to_string(),
+            identity: token.map(hash),
+        }
+    }
+
+    /// Name of the file the entry is saved in
+    fn file_name(&self) -> String {
+        let identity = self.identity.as_deref().unwrap_or_default();
+        format!("{}.json", hash(&format!("{identity} {}", self.url)))
+    }
+}
+
+fn hash(value: &str) -> String {
+    URL_SAFE_NO_PAD.encode(Sha256::digest(value.as_bytes()))
+}
+
+/// What to do with a request, given the cache
+pub enum Lookup {
+    /// The cached response can be used without asking the server
+    Fresh(ApiResponse),
+    /// The server has to confirm the cached response is still valid
+    Revalidate {
+        etag: Option<HeaderValue>,
+        last_modified: Option<HeaderValue>,
+    },
+    Miss,
+}
+
+struct CacheEntry {
+    response: ApiResponse,
+    stored_at: DateTime<Utc>,
+    /// Until when the response can be used without revalidating it
+    fresh_until: Option<DateTime<Utc>>,
+}
+
+/// [CacheEntry] as saved on disk
+#[derive(Serialize, Deserialize)]
+struct SavedEntry {
+    url: String,
+    identity: Option<String>,
+    status: u16,
+    headers: Vec<(String, String)>,
+    /// API responses are JSON, so other bodies aren't saved
+    body: String,
+    stored_at: DateTime<Utc>,
+    fresh_until: Option<DateTime<Utc>>,
+}
+
+impl SavedEntry {
+    fn new(key: &CacheKey, entry: &CacheEntry) -> Option<Self> {
+        Some(Self {
+            url: key.url.clone(),
+            identity: key.identity.clone(),
+            status: entry.response.status.as_u16(),
+            headers: entry
+                .response
+                .headers
+                .iter()
+                .filter_map(|(name, value)| Some((name.to_string(), value.to_str().ok()?.into())))
+                .collect(),
+            body: String::from_utf8(entry.response.body.clone()).ok()?,
+            stored_at: entry.stored_at,
+            fresh_until: entry.fresh_until,
+        })
+    }
+
+    fn into_entry(self) -> Option<(CacheKey, CacheEntry)> {
+        let mut headers = HeaderMap::new();
+        for (name, value) in self.headers {
+            headers.append(
+                HeaderName::try_from(name).ok()?,
+                HeaderValue::try_from(value).ok()?,
+            );
+        }
+        let key = CacheKey {
+            url: self.url,
+            identity: self.identity,
+        };
+        let entry = CacheEntry {
+            response: ApiResponse {
+                status: StatusCode::from_u16(self.status).ok()?,
+                headers,
+                body: self.body.into_bytes(),
+            },
+            stored_at: self.stored_at,
+            fresh_until: self.fresh_until,
+        };
+        Some((key, entry))
+    }
+}
+
+/// HTTP cache for `GET` responses, using `ETag`/`Last-Modified` for conditional requests
+/// and honoring `Cache-Control`
+#[derive(Clone, Default)]
+pub struct ResponseCache {
+    entries: Arc<Mutex<HashMap<CacheKey, CacheEntry>>>,
+    /// Directory the entries are saved in, one file each, so they're kept between launches
+    dir: Option<PathBuf>,
+}
+
+impl ResponseCache {
+    /// Cache saved in the directory, starting with the entries saved in it before
+    pub fn persistent(dir: PathBuf) -> Self {
+        let mut entries = HashMap::new();
+        for path in fs::read_dir(&dir)
+            .into_iter()
+            .flatten()
+            .filter_map(|file| Some(file.ok()?.path()))
+        {
+            let entry = fs::read(&path)
+                .ok()
+                .and_then(|data| serde_json::from_slice::<SavedEntry>(&data).ok())
+                .and_then(SavedEntry::into_entry);
+            match entry {
+                Some((key, entry)) => {
+                    entries.insert(key, entry);
+                }
+                None => {
+                    tracing::warn!("Removing unreadable cache entry {}", path.display());
+                    let _ = fs::remove_file(&path);
+                }
+            }
+        }
+
+        Self {
+            entries: Arc::new(Mutex::new(entries)),
+            dir: Some(dir),
+        }
+    }
+
+    pub fn lookup(&self, key: &CacheKey) -> Lookup {
+        let entries = self.entries.lock().unwrap_or_else(|err| err.into_inner());
+        let Some(entry) = entries.get(key) else {
+            return Lookup::Miss;
+        };
+
+        if entry
+            .fresh_until
+            .is_some_and(|fresh_until| Utc::now() < fresh_until)
+        {
+            return Lookup::Fresh(entry.response.clone());
+        }
+        Lookup::Revalidate {
+            etag: entry.response.headers.get(ETAG).cloned(),
+            last_modified: entry.response.headers.get(LAST_MODIFIED).cloned(),
+        }
+    }
+
+    /// Stores a successful response, if its `Cache-Control` allows it and it can be used
+    /// or revalidated later
+    pub fn store(&self, key: CacheKey, response: &ApiResponse) {
+        let directives = CacheControl::parse(&response.headers);
+        let has_validator =
+            response.headers.contains_key(ETAG) || response.headers.contains_key(LAST_MODIFIED);
+        if directives.no_store || (directives.max_age.is_none() && !has_validator) {
+            self.remove(&key);
+            return;
+        }
+
+        let now = Utc::now();
+        let mut entries = self.entries.lock().unwrap_or_else(|err| err.into_inner());
+        if entries.len() >= MAX_ENTRIES && !entries.contains_key(&key) {
+            let oldest = entries
+                .iter()
+                .min_by_key(|(_, entry)| entry.stored_at)
+                .map(|(key, _)| key.clone());
+            if let Some(oldest) = oldest {
+                entries.remove(&oldest);
+                self.delete(&oldest);
+            }
+        }
+        let entry = CacheEntry {
+            response: response.clone(),
+            stored_at: now,
+            fresh_until: directives.fresh_until(now),
+        };
+        self.save(&key, &entry);
+        entries.insert(key, entry);
+    }
+
+    /// Handles `304 Not Modified`, returning the cached response with its freshness updated
+    /// from the new headers. `None` if the response is no longer cached.
+    pub fn revalidated(&self, key: &CacheKey, headers: &HeaderMap) -> Option<ApiResponse> {
+        let mut entries = self.entries.lock().unwrap_or_else(|err| err.into_inner());
+        let entry = entries.get_mut(key)?;
+
+        let now = Utc::now();
+        entry.stored_at = now;
+        if headers.contains_key(CACHE_CONTROL) {
+            entry.fresh_until = CacheControl::parse(headers).fresh_until(now);
+        }
+        for name in [CACHE_CONTROL, ETAG, LAST_MODIFIED] {
+            if let Some(value) = headers.get(&name) {
+                entry.response.headers.insert(name, value.clone());
+            }
+        }
+        self.save(key, entry);
+        Some(entry.response.clone())
+    }
+
+    pub fn remove(&self, key: &CacheKey) {
+        let mut entries = self.entries.lock().unwrap_or_else(|err| err.into_inner());
+        if entries.remove(key).is_some() {
+            self.delete(key);
+        }
+    }
+
+    /// Bytes of the bodies of the cached responses
+    pub fn size(&self) -> usize {
+        let entries = self.entries.lock().unwrap_or_else(|err| err.into_inner());
+        entries
+            .values()
+            .map(|entry| entry.response.body.len())
+            .sum()
+    }
+
+    pub fn clear(&self) {
+        let mut entries = self.entries.lock().unwrap_or_else(|err| err.into_inner());
+        entries.clear();
+        if let Some(dir) = &self.dir {
+            if let Err(err) = fs::remove_dir_all(dir) {
+                if err.kind() != std::io::ErrorKind::NotFound {
+                    tracing::error!("Failed to clear the cache: {err}");
+                }
+            }
+        }
+    }
+
+    /// Requires every cached response to be revalidated before it is used again, e.g. when
+    /// the user refreshes
+    pub fn expire(&self) {
+        self.expire_matching(|_| true);
+    }
+
+    /// Requires the cached responses for the resource at the URL, the resources under it and
+    /// those it is under to be revalidated before they're used again. Called after changing
+    /// the resource, e.g. `PATCH /tag/1` expires `/tag/1`, `/tag/1/...` and `/tag?page=0`.
+    pub fn invalidate(&self, url: &Url) {
+        let path = url.path().trim_end_matches('/');
+        self.expire_matching(|key| {
+            let Ok(cached) = Url::parse(&key.url) else {
+                return true;
+            };
+            let cached_path = cached.path().trim_end_matches('/');
+            cached.origin() == url.origin()
+                && (cached_path == path
+                    || is_under(cached_path, path)
+                    || is_under(path, cached_path))
+        });
+    }
+
+    fn expire_matching(&self, predicate: impl Fn(&CacheKey) -> bool) {
+        let mut entries = self.entries.lock().unwrap_or_else(|err| err.into_inner());
+        for (key, entry) in entries.iter_mut() {
+            if entry.fresh_until.is_some() && predicate(key) {
+                entry.fresh_until = None;
+                self.save(key, entry);
+            }
+        }
+    }
+
+    /// Writes to a temporary file first, so a crash can't leave the entry half written
+    fn save(&self, key: &CacheKey, entry: &CacheEntry) {
+        let Some(dir) = &self.dir else {
+            return;
+        };
+        let Some(saved) = SavedEntry::new(key, entry) else {
+            return;
+        };
+        let path = dir.join(key.file_name());
+        let result = fs::create_dir_all(dir).and_then(|_| {
+            let data = serde_json::to_vec(&saved).map_err(std::io::Error::other)?;
+            let temporary = path.with_extension("json.tmp");
+            fs::write(&temporary, data)?;
+            fs::rename(&temporary, &path)
+        });
+        if let Err(err) = result {
+            tracing::error!("Failed to save cached response: {err}");
+        }
+    }
+
+    fn delete(&self, key: &CacheKey) {
+        if let Some(dir) = &self.dir {
+            let _ = fs::remove_file(dir.join(key.file_name()));
+        }
+    }
+}
+
+/// Whether the path is below the other, e.g. `/tag/1` is under `/tag`
+fn is_under(path: &str, parent: &str) -> bool {
+    path.strip_prefix(parent)
+        .is_some_and(|rest| rest.starts_with('/'))
+}
+
+/// The `Cache-Control` directives which matter to a private client cache
+#[derive(Default)]
+struct CacheControl {
+    no_store: bool,
+    no_cache: bool,
+    max_age: Option<Duration>,
+}
+
+impl CacheControl {
+    fn parse(headers: &HeaderMap) -> Self {
+        let mut directives = Self::default();
+        let values = headers
+            .get_all(CACHE_CONTROL)
+            .iter()
+            .filter_map(|value| value.to_str().ok());
+
+        for directive in values.flat_map(|value| value.split(',')) {
+            let (name, value) = match directive.split_once('=') {
+                Some((name, value)) => (name.trim(), Some(value.trim().trim_matches('"'))),
+                None => (directive.trim(), None),
+            };
+            match name.to_ascii_lowercase().as_str() {
+                "no-store" => directives.no_store = true,
+                "no-cache" => directives.no_cache = true,
+                "max-age" => {
+                    directives.max_age = value
+                        .and_then(|value| value.parse().ok())
+                        .map(Duration::from_secs)
+                }
+                _ => {}
+            }
+        }
+        directives
+    }
+
+    fn fresh_until(&self, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        match self.max_age {
+            Some(max_age) if !self.no_cache && !max_age.is_zero() => {
+                Some(now + chrono::Duration::from_std(max_age).ok()?)
+            }
+            _ => None,
+        }
+    }
+}
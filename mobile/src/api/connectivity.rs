@@ -0,0 +1,44 @@
+use std::sync::Arc;
+
+use tokio::sync::watch;
+
+/// Whether the device is connected to the network, as reported by the platform.
+/// Retries are paused while offline and resume as soon as the connection comes back.
+#[derive(Clone)]
+pub struct Connectivity {
+    online: Arc<watch::Sender<bool>>,
+}
+
+impl Connectivity {
+    pub fn new() -> Self {
+        let (online, _) = watch::channel(true);
+        Self {
+            online: Arc::new(online),
+        }
+    }
+
+    pub fn is_online(&self) -> bool {
+        *self.online.borrow()
+    }
+
+    pub fn set_online(&self, online: bool) {
+        self.online.send_if_modified(|current| {
+            let changed = *current != online;
+            *current = online;
+            changed
+        });
+    }
+
+    /// Resolves once the device is online
+    pub async fn wait_online(&self) {
+        let mut online = self.online.subscribe();
+        // The sender is kept alive by `self`, so this can't fail
+        let _ = online.wait_for(|online| *online).await;
+    }
+}
+
+impl Default for Connectivity {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -1,32 +1,197 @@
+use std::{collections::HashMap, time::Duration};
+
+use chrono::{DateTime, Utc};
+use reqwest::{header::HeaderMap, StatusCode};
 use serde::Deserialize;
 use thiserror::Error;
+use ui::forms::form::SubmitError;
+
+use crate::i18n::t;
 
 pub type Result<T> = std::result::Result<T, ApiClientError>;
 
-#[derive(Error, Debug)]
+/// Errors from the API client.
+///
+/// Holds messages rather than the underlying errors, so it can be cloned into the store and
+/// compared in props.
+#[derive(Error, Clone, Debug, PartialEq)]
 pub enum ApiClientError {
-    #[error("Reqwest error: {0}")]
-    ReqwestError(#[from] reqwest::Error),
-    #[error("Serde error: {0}")]
-    SerdeError(#[from] serde_json::Error),
-    #[error("Auth error: {0}")]
-    AuthError(String),
-    #[error("API error: (Status: {status}): {message}")]
-    ApiError { status: u16, message: String },
+    /// The token is missing, invalid or expired. The user has to log in again.
+    #[error("Unauthorized: {0}")]
+    Unauthorized(String),
+    #[error("Forbidden: {0}")]
+    Forbidden(String),
+    #[error("Not found: {0}")]
+    NotFound(String),
+    /// The request was rejected, with messages for the fields which were invalid
+    #[error("Validation error: {message}")]
+    Validation {
+        message: String,
+        fields: HashMap<String, String>,
+    },
+    #[error("Rate limited: {message}")]
+    RateLimited {
+        message: String,
+        /// How long to wait before trying again, from the `Retry-After` header
+        retry_after: Option<Duration>,
+    },
+    #[error("Server error (Status: {status}): {message}")]
+    Server { status: u16, message: String },
+    /// A status which none of the other variants cover
+    #[error("API error (Status: {status}): {message}")]
+    Api { status: u16, message: String },
+    /// The server couldn't be reached
+    #[error("Offline: {0}")]
+    Offline(String),
+    #[error("Request timed out")]
+    Timeout,
+    #[error("Request error: {0}")]
+    Request(String),
+    /// The response didn't have the expected shape
+    #[error("Invalid response: {0}")]
+    InvalidResponse(String),
 }
 
 impl ApiClientError {
+    /// Creates the error for a response with an unexpected status
+    pub fn from_response(status: StatusCode, headers: &HeaderMap, body: &[u8]) -> Self {
+        let (message, fields) = match serde_json::from_slice::<ApiErrorResponse>(body) {
+            Ok(error) => (error.message, error.fields),
+            Err(_) => (
+                t!("error-unknown-server", status = status.as_u16()),
+                HashMap::new(),
+            ),
+        };
+
+        match status {
+            StatusCode::UNAUTHORIZED => ApiClientError::Unauthorized(message),
+            StatusCode::FORBIDDEN => ApiClientError::Forbidden(message),
+            StatusCode::NOT_FOUND => ApiClientError::NotFound(message),
+            StatusCode::BAD_REQUEST | StatusCode::UNPROCESSABLE_ENTITY => {
+                ApiClientError::Validation { message, fields }
+            }
+            StatusCode::TOO_MANY_REQUESTS => ApiClientError::RateLimited {
+                message,
+                retry_after: retry_after(headers),
+            },
+            status if status.is_server_error() => ApiClientError::Server {
+                status: status.as_u16(),
+                message,
+            },
+            status => ApiClientError::Api {
+                status: status.as_u16(),
+                message,
+            },
+        }
+    }
+
+    /// Message to show to the user
     pub fn message(&self) -> String {
         match self {
-            ApiClientError::ReqwestError(err) => err.to_string(),
-            ApiClientError::SerdeError(err) => err.to_string(),
-            ApiClientError::AuthError(err) => err.to_string(),
-            ApiClientError::ApiError { message, .. } => message.to_string(),
+            ApiClientError::Unauthorized(message)
+            | ApiClientError::Forbidden(message)
+            | ApiClientError::NotFound(message)
+            | ApiClientError::Validation { message, .. }
+            | ApiClientError::Server { message, .. }
+            | ApiClientError::Api { message, .. }
+            | ApiClientError::Request(message) => message.to_string(),
+            ApiClientError::RateLimited {
+                message,
+                retry_after: Some(retry_after),
+            } => t!(
+                "error-rate-limited",
+                message = message.clone(),
+                seconds = retry_after.as_secs()
+            ),
+            ApiClientError::RateLimited { message, .. } => message.to_string(),
+            ApiClientError::Offline(_) => t!("error-offline"),
+            ApiClientError::Timeout => t!("error-timeout"),
+            ApiClientError::InvalidResponse(_) => t!("error-invalid-response"),
+        }
+    }
+
+    /// Status code of the response, if the server responded
+    pub fn status(&self) -> Option<u16> {
+        match self {
+            ApiClientError::Unauthorized(_) => Some(401),
+            ApiClientError::Forbidden(_) => Some(403),
+            ApiClientError::NotFound(_) => Some(404),
+            ApiClientError::RateLimited { .. } => Some(429),
+            ApiClientError::Server { status, .. } | ApiClientError::Api { status, .. } => {
+                Some(*status)
+            }
+            _ => None,
+        }
+    }
+
+    /// Message for an invalid field, for validation errors
+    pub fn field(&self, name: &str) -> Option<&str> {
+        match self {
+            ApiClientError::Validation { fields, .. } => fields.get(name).map(String::as_str),
+            _ => None,
         }
     }
+
+    /// Whether the same request may succeed if it is made again later
+    pub fn is_retryable(&self) -> bool {
+        matches!(
+            self,
+            ApiClientError::RateLimited { .. }
+                | ApiClientError::Server { .. }
+                | ApiClientError::Offline(_)
+                | ApiClientError::Timeout
+        )
+    }
+}
+
+impl From<reqwest::Error> for ApiClientError {
+    fn from(err: reqwest::Error) -> Self {
+        if err.is_timeout() {
+            ApiClientError::Timeout
+        } else if err.is_connect() {
+            ApiClientError::Offline(err.to_string())
+        } else if err.is_decode() {
+            ApiClientError::InvalidResponse(err.to_string())
+        } else {
+            ApiClientError::Request(err.to_string())
+        }
+    }
+}
+
+impl From<serde_json::Error> for ApiClientError {
+    fn from(err: serde_json::Error) -> Self {
+        ApiClientError::InvalidResponse(err.to_string())
+    }
 }
 
 #[derive(Debug, Deserialize)]
 pub struct ApiErrorResponse {
     pub message: String,
+    /// Messages for invalid fields, keyed by field name
+    #[serde(default, alias = "errors")]
+    pub fields: HashMap<String, String>,
+}
+
+/// Reads the `Retry-After` header, given either in seconds or as an HTTP date
+pub(super) fn retry_after(headers: &HeaderMap) -> Option<Duration> {
+    let value = headers.get("Retry-After")?.to_str().ok()?.trim();
+    if let Ok(seconds) = value.parse::<u64>() {
+        return Some(Duration::from_secs(seconds));
+    }
+
+    let date = DateTime::parse_from_rfc2822(value).ok()?;
+    (date.with_timezone(&Utc) - Utc::now()).to_std().ok()
+}
+
+/// Shows the messages for invalid fields on the fields of the form
+impl From<ApiClientError> for SubmitError {
+    fn from(err: ApiClientError) -> Self {
+        match err {
+            ApiClientError::Validation { message, fields } => SubmitError {
+                message: fields.is_empty().then_some(message),
+                fields,
+            },
+            err => SubmitError::new(err.message()),
+        }
+    }
 }
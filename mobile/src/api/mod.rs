@@ -1,51 +1,117 @@
 use reqwest::{
-    header::{HeaderMap, HeaderValue},
+    header::{HeaderMap, HeaderValue, IF_MODIFIED_SINCE, IF_NONE_MATCH},
     Method, StatusCode,
 };
 use serde::de::DeserializeOwned;
 use std::{
+    path::PathBuf,
     sync::{Arc, Mutex},
-    time::Duration,
 };
 
-use crate::api::error::ApiErrorResponse;
 use types::{feed::*, index::*, list::*, search::*, tag::*, user::*};
 
+mod cache;
+mod connectivity;
 mod error;
+mod retry;
+#[cfg(test)]
+mod test_server;
 pub mod types;
 
+use cache::{ApiResponse, CacheKey, Lookup, ResponseCache};
+pub use connectivity::Connectivity;
 pub use error::ApiClientError;
 use error::Result;
+pub use retry::RetryPolicy;
+use retry::RETRY_STATUSES;
 
 const USER_AGENT: &str = concat!("bind-app/", env!("CARGO_PKG_VERSION"));
 
 #[derive(Clone)]
 pub struct ApiClient {
     client: reqwest::Client,
-    pub base_url: String,
+    base_url: Arc<Mutex<String>>,
     token: Arc<Mutex<Option<String>>>,
+    retry: RetryPolicy,
+    connectivity: Connectivity,
+    cache: ResponseCache,
 }
 
 impl ApiClient {
     pub fn new(base_url: &'static str) -> Self {
-        let base_url = base_url.to_string();
-
         let mut headers = HeaderMap::new();
         headers.insert("Accept", HeaderValue::from_static("application/json"));
         headers.insert("Content-Type", HeaderValue::from_static("application/json"));
         headers.insert("User-Agent", HeaderValue::from_static(USER_AGENT));
 
+        // Timeouts are set per attempt, from the retry policy.
+        // Redirects aren't followed, as OAuth authorization returns the provider's URL as one.
         let client_builder = reqwest::ClientBuilder::new()
             .default_headers(headers)
-            .timeout(Duration::from_secs(30));
+            .redirect(reqwest::redirect::Policy::none());
 
         Self {
             client: client_builder.build().unwrap(),
-            base_url,
+            base_url: Arc::new(Mutex::new(base_url.to_string())),
             token: Arc::new(Mutex::new(None)),
+            retry: RetryPolicy::default(),
+            connectivity: Connectivity::new(),
+            cache: ResponseCache::default(),
+        }
+    }
+
+    /// Returns a client which shares this client's token, connectivity and cache, but retries
+    /// requests according to `retry`. Used to override the policy for a single request:
+    ///
+    /// ```ignore
+    /// api.with_retry(RetryPolicy::none()).get_item(id).await
+    /// ```
+    pub fn with_retry(&self, retry: RetryPolicy) -> Self {
+        Self {
+            retry,
+            ..self.clone()
         }
     }
 
+    /// Keeps cached responses in the directory, so they're used after the app is restarted
+    pub fn with_cache_dir(mut self, dir: PathBuf) -> Self {
+        self.cache = ResponseCache::persistent(dir);
+        self
+    }
+
+    pub fn connectivity(&self) -> &Connectivity {
+        &self.connectivity
+    }
+
+    /// Bytes of responses kept in the cache
+    pub fn cache_size(&self) -> usize {
+        self.cache.size()
+    }
+
+    /// Forgets every cached response
+    pub fn clear_cache(&self) {
+        self.cache.clear();
+    }
+
+    /// Makes the cached responses be revalidated with the server before they're used again
+    pub fn expire_cache(&self) {
+        self.cache.expire();
+    }
+
+    /// Server the requests are made to
+    pub fn base_url(&self) -> String {
+        self.base_url
+            .lock()
+            .unwrap_or_else(|err| err.into_inner())
+            .clone()
+    }
+
+    /// Makes requests to another server from now on, e.g. the self-hosted server of an account
+    pub fn set_base_url(&self, base_url: &str) {
+        *self.base_url.lock().unwrap_or_else(|err| err.into_inner()) =
+            base_url.trim_end_matches('/').to_string();
+    }
+
     pub fn get_token(&self) -> Option<String> {
         match self.token.get_cloned() {
             Ok(token) => token,
@@ -69,7 +135,7 @@ impl ApiClient {
     fn make_request(&self, method: Method, path: &str) -> reqwest::RequestBuilder {
         let mut request = self.client.request(
             method,
-            format!("{}/{}", self.base_url, path.trim_start_matches("/")),
+            format!("{}/{}", self.base_url(), path.trim_start_matches("/")),
         );
         if let Some(token) = &self.get_token() {
             request = request.bearer_auth(token);
@@ -77,24 +143,146 @@ impl ApiClient {
         request
     }
 
+    /// Sends the request and reads the response.
+    /// `GET` responses are cached, and served from the cache while fresh or after the server
+    /// confirms they haven't changed with `304 Not Modified`. Successful changes expire the
+    /// responses for the resource they changed.
+    async fn send(&self, request: reqwest::RequestBuilder) -> Result<ApiResponse> {
+        let request = request.timeout(self.retry.timeout).build()?;
+        if request.method() != Method::GET {
+            let url = request.url().clone();
+            let response = self.read(self.execute(request).await?).await?;
+            if response.status.is_success() {
+                self.cache.invalidate(&url);
+            }
+            return Ok(response);
+        }
+
+        let key = CacheKey::new(request.url(), self.get_token().as_deref());
+        let lookup = self.cache.lookup(&key);
+        if let Lookup::Fresh(response) = lookup {
+            return Ok(response);
+        }
+
+        // Without conditions, in case the cached response is evicted before `304` arrives
+        let fallback = request.try_clone();
+        let mut request = request;
+        if let Lookup::Revalidate {
+            etag,
+            last_modified,
+        } = lookup
+        {
+            let headers = request.headers_mut();
+            if let Some(etag) = etag {
+                headers.insert(IF_NONE_MATCH, etag);
+            }
+            if let Some(last_modified) = last_modified {
+                headers.insert(IF_MODIFIED_SINCE, last_modified);
+            }
+        }
+
+        let mut response = self.read(self.execute(request).await?).await?;
+        if response.status == StatusCode::NOT_MODIFIED {
+            match (self.cache.revalidated(&key, &response.headers), fallback) {
+                (Some(cached), _) => return Ok(cached),
+                (None, Some(fallback)) => {
+                    response = self.read(self.execute(fallback).await?).await?;
+                }
+                (None, None) => {}
+            }
+        }
+
+        if response.status == StatusCode::OK {
+            self.cache.store(key, &response);
+        }
+        Ok(response)
+    }
+
+    async fn read(&self, response: reqwest::Response) -> Result<ApiResponse> {
+        Ok(ApiResponse {
+            status: response.status(),
+            headers: response.headers().clone(),
+            body: response.bytes().await?.to_vec(),
+        })
+    }
+
+    /// Executes the request, retrying failures according to the retry policy.
+    /// Retries are paused while offline, without counting against the policy's retries.
+    async fn execute(&self, request: reqwest::Request) -> Result<reqwest::Response> {
+        let mut attempt = 0;
+
+        loop {
+            // Requests with streaming bodies can't be cloned, so they can't be retried
+            let Some(next) = request.try_clone() else {
+                return Ok(self.client.execute(request).await?);
+            };
+            let result = self.client.execute(next).await;
+
+            let can_retry = self.retry.should_retry(request.method(), attempt);
+            let delay = match &result {
+                Ok(response) if can_retry && RETRY_STATUSES.contains(&response.status()) => Some(
+                    self.retry
+                        .delay(attempt, error::retry_after(response.headers())),
+                ),
+                Err(err) if can_retry && (err.is_connect() || err.is_timeout()) => {
+                    Some(self.retry.backoff(attempt))
+                }
+                _ => None,
+            };
+            let Some(delay) = delay else {
+                return Ok(result?);
+            };
+
+            if !self.connectivity.is_online() {
+                tracing::warn!(
+                    "Offline, waiting to retry {} {}",
+                    request.method(),
+                    request.url()
+                );
+                let reconnected = tokio::time::timeout(
+                    self.retry.offline_timeout,
+                    self.connectivity.wait_online(),
+                )
+                .await;
+                if reconnected.is_err() {
+                    return Ok(result?);
+                }
+                continue;
+            }
+
+            attempt += 1;
+            tracing::warn!(
+                "Retrying {} {} in {}ms (attempt {attempt})",
+                request.method(),
+                request.url(),
+                delay.as_millis()
+            );
+            tokio::time::sleep(delay).await;
+        }
+    }
+
     /// Handles a response from the API, returning the deserialized response if the status code matches.
-    /// Otherwise, returns an error with the status code and message.
-    async fn handle_response<T: DeserializeOwned>(
+    /// Otherwise, returns the error matching the status code.
+    fn handle_response<T: DeserializeOwned>(
         &self,
-        response: reqwest::Response,
+        response: ApiResponse,
         status_code: StatusCode,
     ) -> Result<T> {
-        if response.status() == status_code {
-            return Ok(response.json::<T>().await?);
+        if response.status != status_code {
+            return Err(ApiClientError::from_response(
+                response.status,
+                &response.headers,
+                &response.body,
+            ));
         }
 
-        let status = response.status().as_u16();
-        let message = response
-            .json::<ApiErrorResponse>()
-            .await
-            .map(|error| error.message)
-            .unwrap_or_else(|_| format!("Unknown error from server ({})", status));
-        Err(ApiClientError::ApiError { status, message })
+        // Responses without content, e.g. `204 No Content`, deserialize into `()`
+        let body: &[u8] = if response.body.is_empty() {
+            b"null"
+        } else {
+            &response.body
+        };
+        Ok(serde_json::from_slice(body)?)
     }
 
     // ---------------
@@ -103,41 +291,53 @@ impl ApiClient {
 
     /// `GET /feed/{id}`: Get a feed by ID
     pub async fn get_feed(&self, id: i32) -> Result<Feed> {
-        let response = self
-            .make_request(Method::GET, &format!("/feed/{}", id))
-            .send()
-            .await?;
+        let request = self.make_request(Method::GET, &format!("/feed/{}", id));
+        let response = self.send(request).await?;
 
         self.handle_response(response, reqwest::StatusCode::OK)
-            .await
     }
 
     /// `PUT /feed`: Create a new feed subscription.
     pub async fn create_feed(&self, link: &str) -> Result<Feed> {
-        let response = self
+        let request = self
             .make_request(Method::PUT, "/feed")
             .json(&CreateFeedRequest {
                 link: link.to_string(),
-            })
-            .send()
-            .await?;
+            });
+        let response = self.send(request).await?;
 
         self.handle_response(response, reqwest::StatusCode::CREATED)
-            .await
     }
 
     /// `POST /feed/discover`: Discover feeds from a website URL
     pub async fn discover_feeds(&self, link: &str) -> Result<Vec<FeedInformation>> {
-        let response = self
-            .make_request(Method::POST, "/feed/discover")
-            .json(&DiscoverFeedsRequest {
-                link: link.to_string(),
-            })
-            .send()
-            .await?;
+        let request =
+            self.make_request(Method::POST, "/feed/discover")
+                .json(&DiscoverFeedsRequest {
+                    link: link.to_string(),
+                });
+        let response = self.send(request).await?;
+
+        self.handle_response(response, reqwest::StatusCode::OK)
+    }
+
+    /// `DELETE /feed/{id}`: Unsubscribe from a feed
+    pub async fn delete_feed(&self, id: i32) -> Result<()> {
+        let request = self.make_request(Method::DELETE, &format!("/feed/{}", id));
+        let response = self.send(request).await?;
+
+        self.handle_response(response, reqwest::StatusCode::NO_CONTENT)
+    }
+
+    /// `GET /feed/{id}/item`: List the items of a feed, most recently published first
+    pub async fn list_feed_items(&self, id: i32, page: usize) -> Result<Vec<FeedItem>> {
+        let request = self.make_request(
+            Method::GET,
+            &format!("/feed/{}/item?page={}&limit=20", id, page),
+        );
+        let response = self.send(request).await?;
 
         self.handle_response(response, reqwest::StatusCode::OK)
-            .await
     }
 
     // ---------------
@@ -146,36 +346,26 @@ impl ApiClient {
 
     /// `GET /index/{id}`: Get an index
     pub async fn get_index(&self, id: i32) -> Result<UserIndex> {
-        let response = self
-            .make_request(Method::GET, &format!("/index/{}", id))
-            .send()
-            .await?;
+        let request = self.make_request(Method::GET, &format!("/index/{}", id));
+        let response = self.send(request).await?;
 
         self.handle_response(response, reqwest::StatusCode::OK)
-            .await
     }
 
     /// `GET /index`: List all indexes
     pub async fn list_indexes(&self, page: usize) -> Result<Vec<UserIndex>> {
-        let response = self
-            .make_request(Method::GET, &format!("/index?page={}&limit=20", page))
-            .send()
-            .await?;
+        let request = self.make_request(Method::GET, &format!("/index?page={}&limit=20", page));
+        let response = self.send(request).await?;
 
         self.handle_response(response, reqwest::StatusCode::OK)
-            .await
     }
 
     /// `PUT /index`: Create a new index
     pub async fn create_index(&self, index_data: &CreateIndexRequest) -> Result<UserIndex> {
-        let response = self
-            .make_request(Method::PUT, "/index")
-            .json(index_data)
-            .send()
-            .await?;
+        let request = self.make_request(Method::PUT, "/index").json(index_data);
+        let response = self.send(request).await?;
 
         self.handle_response(response, reqwest::StatusCode::CREATED)
-            .await
     }
 
     /// `PATCH /index/{id}`: Update an index
@@ -184,25 +374,20 @@ impl ApiClient {
         id: i32,
         index_data: &UpdateIndexRequest,
     ) -> Result<UserIndex> {
-        let response = self
+        let request = self
             .make_request(Method::PATCH, &format!("/index/{}", id))
-            .json(index_data)
-            .send()
-            .await?;
+            .json(index_data);
+        let response = self.send(request).await?;
 
         self.handle_response(response, reqwest::StatusCode::OK)
-            .await
     }
 
     /// `DELETE /index/{id}`: Delete an index
     pub async fn delete_index(&self, id: i32) -> Result<()> {
-        let response = self
-            .make_request(Method::DELETE, &format!("/index/{}", id))
-            .send()
-            .await?;
+        let request = self.make_request(Method::DELETE, &format!("/index/{}", id));
+        let response = self.send(request).await?;
 
         self.handle_response(response, reqwest::StatusCode::NO_CONTENT)
-            .await
     }
 
     // ---------------
@@ -210,14 +395,11 @@ impl ApiClient {
     // ---------------
 
     /// `GET /item/{id}`: Get a feed item by ID
-    pub async fn get_item(&self, id: i32) -> Result<FeedItem> {
-        let response = self
-            .make_request(Method::GET, &format!("/item/{}", id))
-            .send()
-            .await?;
+    pub async fn get_item(&self, id: i64) -> Result<FeedItem> {
+        let request = self.make_request(Method::GET, &format!("/item/{}", id));
+        let response = self.send(request).await?;
 
         self.handle_response(response, reqwest::StatusCode::OK)
-            .await
     }
 
     // ---------------
@@ -226,59 +408,44 @@ impl ApiClient {
 
     /// `GET /list/{id}`: Get a list by ID
     pub async fn get_list(&self, id: i32) -> Result<UserList> {
-        let response = self
-            .make_request(Method::GET, &format!("/list/{}", id))
-            .send()
-            .await?;
+        let request = self.make_request(Method::GET, &format!("/list/{}", id));
+        let response = self.send(request).await?;
 
         self.handle_response(response, reqwest::StatusCode::OK)
-            .await
     }
 
     /// `GET /list`: List all lists
     pub async fn list_lists(&self, page: usize) -> Result<Vec<UserList>> {
-        let response = self
-            .make_request(Method::GET, &format!("/list?page={}&limit=20", page))
-            .send()
-            .await?;
+        let request = self.make_request(Method::GET, &format!("/list?page={}&limit=20", page));
+        let response = self.send(request).await?;
 
         self.handle_response(response, reqwest::StatusCode::OK)
-            .await
     }
 
     /// `PUT /list`: Create a new list
     pub async fn create_list(&self, list_data: &CreateListRequest) -> Result<UserList> {
-        let response = self
-            .make_request(Method::PUT, "/list")
-            .json(list_data)
-            .send()
-            .await?;
+        let request = self.make_request(Method::PUT, "/list").json(list_data);
+        let response = self.send(request).await?;
 
         self.handle_response(response, reqwest::StatusCode::CREATED)
-            .await
     }
 
     /// `PATCH /list/{id}`: Update a list
     pub async fn update_list(&self, id: u32, list_data: &UpdateListRequest) -> Result<UserList> {
-        let response = self
+        let request = self
             .make_request(Method::PATCH, &format!("/list/{}", id))
-            .json(list_data)
-            .send()
-            .await?;
+            .json(list_data);
+        let response = self.send(request).await?;
 
         self.handle_response(response, reqwest::StatusCode::OK)
-            .await
     }
 
     /// `DELETE /list/{id}`: Delete a list
     pub async fn delete_list(&self, id: i32) -> Result<()> {
-        let response = self
-            .make_request(Method::DELETE, &format!("/list/{}", id))
-            .send()
-            .await?;
+        let request = self.make_request(Method::DELETE, &format!("/list/{}", id));
+        let response = self.send(request).await?;
 
         self.handle_response(response, reqwest::StatusCode::NO_CONTENT)
-            .await
     }
 
     // ---------------
@@ -287,24 +454,22 @@ impl ApiClient {
 
     /// `GET /list/{list_id}/item/{item_id}`: Get a list item by ID
     pub async fn get_list_item(&self, list_id: i32, item_id: i64) -> Result<UserListItem> {
-        let response = self
-            .make_request(Method::GET, &format!("/list/{}/item/{}", list_id, item_id))
-            .send()
-            .await?;
+        let request =
+            self.make_request(Method::GET, &format!("/list/{}/item/{}", list_id, item_id));
+        let response = self.send(request).await?;
 
         self.handle_response(response, reqwest::StatusCode::OK)
-            .await
     }
 
     /// `GET /list/{list_id}/item`: List all list items
     pub async fn list_list_items(&self, list_id: i32, page: usize) -> Result<Vec<UserListItem>> {
-        let response = self
-            .make_request(Method::GET, &format!("/list/item?page={}&limit=20", page))
-            .send()
-            .await?;
+        let request = self.make_request(
+            Method::GET,
+            &format!("/list/{}/item?page={}&limit=20", list_id, page),
+        );
+        let response = self.send(request).await?;
 
         self.handle_response(response, reqwest::StatusCode::OK)
-            .await
     }
 
     /// `PUT /list/{list_id}/item`: Create a new list item
@@ -313,28 +478,23 @@ impl ApiClient {
         list_id: i32,
         item_data: &CreateListItemRequest,
     ) -> Result<UserListItem> {
-        let response = self
+        let request = self
             .make_request(Method::PUT, &format!("/list/{}/item", list_id))
-            .json(item_data)
-            .send()
-            .await?;
+            .json(item_data);
+        let response = self.send(request).await?;
 
         self.handle_response(response, reqwest::StatusCode::CREATED)
-            .await
     }
 
     /// `DELETE /list/{list_id}/item/{item_id}`: Delete a list item
     pub async fn delete_list_item(&self, list_id: i32, item_id: i64) -> Result<()> {
-        let response = self
-            .make_request(
-                Method::DELETE,
-                &format!("/list/{}/item/{}", list_id, item_id),
-            )
-            .send()
-            .await?;
+        let request = self.make_request(
+            Method::DELETE,
+            &format!("/list/{}/item/{}", list_id, item_id),
+        );
+        let response = self.send(request).await?;
 
         self.handle_response(response, reqwest::StatusCode::NO_CONTENT)
-            .await
     }
 
     // ---------------
@@ -343,14 +503,12 @@ impl ApiClient {
 
     /// `POST /search`: Search for feeds
     pub async fn search(&self, search_data: &SearchRequest, page: usize) -> Result<Vec<Feed>> {
-        let response = self
+        let request = self
             .make_request(Method::POST, &format!("/search?page={}&limit=20", page))
-            .json(search_data)
-            .send()
-            .await?;
+            .json(search_data);
+        let response = self.send(request).await?;
 
         self.handle_response(response, reqwest::StatusCode::OK)
-            .await
     }
 
     // ---------------
@@ -359,48 +517,36 @@ impl ApiClient {
 
     /// `GET /tag`: List all tags
     pub async fn list_tags(&self, page: usize) -> Result<Vec<UserTag>> {
-        let response = self
-            .make_request(Method::GET, &format!("/tag?page={}&limit=20", page))
-            .send()
-            .await?;
+        let request = self.make_request(Method::GET, &format!("/tag?page={}&limit=20", page));
+        let response = self.send(request).await?;
 
         self.handle_response(response, reqwest::StatusCode::OK)
-            .await
     }
 
     /// `PUT /tag`: Create a new tag
     pub async fn create_tag(&self, tag_data: &CreateTagRequest) -> Result<UserTag> {
-        let response = self
-            .make_request(Method::PUT, "/tag")
-            .json(tag_data)
-            .send()
-            .await?;
+        let request = self.make_request(Method::PUT, "/tag").json(tag_data);
+        let response = self.send(request).await?;
 
         self.handle_response(response, reqwest::StatusCode::CREATED)
-            .await
     }
 
     /// `PATCH /tag/{id}`: Update a tag
     pub async fn update_tag(&self, id: i32, tag_data: &UpdateTagRequest) -> Result<UserTag> {
-        let response = self
+        let request = self
             .make_request(Method::PATCH, &format!("/tag/{}", id))
-            .json(tag_data)
-            .send()
-            .await?;
+            .json(tag_data);
+        let response = self.send(request).await?;
 
         self.handle_response(response, reqwest::StatusCode::OK)
-            .await
     }
 
     /// `DELETE /tag/{id}`: Delete a tag
     pub async fn delete_tag(&self, id: i32) -> Result<()> {
-        let response = self
-            .make_request(Method::DELETE, &format!("/tag/{}", id))
-            .send()
-            .await?;
+        let request = self.make_request(Method::DELETE, &format!("/tag/{}", id));
+        let response = self.send(request).await?;
 
         self.handle_response(response, reqwest::StatusCode::NO_CONTENT)
-            .await
     }
 
     // ---------------
@@ -409,39 +555,31 @@ impl ApiClient {
 
     /// `GET /user/history/{id}`: Get a history item by ID
     pub async fn get_history(&self, id: i64) -> Result<HistoryItem> {
-        let response = self
-            .make_request(Method::GET, &format!("/user/history/{}", id))
-            .send()
-            .await?;
+        let request = self.make_request(Method::GET, &format!("/user/history/{}", id));
+        let response = self.send(request).await?;
 
         self.handle_response(response, reqwest::StatusCode::OK)
-            .await
     }
 
     /// `GET /user/history`: List all history items for the current user
     pub async fn list_history(&self, page: usize) -> Result<Vec<HistoryItem>> {
-        let response = self
-            .make_request(
-                Method::GET,
-                &format!("/user/history?page={}&limit=20", page),
-            )
-            .send()
-            .await?;
+        let request = self.make_request(
+            Method::GET,
+            &format!("/user/history?page={}&limit=20", page),
+        );
+        let response = self.send(request).await?;
 
         self.handle_response(response, reqwest::StatusCode::OK)
-            .await
     }
 
     /// `PUT /user/history`: Create a new history item for the current user
     pub async fn create_history(&self, item_data: &CreateHistoryRequest) -> Result<HistoryItem> {
-        let response = self
+        let request = self
             .make_request(Method::PUT, "/user/history")
-            .json(item_data)
-            .send()
-            .await?;
+            .json(item_data);
+        let response = self.send(request).await?;
 
         self.handle_response(response, reqwest::StatusCode::CREATED)
-            .await
     }
 
     /// `PATCH /user/history/{id}`: Update a history item
@@ -450,25 +588,20 @@ impl ApiClient {
         id: i64,
         item_data: &UpdateHistoryRequest,
     ) -> Result<HistoryItem> {
-        let response = self
+        let request = self
             .make_request(Method::PATCH, &format!("/user/history/{}", id))
-            .json(item_data)
-            .send()
-            .await?;
+            .json(item_data);
+        let response = self.send(request).await?;
 
         self.handle_response(response, reqwest::StatusCode::OK)
-            .await
     }
 
     /// `DELETE /user/history/{id}`: Delete a history item
     pub async fn delete_history(&self, id: i64) -> Result<()> {
-        let response = self
-            .make_request(Method::DELETE, &format!("/user/history/{}", id))
-            .send()
-            .await?;
+        let request = self.make_request(Method::DELETE, &format!("/user/history/{}", id));
+        let response = self.send(request).await?;
 
         self.handle_response(response, reqwest::StatusCode::NO_CONTENT)
-            .await
     }
 
     // ---------------
@@ -481,30 +614,38 @@ impl ApiClient {
         &self,
         registration_data: &UserRegisterRequest,
     ) -> Result<UserRegisterResponse> {
-        let response = self
+        let request = self
             .make_request(Method::POST, "/user/email/register")
-            .json(registration_data)
-            .send()
-            .await?;
+            .json(registration_data);
+        let response = self.send(request).await?;
 
         self.handle_response(response, reqwest::StatusCode::OK)
-            .await
     }
 
     /// `POST /user/email/verify`: Sends an email to the user with a verification code
     /// which must be used during registration.
     /// This endpoint does not require prior authentication.
     pub async fn send_email_verification(&self, email: &str) -> Result<()> {
-        let response = self
+        let request = self
             .make_request(reqwest::Method::POST, "/user/email/verify")
             .json(&EmailVerificationRequest {
                 email: email.to_string(),
-            })
-            .send()
-            .await?;
+            });
+        let response = self.send(request).await?;
 
         self.handle_response(response, reqwest::StatusCode::OK)
-            .await
+    }
+
+    /// `GET /user/email/username-available`: Whether no user has the username yet.
+    /// This endpoint does not require prior authentication.
+    pub async fn username_available(&self, username: &str) -> Result<bool> {
+        let request = self
+            .make_request(Method::GET, "/user/email/username-available")
+            .query(&[("username", username)]);
+        let response = self.send(request).await?;
+
+        self.handle_response::<UsernameAvailabilityResponse>(response, reqwest::StatusCode::OK)
+            .map(|response| response.available)
     }
 
     /// `POST /user/email/login`: Login with email/username and password.
@@ -516,32 +657,28 @@ impl ApiClient {
     ) -> Result<UserLoginResponse> {
         let is_email = email_or_username.contains('@');
 
-        let response = self
+        let request = self
             .make_request(reqwest::Method::POST, "/user/email/login")
             .json(&UserLoginRequest {
                 email: is_email.then_some(email_or_username.to_string()),
                 username: (!is_email).then_some(email_or_username.to_string()),
                 password: password.to_string(),
-            })
-            .send()
-            .await?;
+            });
+        let response = self.send(request).await?;
 
         self.handle_response(response, reqwest::StatusCode::OK)
-            .await
     }
 
     /// `POST /user/email/send-password-reset-code`: Send reset password email code
     pub async fn send_password_reset_code(&self, email: &str) -> Result<()> {
-        let response = self
+        let request = self
             .make_request(Method::POST, "/user/email/send-password-reset-code")
             .json(&SendPasswordCodeRequest {
                 email: email.to_string(),
-            })
-            .send()
-            .await?;
+            });
+        let response = self.send(request).await?;
 
         self.handle_response(response, reqwest::StatusCode::OK)
-            .await
     }
 
     /// `POST /user/email/reset-password`: Reset user's password
@@ -551,14 +688,25 @@ impl ApiClient {
             code: code.to_string(),
             new_password: new_password.to_string(),
         };
-        let response = self
+        let request = self
             .make_request(Method::POST, "/user/email/reset-password")
-            .json(&request)
-            .send()
-            .await?;
+            .json(&request);
+        let response = self.send(request).await?;
+
+        self.handle_response(response, reqwest::StatusCode::OK)
+    }
+
+    /// `POST /user/email/change-password`: Change the logged in user's password
+    pub async fn change_password(&self, current_password: &str, new_password: &str) -> Result<()> {
+        let request = self
+            .make_request(Method::POST, "/user/email/change-password")
+            .json(&ChangePasswordRequest {
+                current_password: current_password.to_string(),
+                new_password: new_password.to_string(),
+            });
+        let response = self.send(request).await?;
 
         self.handle_response(response, reqwest::StatusCode::OK)
-            .await
     }
 
     // ---------------
@@ -566,28 +714,229 @@ impl ApiClient {
     // ---------------
 
     /// `POST /user/oauth/authorize`: Get redirect URL to OAuth provider for authorization
-    pub async fn authorize_user(&self, provider: &str) -> Result<String> {
-        let response = self
+    pub async fn authorize_user(
+        &self,
+        provider: OAuthProvider,
+        state: &str,
+        code_challenge: &str,
+    ) -> Result<String> {
+        let request = self
             .make_request(Method::POST, "/user/oauth/authorize")
             .json(&AuthorizeRequest {
-                // TODO: use enum
-                provider: provider.to_string(),
-                client: OAuthRedirectClient::Android,
-            })
-            .send()
-            .await?;
-
-        if response.status() != reqwest::StatusCode::TEMPORARY_REDIRECT {
-            return self
-                .handle_response(response, reqwest::StatusCode::TEMPORARY_REDIRECT)
-                .await;
+                provider,
+                client: OAuthRedirectClient::current(),
+                state: state.to_string(),
+                code_challenge: code_challenge.to_string(),
+                code_challenge_method: "S256".to_string(),
+            });
+        let response = self.send(request).await?;
+
+        if response.status != reqwest::StatusCode::TEMPORARY_REDIRECT {
+            return self.handle_response(response, reqwest::StatusCode::TEMPORARY_REDIRECT);
         }
 
         let location = response
-            .headers()
+            .headers
             .get("Location")
-            .expect("No location header");
-        let location = location.to_str().expect("Invalid location header");
+            .ok_or_else(|| ApiClientError::InvalidResponse("No location header".to_string()))?;
+        let location = location
+            .to_str()
+            .map_err(|_| ApiClientError::InvalidResponse("Invalid location header".to_string()))?;
         Ok(location.to_string())
     }
+
+    /// `POST /user/oauth/token`: Exchange the code from the OAuth callback for a token
+    pub async fn exchange_oauth_code(
+        &self,
+        provider: OAuthProvider,
+        code: &str,
+        code_verifier: &str,
+    ) -> Result<UserLoginResponse> {
+        let request =
+            self.make_request(Method::POST, "/user/oauth/token")
+                .json(&OAuthTokenRequest {
+                    provider,
+                    code: code.to_string(),
+                    code_verifier: code_verifier.to_string(),
+                });
+        let response = self.send(request).await?;
+
+        self.handle_response(response, reqwest::StatusCode::OK)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, Instant};
+
+    use super::*;
+    use test_server::{TestResponse, TestServer};
+
+    fn client(server: &TestServer, retry: RetryPolicy) -> ApiClient {
+        let api = ApiClient::new("http://localhost").with_retry(retry);
+        api.set_base_url(&server.url);
+        api
+    }
+
+    fn fast_retries() -> RetryPolicy {
+        RetryPolicy {
+            base_delay: Duration::from_millis(1),
+            max_delay: Duration::from_millis(10),
+            max_retry_after: Duration::from_millis(200),
+            ..Default::default()
+        }
+    }
+
+    async fn request(api: &ApiClient, method: Method, path: &str) -> ApiResponse {
+        api.send(api.make_request(method, path)).await.unwrap()
+    }
+
+    #[test]
+    fn honors_retry_after_up_to_its_cap() {
+        let retry = RetryPolicy::default();
+        assert_eq!(
+            retry.delay(0, Some(Duration::from_secs(60))),
+            Duration::from_secs(60)
+        );
+        assert_eq!(
+            retry.delay(0, Some(Duration::from_secs(3600))),
+            retry.max_retry_after
+        );
+        assert!(retry.delay(2, None) <= retry.max_delay);
+    }
+
+    #[tokio::test]
+    async fn retries_server_errors_until_success() {
+        let server = TestServer::start(|_, index| match index {
+            0 => TestResponse::new(503),
+            1 => TestResponse::new(502),
+            _ => TestResponse::new(200).body("{}"),
+        })
+        .await;
+        let api = client(&server, fast_retries());
+
+        let response = request(&api, Method::GET, "/feed/1").await;
+        assert_eq!(response.status, StatusCode::OK);
+        assert_eq!(server.requests().len(), 3);
+    }
+
+    #[tokio::test]
+    async fn gives_up_after_max_retries() {
+        let server = TestServer::start(|_, _| TestResponse::new(503)).await;
+        let api = client(&server, fast_retries().with_max_retries(2));
+
+        let response = request(&api, Method::GET, "/feed/1").await;
+        assert_eq!(response.status, StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(server.requests().len(), 3);
+    }
+
+    #[tokio::test]
+    async fn does_not_retry_other_failures_or_methods() {
+        let server = TestServer::start(|request, _| match request.path.as_str() {
+            "/missing" => TestResponse::new(404),
+            _ => TestResponse::new(503),
+        })
+        .await;
+        let api = client(&server, fast_retries());
+
+        let response = request(&api, Method::GET, "/missing").await;
+        assert_eq!(response.status, StatusCode::NOT_FOUND);
+        // `PUT` creates resources, so it isn't made twice
+        let response = request(&api, Method::PUT, "/user/history").await;
+        assert_eq!(response.status, StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(server.requests().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn retries_after_long_retry_after() {
+        // Longer than both `max_delay` and `max_retry_after`, so it is shortened
+        let server = TestServer::start(|_, index| match index {
+            0 => TestResponse::new(429).header("Retry-After", "3600"),
+            _ => TestResponse::new(200).body("{}"),
+        })
+        .await;
+        let api = client(&server, fast_retries());
+
+        let started = Instant::now();
+        let response = request(&api, Method::GET, "/feed/1").await;
+        assert_eq!(response.status, StatusCode::OK);
+        assert_eq!(server.requests().len(), 2);
+        assert!(started.elapsed() >= Duration::from_millis(200));
+        assert!(started.elapsed() < Duration::from_secs(5));
+    }
+
+    /// Serves every resource with an `ETag`, fresh for a minute, and confirms it hasn't changed
+    /// when asked
+    async fn cached_server() -> TestServer {
+        TestServer::start(|request, _| match request.method.as_str() {
+            "GET" if request.headers.contains_key("if-none-match") => TestResponse::new(304),
+            "GET" => TestResponse::new(200)
+                .header("Cache-Control", "max-age=60")
+                .header("ETag", "\"v1\"")
+                .body("{}"),
+            _ => TestResponse::new(200).body("{}"),
+        })
+        .await
+    }
+
+    fn requested(server: &TestServer) -> Vec<String> {
+        server
+            .requests()
+            .iter()
+            .map(|request| format!("{} {}", request.method, request.path))
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn changes_expire_only_related_responses() {
+        let server = cached_server().await;
+        let api = client(&server, fast_retries());
+
+        for path in ["/tag/1", "/tag?page=0", "/feed/1", "/tag/2"] {
+            request(&api, Method::GET, path).await;
+            // Fresh, so served from the cache
+            request(&api, Method::GET, path).await;
+        }
+        assert_eq!(server.requests().len(), 4);
+
+        request(&api, Method::PATCH, "/tag/1").await;
+        for path in ["/tag/1", "/tag?page=0", "/feed/1", "/tag/2"] {
+            let response = request(&api, Method::GET, path).await;
+            assert_eq!(response.status, StatusCode::OK);
+        }
+
+        // The tag and the list of tags are revalidated, the other resources are still fresh
+        assert_eq!(
+            requested(&server)[4..],
+            ["PATCH /tag/1", "GET /tag/1", "GET /tag?page=0"]
+        );
+        assert!(server.requests()[5].headers.contains_key("if-none-match"));
+    }
+
+    #[tokio::test]
+    async fn keeps_responses_between_launches() {
+        let server = cached_server().await;
+        let dir = std::env::temp_dir().join(format!("bind-api-cache-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+
+        let api = client(&server, fast_retries()).with_cache_dir(dir.clone());
+        request(&api, Method::GET, "/feed/1").await;
+        api.set_token(Some("token".to_string()));
+        request(&api, Method::GET, "/feed/1").await;
+        assert_eq!(server.requests().len(), 2);
+
+        // Restarted, the saved responses are used for the same user
+        let api = client(&server, fast_retries()).with_cache_dir(dir.clone());
+        api.set_token(Some("token".to_string()));
+        let response = request(&api, Method::GET, "/feed/1").await;
+        assert_eq!(response.status, StatusCode::OK);
+        assert_eq!(response.body, b"{}");
+        assert_eq!(server.requests().len(), 2);
+        assert_eq!(api.cache_size(), 4);
+
+        api.clear_cache();
+        assert!(!dir.exists());
+        let api = client(&server, fast_retries()).with_cache_dir(dir);
+        assert_eq!(api.cache_size(), 0);
+    }
 }
@@ -0,0 +1,96 @@
+use std::time::Duration;
+
+use rand::Rng;
+use reqwest::{Method, StatusCode};
+
+/// Statuses which mean the server may handle the same request later
+pub const RETRY_STATUSES: [StatusCode; 4] = [
+    StatusCode::TOO_MANY_REQUESTS,
+    StatusCode::BAD_GATEWAY,
+    StatusCode::SERVICE_UNAVAILABLE,
+    StatusCode::GATEWAY_TIMEOUT,
+];
+
+/// When and how often failed requests are made again
+#[derive(Clone, Debug)]
+pub struct RetryPolicy {
+    /// Retries after the first attempt
+    pub max_retries: u32,
+    /// Delay before the first retry, doubled for every retry after it
+    pub base_delay: Duration,
+    /// Longest delay between retries backing off
+    pub max_delay: Duration,
+    /// Longest delay asked for by the server with `Retry-After`. Longer ones are shortened to
+    /// this, so the request is still made again.
+    pub max_retry_after: Duration,
+    /// Timeout of each attempt
+    pub timeout: Duration,
+    /// Longest time to wait for the connection to come back while offline
+    pub offline_timeout: Duration,
+    /// Methods whose requests are retried.
+    /// `PUT` isn't retried by default, as the API creates resources with it.
+    pub methods: Vec<Method>,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_retries: 3,
+            base_delay: Duration::from_millis(500),
+            max_delay: Duration::from_secs(30),
+            max_retry_after: Duration::from_secs(120),
+            timeout: Duration::from_secs(30),
+            offline_timeout: Duration::from_secs(60),
+            methods: vec![Method::GET, Method::HEAD, Method::OPTIONS, Method::DELETE],
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Makes every request once
+    pub fn none() -> Self {
+        Self {
+            max_retries: 0,
+            ..Default::default()
+        }
+    }
+
+    pub fn with_max_retries(mut self, max_retries: u32) -> Self {
+        self.max_retries = max_retries;
+        self
+    }
+
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    pub fn with_methods(mut self, methods: impl IntoIterator<Item = Method>) -> Self {
+        self.methods = methods.into_iter().collect();
+        self
+    }
+
+    /// Delay before the retry after `attempt` retries, as asked for by the server if it did
+    pub fn delay(&self, attempt: u32, retry_after: Option<Duration>) -> Duration {
+        match retry_after {
+            Some(retry_after) => retry_after.min(self.max_retry_after),
+            None => self.backoff(attempt),
+        }
+    }
+
+    /// Whether a request which failed `attempt` retries in may be retried again
+    pub fn should_retry(&self, method: &Method, attempt: u32) -> bool {
+        attempt < self.max_retries && self.methods.contains(method)
+    }
+
+    /// Delay before the retry after `attempt` retries.
+    /// Jittered between half and all of the exponential delay, so clients don't retry in lockstep.
+    pub fn backoff(&self, attempt: u32) -> Duration {
+        let delay = self
+            .base_delay
+            .saturating_mul(2u32.saturating_pow(attempt))
+            .min(self.max_delay);
+        let millis = delay.as_millis() as u64;
+        Duration::from_millis(rand::rng().random_range(millis / 2..=millis))
+    }
+}
@@ -0,0 +1,147 @@
+//! Local HTTP server standing in for the API in tests
+
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
+
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::{TcpListener, TcpStream},
+};
+
+/// Request received by the [TestServer]
+#[derive(Clone, Debug)]
+pub struct TestRequest {
+    pub method: String,
+    pub path: String,
+    /// Headers by lowercase name
+    pub headers: HashMap<String, String>,
+}
+
+/// Response the [TestServer] answers a request with
+#[derive(Clone, Debug)]
+pub struct TestResponse {
+    status: u16,
+    headers: Vec<(String, String)>,
+    body: String,
+}
+
+impl TestResponse {
+    pub fn new(status: u16) -> Self {
+        Self {
+            status,
+            headers: vec![],
+            body: String::new(),
+        }
+    }
+
+    pub fn header(mut self, name: &str, value: &str) -> Self {
+        self.headers.push((name.to_string(), value.to_string()));
+        self
+    }
+
+    pub fn body(mut self, body: &str) -> Self {
+        self.body = body.to_string();
+        self
+    }
+}
+
+type Respond = dyn Fn(&TestRequest, usize) -> TestResponse + Send + Sync;
+
+pub struct TestServer {
+    pub url: String,
+    requests: Arc<Mutex<Vec<TestRequest>>>,
+}
+
+impl TestServer {
+    /// Starts the server, answering each request with `respond`. It's also given the number of
+    /// requests received before, to fail the first attempts of a request.
+    pub async fn start(
+        respond: impl Fn(&TestRequest, usize) -> TestResponse + Send + Sync + 'static,
+    ) -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let respond: Arc<Respond> = Arc::new(respond);
+
+        let received = requests.clone();
+        tokio::spawn(async move {
+            while let Ok((stream, _)) = listener.accept().await {
+                tokio::spawn(handle(stream, received.clone(), respond.clone()));
+            }
+        });
+
+        Self { url, requests }
+    }
+
+    /// Requests received so far, oldest first
+    pub fn requests(&self) -> Vec<TestRequest> {
+        self.requests.lock().unwrap().clone()
+    }
+}
+
+async fn handle(
+    mut stream: TcpStream,
+    requests: Arc<Mutex<Vec<TestRequest>>>,
+    respond: Arc<Respond>,
+) {
+    let mut data = Vec::new();
+    let mut buffer = [0; 1024];
+    let head_length = loop {
+        if let Some(end) = data.windows(4).position(|window| window == b"\r\n\r\n") {
+            break end + 4;
+        }
+        match stream.read(&mut buffer).await {
+            Ok(0) | Err(_) => return,
+            Ok(read) => data.extend_from_slice(&buffer[..read]),
+        }
+    };
+
+    let head = String::from_utf8_lossy(&data[..head_length]).to_string();
+    let mut lines = head.lines();
+    let mut start = lines.next().unwrap_or_default().split_whitespace();
+    let method = start.next().unwrap_or_default().to_string();
+    let path = start.next().unwrap_or_default().to_string();
+    let headers: HashMap<String, String> = lines
+        .filter_map(|line| line.split_once(':'))
+        .map(|(name, value)| (name.trim().to_lowercase(), value.trim().to_string()))
+        .collect();
+
+    // Read the body, so the connection isn't reset while the client is still sending it
+    let length: usize = headers
+        .get("content-length")
+        .and_then(|length| length.parse().ok())
+        .unwrap_or(0);
+    while data.len() < head_length + length {
+        match stream.read(&mut buffer).await {
+            Ok(0) | Err(_) => return,
+            Ok(read) => data.extend_from_slice(&buffer[..read]),
+        }
+    }
+
+    let request = TestRequest {
+        method,
+        path,
+        headers,
+    };
+    let response = {
+        let mut requests = requests.lock().unwrap();
+        let response = respond(&request, requests.len());
+        requests.push(request);
+        response
+    };
+
+    let mut head = format!(
+        "HTTP/1.1 {} Test\r\nContent-Length: {}\r\nConnection: close\r\n",
+        response.status,
+        response.body.len()
+    );
+    for (name, value) in &response.headers {
+        head.push_str(&format!("{name}: {value}\r\n"));
+    }
+    head.push_str("\r\n");
+    let _ = stream
+        .write_all(&[head.as_bytes(), response.body.as_bytes()].concat())
+        .await;
+}
@@ -15,7 +15,7 @@ pub struct DiscoverFeedsRequest {
 // ----------
 
 /// Information about a discovered feed
-#[derive(Debug, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Deserialize)]
 pub struct FeedInformation {
     /// URL of the feed
     pub url: String,
@@ -23,7 +23,7 @@ pub struct FeedInformation {
     pub format: FeedFormat,
 }
 
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
 pub struct FeedItemEnclosure {
     /// URL of the media file
     pub url: String,
@@ -33,7 +33,27 @@ pub struct FeedItemEnclosure {
     pub mime_type: String,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+impl FeedItemEnclosure {
+    pub fn kind(&self) -> EnclosureKind {
+        match self.mime_type.split('/').next().unwrap_or_default() {
+            "audio" => EnclosureKind::Audio,
+            "video" => EnclosureKind::Video,
+            "image" => EnclosureKind::Image,
+            _ => EnclosureKind::Other,
+        }
+    }
+}
+
+/// Kind of media attached to a feed item, derived from its MIME type
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum EnclosureKind {
+    Audio,
+    Video,
+    Image,
+    Other,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
 pub enum FeedFormat {
     Atom,
     Rss,
@@ -55,7 +75,7 @@ impl FeedFormat {
     }
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
 pub enum FeedStatus {
     /// Feed is active and being updated regularly
     Active,
@@ -67,7 +87,7 @@ pub enum FeedStatus {
     Broken,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct Feed {
     /// Unique identifier of the feed
     pub id: i32,
@@ -107,7 +127,7 @@ pub struct Feed {
     pub next_fetch_at: chrono::DateTime<chrono::Utc>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct FeedItem {
     /// Unique identifier of the feed item
     pub id: i64,
@@ -23,7 +23,7 @@ pub struct UpdateIndexRequest {
 
 /// Represents an index created by a user.
 /// An **Index** can be thought of as a custom search over a certain set of feeds.
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, PartialEq)]
 pub struct UserIndex {
     pub id: i32,
     pub owner: i32,
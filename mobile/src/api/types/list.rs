@@ -27,7 +27,7 @@ pub struct CreateListItemRequest {
 
 /// Represent a list created by a user
 /// Lists are a way for users to catalogue items from feeds
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
 pub struct UserList {
     pub id: i32,
     pub owner: i32,
@@ -39,7 +39,7 @@ pub struct UserList {
 }
 
 /// Represents a single item in a user's list
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
 pub struct UserListItem {
     pub id: i32,
     pub index: i32,
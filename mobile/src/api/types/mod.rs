@@ -8,7 +8,7 @@ pub mod search;
 pub mod tag;
 pub mod user;
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct Icon {
     pub icon: String,
     pub color: String,
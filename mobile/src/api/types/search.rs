@@ -3,6 +3,6 @@ use serde::Serialize;
 
 #[derive(Debug, Serialize)]
 pub struct SearchRequest {
-    query: String,
-    sort: SortOrder,
+    pub query: String,
+    pub sort: SortOrder,
 }
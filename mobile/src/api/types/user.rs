@@ -3,7 +3,7 @@ use serde::{Deserialize, Serialize};
 // History
 
 /// Represents a single item in a user's history
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, PartialEq)]
 pub struct HistoryItem {
     pub id: i64,
     /// ID of the user this history item belongs to
@@ -64,6 +64,11 @@ pub struct EmailVerificationRequest {
     pub email: String,
 }
 
+#[derive(Deserialize, Debug)]
+pub struct UsernameAvailabilityResponse {
+    pub available: bool,
+}
+
 #[derive(Serialize, Debug)]
 pub struct SendPasswordCodeRequest {
     pub email: String,
@@ -76,12 +81,46 @@ pub struct ResetPasswordRequest {
     pub new_password: String,
 }
 
+#[derive(Serialize, Debug)]
+pub struct ChangePasswordRequest {
+    pub current_password: String,
+    pub new_password: String,
+}
+
 // OAuth
 
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OAuthProvider {
+    Google,
+    Apple,
+}
+
+impl OAuthProvider {
+    pub fn name(&self) -> &'static str {
+        match self {
+            OAuthProvider::Google => "Google",
+            OAuthProvider::Apple => "Apple",
+        }
+    }
+}
+
 #[derive(Serialize)]
 pub struct AuthorizeRequest {
-    pub provider: String,
+    pub provider: OAuthProvider,
     pub client: OAuthRedirectClient,
+    /// Returned unchanged in the callback, to tie it to this request
+    pub state: String,
+    /// PKCE challenge, the hash of the verifier sent when exchanging the code
+    pub code_challenge: String,
+    pub code_challenge_method: String,
+}
+
+#[derive(Serialize, Debug)]
+pub struct OAuthTokenRequest {
+    pub provider: OAuthProvider,
+    pub code: String,
+    pub code_verifier: String,
 }
 
 #[derive(Clone, Copy, Debug, Serialize, Deserialize)]
@@ -92,6 +131,19 @@ pub enum OAuthRedirectClient {
     IOS,
 }
 
+impl OAuthRedirectClient {
+    /// The client the server redirects back to after authorization
+    pub fn current() -> Self {
+        if cfg!(target_os = "ios") {
+            OAuthRedirectClient::IOS
+        } else if cfg!(target_os = "android") {
+            OAuthRedirectClient::Android
+        } else {
+            OAuthRedirectClient::Web
+        }
+    }
+}
+
 // ----------
 
 #[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
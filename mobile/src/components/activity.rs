@@ -1,6 +1,84 @@
+use chrono::{DateTime, Duration, Utc};
 use dioxus::prelude::*;
 use ui::layout::Column;
 
+use crate::{i18n::t, store::use_store};
+
+/// Number of weeks the activity of feeds is shown for
+pub const ACTIVITY_WEEKS: usize = 12;
+
+/// Posting frequency of a feed, bucketed by week
+#[derive(Clone, Debug, PartialEq)]
+pub struct ActivityHistogram {
+    /// Number of items published in each week, oldest first
+    pub counts: Vec<usize>,
+    /// Average number of items published per month over the histogram's range
+    pub per_month: usize,
+}
+
+impl ActivityHistogram {
+    /// Buckets the given publish dates into `weeks` weekly buckets ending at `now`.
+    /// Dates outside of the range are ignored.
+    pub fn from_dates(
+        dates: impl IntoIterator<Item = DateTime<Utc>>,
+        now: DateTime<Utc>,
+        weeks: usize,
+    ) -> Self {
+        let weeks = weeks.max(2);
+        let mut counts = vec![0; weeks];
+
+        for date in dates {
+            let age = now.signed_duration_since(date);
+            if age < Duration::zero() {
+                continue;
+            }
+            let bucket = (age.num_days() / 7) as usize;
+            if bucket < weeks {
+                counts[weeks - 1 - bucket] += 1;
+            }
+        }
+
+        let total = counts.iter().sum::<usize>() as f32;
+        let months = (weeks * 7) as f32 / 30.;
+        let per_month = (total / months).round() as usize;
+
+        Self { counts, per_month }
+    }
+
+    /// Points normalized to 0 - 1 for the [ActivityGraph]
+    pub fn points(&self) -> Vec<f32> {
+        let max = self.counts.iter().copied().max().unwrap_or(0);
+        if max == 0 {
+            return vec![0.; self.counts.len()];
+        }
+        self.counts
+            .iter()
+            .map(|count| *count as f32 / max as f32)
+            .collect()
+    }
+}
+
+/// Activity of the feed over the last [ACTIVITY_WEEKS], from every item it published in that
+/// time. `None` until the items are loaded, or if they couldn't be.
+pub fn use_feed_activity(feed_id: i32) -> Resource<Option<ActivityHistogram>> {
+    let store = use_store();
+    use_resource(use_reactive!(|feed_id| async move {
+        let now = Utc::now();
+        let since = now - Duration::weeks(ACTIVITY_WEEKS as i64);
+        match store.feed_items_since(feed_id, Some(since)).await {
+            Ok(items) => Some(ActivityHistogram::from_dates(
+                items.iter().filter_map(|item| item.published_at),
+                now,
+                ACTIVITY_WEEKS,
+            )),
+            Err(err) => {
+                tracing::error!("Failed to load activity of feed {feed_id}: {err}");
+                None
+            }
+        }
+    }))
+}
+
 #[derive(Props, Clone, PartialEq)]
 pub struct ActivityGraphProps {
     pub points: Vec<f32>,
@@ -77,7 +155,94 @@ pub fn Activity(props: ActivityProps) -> Element {
     rsx! {
         div { display: "flex", gap: "6px", flex_direction: if horizontal { "row" } else { "column" }, justify_content: "center", align_items: "center",
             ActivityGraph { points, width, stroke_width, color }
-            span { font_size: "12px", "{per_month} / mo" }
+            span { font_size: "12px", {t!("activity-per-month", count = per_month)} }
+        }
+    }
+}
+
+/// [Activity] of the feed, shown once its items are loaded
+#[component]
+pub fn FeedActivity(
+    feed_id: i32,
+    #[props(default = 48)] width: usize,
+    #[props(default = false)] horizontal: bool,
+) -> Element {
+    let activity = use_feed_activity(feed_id);
+    let Some(histogram) = activity.read().clone().flatten() else {
+        return rsx! {};
+    };
+
+    rsx! {
+        Activity {
+            points: histogram.points(),
+            per_month: histogram.per_month,
+            width,
+            horizontal,
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+
+    fn now() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2025, 6, 1, 12, 0, 0).unwrap()
+    }
+
+    #[test]
+    fn buckets_dates_by_week_oldest_first() {
+        let dates = [
+            now() - Duration::hours(1),
+            now() - Duration::days(6),
+            now() - Duration::days(7),
+            now() - Duration::days(27),
+        ];
+        let histogram = ActivityHistogram::from_dates(dates, now(), 4);
+        assert_eq!(histogram.counts, [1, 0, 1, 2]);
+    }
+
+    #[test]
+    fn ignores_dates_outside_of_range() {
+        let dates = [
+            now() + Duration::days(1),
+            now() - Duration::days(28),
+            now() - Duration::days(100),
+        ];
+        let histogram = ActivityHistogram::from_dates(dates, now(), 4);
+        assert_eq!(histogram.counts, [0, 0, 0, 0]);
+        assert_eq!(histogram.per_month, 0);
+        assert_eq!(histogram.points(), [0., 0., 0., 0.]);
+    }
+
+    #[test]
+    fn averages_items_per_month() {
+        // Two items a week for 12 weeks, which is 84 days
+        let dates = (0..24).map(|index| now() - Duration::hours(index * 84));
+        let histogram = ActivityHistogram::from_dates(dates, now(), 12);
+        assert_eq!(histogram.counts, [2; 12]);
+        assert_eq!(histogram.per_month, 9);
+    }
+
+    #[test]
+    fn points_are_relative_to_busiest_week() {
+        let dates = [
+            now(),
+            now(),
+            now() - Duration::days(7),
+            now() - Duration::days(7),
+            now() - Duration::days(7),
+            now() - Duration::days(7),
+        ];
+        let histogram = ActivityHistogram::from_dates(dates, now(), 3);
+        assert_eq!(histogram.points(), [0., 1., 0.5]);
+    }
+
+    #[test]
+    fn has_at_least_two_weeks() {
+        let histogram = ActivityHistogram::from_dates([now()], now(), 0);
+        assert_eq!(histogram.counts, [0, 1]);
+    }
+}
@@ -0,0 +1,126 @@
+use dioxus::prelude::*;
+use ui::{
+    forms::button::{SolidButton, TransparentButton},
+    icons::PlayIcon,
+    layout::{Column, Row},
+};
+
+use crate::{
+    api::types::feed::{EnclosureKind, FeedItem, FeedItemEnclosure},
+    i18n::t,
+    playback::{use_playback, QueueEntry},
+    read_state::use_read_store,
+};
+
+/// Formats a size in bytes, e.g. "45.2 MB"
+pub fn format_size(bytes: i64) -> String {
+    const UNITS: [&str; 4] = ["B", "KB", "MB", "GB"];
+
+    let mut size = bytes.max(0) as f64;
+    let mut unit = 0;
+    while size >= 1000. && unit < UNITS.len() - 1 {
+        size /= 1000.;
+        unit += 1;
+    }
+
+    if unit == 0 {
+        format!("{} {}", size, UNITS[unit])
+    } else {
+        format!("{:.1} {}", size, UNITS[unit])
+    }
+}
+
+fn describe(enclosure: &FeedItemEnclosure) -> String {
+    let kind = match enclosure.kind() {
+        EnclosureKind::Audio => t!("enclosure-audio"),
+        EnclosureKind::Video => t!("enclosure-video"),
+        EnclosureKind::Image => t!("enclosure-image"),
+        EnclosureKind::Other => t!("enclosure-attachment"),
+    };
+
+    if enclosure.length > 0 {
+        format!("{kind} · {}", format_size(enclosure.length as i64))
+    } else {
+        kind
+    }
+}
+
+/// Short description of the enclosure for item lists
+#[component]
+pub fn EnclosureBadge(enclosure: FeedItemEnclosure) -> Element {
+    rsx! {
+        span {
+            font_family: "IBM Plex Mono",
+            font_size: "12px",
+            text_transform: "uppercase",
+            "{describe(&enclosure)}"
+        }
+    }
+}
+
+/// Renders the item's enclosure with controls for its type
+#[component]
+pub fn Enclosure(item: FeedItem) -> Element {
+    let Some(enclosure) = item.enclosure.clone() else {
+        return rsx! {};
+    };
+
+    match enclosure.kind() {
+        EnclosureKind::Audio => rsx! {
+            Column { gap: "8px", width: "100%",
+                EnclosureBadge { enclosure }
+                QueueButtons { item }
+            }
+        },
+        EnclosureKind::Video => rsx! {
+            Column { gap: "8px", width: "100%",
+                video { controls: true, preload: "metadata", width: "100%",
+                    source { src: enclosure.url, r#type: enclosure.mime_type }
+                }
+                QueueButtons { item }
+            }
+        },
+        EnclosureKind::Image => rsx! {
+            img { src: enclosure.url, width: "100%" }
+        },
+        EnclosureKind::Other => rsx! {
+            a { href: enclosure.url, target: "_blank", "{describe(&enclosure)}" }
+        },
+    }
+}
+
+/// Plays the item's enclosure or adds it to "Up Next", resuming from its history
+#[component]
+fn QueueButtons(item: FeedItem) -> Element {
+    let mut playback = use_playback();
+    let read_store = use_read_store();
+    let entry = QueueEntry::from_item(&item, read_store.mark(item.id));
+    let queued = playback.queue().read().contains(item.id);
+
+    rsx! {
+        Row { gap: "8px",
+            SolidButton {
+                onclick: {
+                    let entry = entry.clone();
+                    move |_| {
+                        if let Some(entry) = entry.clone() {
+                            playback.play(entry);
+                        }
+                    }
+                },
+                PlayIcon { size: 16 }
+                {t!("enclosure-play")}
+            }
+            if !queued {
+                TransparentButton {
+                    onclick: move |_| {
+                        if let Some(entry) = entry.clone() {
+                            playback.enqueue(entry);
+                        }
+                    },
+                    {t!("enclosure-add-to-up-next")}
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,91 @@
+use dioxus::prelude::*;
+use ui::{forms::button::SolidButton, layout::*};
+
+use crate::i18n::t;
+use crate::{api::ApiClientError, store::use_store, views::Route};
+
+/// Catches errors thrown by the views inside it and shows a screen to recover from them.
+///
+/// Views handle the errors they can do something useful with, e.g. showing validation
+/// messages next to fields, and return the rest with `?` so they end up here.
+#[component]
+pub fn AppErrorBoundary(children: Element) -> Element {
+    rsx! {
+        ErrorBoundary {
+            handle_error: |errors: ErrorContext| rsx! {
+                ErrorScreen { errors }
+            },
+            {children}
+        }
+    }
+}
+
+#[component]
+fn ErrorScreen(errors: ErrorContext) -> Element {
+    let store = use_store();
+    let error = errors
+        .errors()
+        .iter()
+        .find_map(|error| error.downcast::<ApiClientError>().cloned());
+
+    // The session is over, so there is nothing to retry. The account's data is kept for when
+    // the user logs in to it again.
+    let unauthorized = matches!(error, Some(ApiClientError::Unauthorized(_)));
+    use_effect(move || {
+        if unauthorized {
+            store.expire_session();
+            navigator().replace(Route::Login {});
+        }
+    });
+
+    let (title, message) = match &error {
+        Some(ApiClientError::Unauthorized(_)) => {
+            (t!("error-logged-out"), t!("error-logged-out-message"))
+        }
+        Some(ApiClientError::Forbidden(_)) => {
+            (t!("error-not-allowed"), t!("error-not-allowed-message"))
+        }
+        Some(ApiClientError::NotFound(_)) => (t!("error-not-found"), t!("error-not-found-message")),
+        Some(err @ (ApiClientError::Offline(_) | ApiClientError::Timeout)) => {
+            (t!("error-no-connection"), err.message())
+        }
+        Some(err) => (t!("error-something-went-wrong"), err.message()),
+        None => (
+            t!("error-something-went-wrong"),
+            errors
+                .errors()
+                .first()
+                .map(|error| error.to_string())
+                .unwrap_or_default(),
+        ),
+    };
+    let retryable = error.as_ref().is_none_or(ApiClientError::is_retryable);
+
+    rsx! {
+        Column { gap: "12px", padding: "32px 16px", align: "center",
+            span { font_size: "20px", "{title}" }
+            span { color: "var(--text-secondary)", text_align: "center", "{message}" }
+            Row { gap: "8px",
+                if retryable {
+                    SolidButton {
+                        onclick: {
+                            let errors = errors.clone();
+                            move |_| {
+                                store.retry_failed();
+                                errors.clear_errors();
+                            }
+                        },
+                        {t!("error-try-again")}
+                    }
+                }
+                SolidButton {
+                    onclick: move |_| {
+                        errors.clear_errors();
+                        navigator().go_back();
+                    },
+                    {t!("error-go-back")}
+                }
+            }
+        }
+    }
+}
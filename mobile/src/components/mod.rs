@@ -1,4 +1,9 @@
 pub mod activity;
 pub mod container;
+pub mod enclosure;
+pub mod error;
 pub mod navbar;
+pub mod password_strength;
+pub mod player;
 pub mod popup;
+pub mod time;
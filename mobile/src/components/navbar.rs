@@ -1,18 +1,22 @@
-use dioxus::{html::u::z_index, prelude::*};
-use ui::forms::button::UnstyledButton;
-
-use crate::views::Route;
+use dioxus::prelude::*;
+use ui::forms::button::IconButton;
 
+/// Button going to a route, shown as active while it's the current one
 #[component]
 pub fn NavbarButton<Route: Routable + PartialEq + Clone>(
     icon: fn(active: bool) -> Element,
+    /// Names the button, since it only shows an icon
+    label: String,
     to: Route,
 ) -> Element {
     let current_route = use_route::<Route>();
+    let active = current_route == to;
     let to_clone = to.clone();
 
     rsx! {
-        UnstyledButton {
+        IconButton {
+            label,
+            aria_current: active.then_some("page"),
             display: "flex",
             align_items: "center",
             justify_content: "center",
@@ -22,19 +26,26 @@ pub fn NavbarButton<Route: Routable + PartialEq + Clone>(
 
             onclick: move |_| { navigator().push(to_clone.clone()); },
 
-            {icon(current_route == to)}
+            {icon(active)}
         }
     }
 }
 
 #[component]
 pub fn NavbarButtonWithoutRoute(
+    /// Names the button, since it only shows an icon
+    label: String,
+    /// The button opens a popup, which is open
+    expanded: Option<bool>,
     children: Element,
     onclick: EventHandler<MouseEvent>,
     onmounted: Option<EventHandler<MountedEvent>>,
 ) -> Element {
     rsx! {
-        UnstyledButton {
+        IconButton {
+            label,
+            aria_haspopup: expanded.map(|_| "dialog"),
+            aria_expanded: expanded,
             display: "flex",
             align_items: "center",
             justify_content: "center",
@@ -47,10 +58,15 @@ pub fn NavbarButtonWithoutRoute(
 }
 
 #[component]
-pub fn Navbar(children: Element) -> Element {
+pub fn Navbar(
+    /// Names the navigation, e.g. to tell the app's and the reader's apart
+    label: String,
+    children: Element,
+) -> Element {
     rsx! {
         nav {
             id: "navbar",
+            aria_label: label,
             {children}
         }
     }
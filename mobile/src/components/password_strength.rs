@@ -0,0 +1,98 @@
+use std::time::Duration;
+
+use dioxus::prelude::*;
+use ui::{forms::form::FieldOptions, layout::Row};
+
+use crate::{
+    components::time::format_duration,
+    i18n::t,
+    views::auth::validation::{validate_password, PasswordStrength},
+};
+
+/// How long to wait after the password changes before estimating its strength again
+const ESTIMATE_DELAY: Duration = Duration::from_millis(150);
+
+/// Validates a new password, estimating its strength into `strength` once it's long enough.
+/// `user_inputs` are the email and username, which make the password weaker when it contains them.
+pub fn new_password_options(
+    mut strength: Signal<Option<PasswordStrength>>,
+    user_inputs: impl Fn() -> Vec<String> + 'static,
+) -> FieldOptions<String> {
+    FieldOptions::new()
+        .validate(|password: &String| validate_password(password))
+        .validate_async(move |password: String| {
+            let user_inputs = user_inputs();
+            async move {
+                tokio::time::sleep(ESTIMATE_DELAY).await;
+                let estimate =
+                    PasswordStrength::estimate_in_background(password, user_inputs).await;
+                let result = match estimate.is_strong_enough() {
+                    true => Ok(()),
+                    false => Err(estimate.error()),
+                };
+                strength.set(Some(estimate));
+                result
+            }
+        })
+}
+
+/// Shows how strong the password is while it's typed, with the estimate from
+/// [new_password_options]
+#[component]
+pub fn PasswordStrengthMeter(
+    password: String,
+    strength: ReadOnlySignal<Option<PasswordStrength>>,
+    /// The strength of the password is being estimated, so `strength` is of a previous one
+    estimating: bool,
+) -> Element {
+    if password.is_empty() {
+        return rsx! {};
+    }
+    // Too short or long to be estimated
+    let Some(strength) = strength().filter(|_| validate_password(&password).is_ok()) else {
+        return rsx! {
+            span { color: "var(--text-secondary)", font_size: "14px", {t!("password-min-length", min = 8)} }
+        };
+    };
+
+    let color = if strength.is_strong_enough() {
+        "var(--text-success)"
+    } else {
+        "var(--text-error)"
+    };
+
+    rsx! {
+        div {
+            display: "flex",
+            flex_direction: "column",
+            gap: "4px",
+            opacity: if estimating { "0.5" } else { "1" },
+            aria_live: "polite",
+
+            Row { gap: "4px",
+                for segment in 0..5u8 {
+                    div {
+                        key: "{segment}",
+                        flex_grow: "1",
+                        height: "4px",
+                        background: if segment <= strength.score { color } else { "var(--text-tertiary)" },
+                    }
+                }
+            }
+            Row { align: "space-between",
+                span { color, font_size: "14px", {strength.label()} }
+                span { color: "var(--text-secondary)", font_size: "14px",
+                    {t!("password-crack-time", duration = format_duration(strength.crack_time))}
+                }
+            }
+            if let Some(warning) = &strength.warning {
+                span { color: "var(--text-error)", font_size: "14px", "{warning}" }
+            }
+            for suggestion in &strength.suggestions {
+                span { key: "{suggestion}", color: "var(--text-secondary)", font_size: "14px",
+                    "{suggestion}"
+                }
+            }
+        }
+    }
+}
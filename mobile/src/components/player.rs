@@ -0,0 +1,72 @@
+use dioxus::prelude::*;
+use ui::{
+    forms::button::UnstyledButton,
+    icons::{PauseIcon, PlayIcon},
+};
+
+use crate::{
+    playback::{use_playback, PlaybackStatus},
+    views::Route,
+};
+
+/// Formats a duration in seconds, e.g. "1:02:03" or "12:34"
+pub fn format_duration(seconds: f64) -> String {
+    let seconds = seconds.max(0.) as u64;
+    let (hours, minutes, seconds) = (seconds / 3600, (seconds / 60) % 60, seconds % 60);
+
+    if hours > 0 {
+        format!("{hours}:{minutes:02}:{seconds:02}")
+    } else {
+        format!("{minutes}:{seconds:02}")
+    }
+}
+
+/// Controls for the currently playing enclosure, hidden when nothing is loaded
+#[component]
+pub fn MiniPlayer() -> Element {
+    let mut playback = use_playback();
+    let queue = playback.queue();
+    let queue = queue.read();
+
+    let Some(current) = queue.current.clone() else {
+        return rsx! {};
+    };
+    let playing = queue.status == PlaybackStatus::Playing;
+    let remaining = current
+        .duration
+        .map(|duration| format!("-{}", format_duration(duration - current.position)))
+        .unwrap_or_else(|| format_duration(current.position));
+
+    rsx! {
+        div { id: "mini-player",
+            div { class: "progress",
+                div { width: "{current.progress() * 100.}%" }
+            }
+
+            div { class: "controls",
+                UnstyledButton {
+                    class: "title",
+                    onclick: move |_| { navigator().push(Route::UpNext {}); },
+                    span { "{current.title}" }
+                    span { class: "time", "{remaining}" }
+                }
+                UnstyledButton { onclick: move |_| playback.skip_backward(),
+                    "-{queue.skip_backward}"
+                }
+                UnstyledButton { onclick: move |_| playback.toggle(),
+                    if playing {
+                        PauseIcon {}
+                    } else {
+                        PlayIcon {}
+                    }
+                }
+                UnstyledButton { onclick: move |_| playback.skip_forward(),
+                    "+{queue.skip_forward}"
+                }
+                UnstyledButton { onclick: move |_| playback.cycle_speed(),
+                    "{queue.speed}×"
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,73 @@
+use std::time::Duration;
+
+use chrono::{DateTime, Datelike, Local, Utc};
+use dioxus::prelude::*;
+
+use crate::i18n::{current_language, t};
+
+/// Formats a duration in its largest whole unit, e.g. "28 minutes" or "3 years"
+pub fn format_duration(duration: Duration) -> String {
+    let seconds = duration.as_secs();
+
+    match seconds {
+        0 => t!("duration-less-than-a-second"),
+        1..60 => t!("duration-seconds", count = seconds),
+        60..3_600 => t!("duration-minutes", count = seconds / 60),
+        3_600..86_400 => t!("duration-hours", count = seconds / 3_600),
+        86_400..2_592_000 => t!("duration-days", count = seconds / 86_400),
+        2_592_000..31_536_000 => t!("duration-months", count = seconds / 2_592_000),
+        31_536_000..3_153_600_000 => t!("duration-years", count = seconds / 31_536_000),
+        _ => t!("duration-centuries"),
+    }
+}
+
+/// Formats the time relative to `now`, e.g. "28 minutes ago" or "in 3 hours"
+pub fn format_relative_time(time: DateTime<Utc>, now: DateTime<Utc>) -> String {
+    let delta = now.signed_duration_since(time);
+    let in_future = delta.num_seconds() < 0;
+    let seconds = delta.num_seconds().unsigned_abs();
+
+    if seconds < 60 {
+        return t!("time-just-now");
+    }
+    let duration = format_duration(Duration::from_secs(seconds));
+
+    if in_future {
+        t!("time-in", duration = duration)
+    } else {
+        t!("time-ago", duration = duration)
+    }
+}
+
+/// Names the local day of the time relative to `now`, e.g. "Today" or "Monday, March 3", with
+/// the year when it's not the current one
+pub fn format_day(time: DateTime<Utc>, now: DateTime<Utc>) -> String {
+    let time = time.with_timezone(&Local);
+    let (day, today) = (time.date_naive(), now.with_timezone(&Local).date_naive());
+
+    // The catalogs give the format of the date, as the order of its parts differs by language
+    let format = match (today - day).num_days() {
+        0 => return t!("day-today"),
+        1 => return t!("day-yesterday"),
+        _ if day.year() == today.year() => t!("day-this-year"),
+        _ => t!("day-other-year"),
+    };
+    time.format_localized(&format, current_language().locale())
+        .to_string()
+}
+
+/// Formats the local date of the time, e.g. "3 Mar 2025"
+pub fn format_date(time: DateTime<Utc>) -> String {
+    time.with_timezone(&Local)
+        .format_localized(&t!("date"), current_language().locale())
+        .to_string()
+}
+
+#[component]
+pub fn RelativeTime(time: DateTime<Utc>) -> Element {
+    let formatted = format_relative_time(time, Utc::now());
+
+    rsx! {
+        time { datetime: time.to_rfc3339(), "{formatted}" }
+    }
+}
@@ -0,0 +1,69 @@
+//! Links which open the app, both `bind://` app links and `https://bind.sh` web links.
+//!
+//! [parse] maps a link to the [Route] it opens. Routes which require an account are kept in
+//! [DeepLinks] while the user logs in, and opened once they have.
+
+use dioxus::prelude::*;
+use ui::overlay::{use_overlays, Toast};
+
+use crate::{hooks::use_token, i18n::t, platform::init_deep_link_listener, views::Route};
+
+mod parser;
+
+pub use parser::{is_public, parse, DeepLinkError, APP_SCHEME};
+
+#[derive(Clone, Copy)]
+pub struct DeepLinks {
+    token: Signal<Option<String>>,
+    /// Route of a link opened while logged out
+    pending: Signal<Option<Route>>,
+}
+
+impl DeepLinks {
+    /// Opens the route, or keeps it until the user logs in if it requires an account
+    pub fn open(&mut self, route: Route) {
+        if is_public(&route) || self.token.peek().is_some() {
+            navigator().push(route);
+        } else {
+            self.pending.set(Some(route));
+            navigator().push(Route::Login {});
+        }
+    }
+
+    /// Takes the route kept while the user logged in
+    pub fn take_pending(&mut self) -> Option<Route> {
+        self.pending.take()
+    }
+}
+
+/// Listens for links the app is opened with. Must be called once, inside the router.
+pub fn use_deep_links_provider() -> DeepLinks {
+    let token = use_token();
+    let pending = use_signal(|| None);
+    let mut overlays = use_overlays();
+
+    let deep_links = use_context_provider(|| DeepLinks { token, pending });
+
+    use_future(move || async move {
+        let rx = init_deep_link_listener();
+        let mut deep_links = deep_links;
+        loop {
+            if let Ok(link) = rx.try_recv() {
+                match parse(&link) {
+                    Ok(route) => deep_links.open(route),
+                    Err(err) => {
+                        tracing::warn!("Failed to open deep link: {err}");
+                        overlays.toast(Toast::new(t!("deep-link-unsupported")));
+                    }
+                }
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+        }
+    });
+
+    deep_links
+}
+
+pub fn use_deep_links() -> DeepLinks {
+    use_context::<DeepLinks>()
+}
@@ -0,0 +1,254 @@
+use std::str::FromStr;
+
+use reqwest::Url;
+use thiserror::Error;
+
+use crate::{oauth::CallbackParams, views::Route};
+
+/// Scheme of links which open the app, e.g. `bind://reader/123`
+pub const APP_SCHEME: &str = "bind";
+
+/// Hosts of web links which are opened in the app, e.g. `https://bind.sh/list/5`
+const WEB_HOSTS: [&str; 2] = ["bind.sh", "www.bind.sh"];
+
+#[derive(Error, Debug, Clone, PartialEq)]
+pub enum DeepLinkError {
+    #[error("Invalid link: {0}")]
+    Invalid(String),
+    /// A valid link, but not to anything the app can show
+    #[error("Unsupported link: {0}")]
+    Unsupported(String),
+    #[error("Invalid {name} in link: {value:?}")]
+    InvalidParameter { name: &'static str, value: String },
+}
+
+/// Parses a link into the route it opens.
+///
+/// App links and web links share their paths, `bind://feed/1` and `https://bind.sh/feed/1`
+/// both open the feed.
+pub fn parse(link: &str) -> Result<Route, DeepLinkError> {
+    let url = Url::parse(link).map_err(|err| DeepLinkError::Invalid(err.to_string()))?;
+
+    // The host of app links is the first segment of the path
+    let mut segments: Vec<&str> = match (url.scheme(), url.host_str()) {
+        (APP_SCHEME, host) => host.into_iter().collect(),
+        ("https", Some(host)) if WEB_HOSTS.contains(&host) => vec![],
+        _ => return Err(DeepLinkError::Unsupported(link.to_string())),
+    };
+    segments.extend(
+        url.path_segments()
+            .into_iter()
+            .flatten()
+            .filter(|segment| !segment.is_empty()),
+    );
+
+    let param = |name: &str| {
+        url.query_pairs()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.into_owned())
+            .unwrap_or_default()
+    };
+
+    match segments.as_slice() {
+        ["reader", id] => Ok(Route::FeedReader {
+            id: parse_id("item id", id)?,
+        }),
+        ["feed", id] => Ok(Route::FeedDetail {
+            id: parse_id("feed id", id)?,
+        }),
+        ["list", id] => Ok(Route::List {
+            id: parse_id("list id", id)?,
+        }),
+        ["index", id] => Ok(Route::Index {
+            id: parse_id("index id", id)?,
+        }),
+        ["auth", "reset-password"] | ["reset-password"] => {
+            let email = param("email");
+            if !email.contains('@') {
+                return Err(DeepLinkError::InvalidParameter {
+                    name: "email",
+                    value: email,
+                });
+            }
+            // Missing when the link only fills in the email, the code is entered by hand then
+            let code = param("code");
+            if !code.chars().all(|c| c.is_ascii_alphanumeric()) {
+                return Err(DeepLinkError::InvalidParameter {
+                    name: "code",
+                    value: code,
+                });
+            }
+            Ok(Route::ResetPasswordConfirm { email, code })
+        }
+        ["oauth", "callback"] if url.scheme() == APP_SCHEME => {
+            let params = CallbackParams::parse(link)
+                .map_err(|err| DeepLinkError::Invalid(err.to_string()))?;
+            Ok(Route::OAuthCallback {
+                code: params.code,
+                state: params.state,
+                token: params.token,
+                error: params.error,
+            })
+        }
+        _ => Err(DeepLinkError::Unsupported(link.to_string())),
+    }
+}
+
+/// Whether the route can be opened without logging in
+pub fn is_public(route: &Route) -> bool {
+    matches!(
+        route,
+        Route::OAuthCallback { .. } | Route::ResetPasswordConfirm { .. }
+    )
+}
+
+/// Parses a positive id
+fn parse_id<T: FromStr + PartialOrd + Default>(
+    name: &'static str,
+    value: &str,
+) -> Result<T, DeepLinkError> {
+    value
+        .parse::<T>()
+        .ok()
+        .filter(|id| *id > T::default())
+        .ok_or_else(|| DeepLinkError::InvalidParameter {
+            name,
+            value: value.to_string(),
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn invalid(name: &'static str, value: &str) -> Result<Route, DeepLinkError> {
+        Err(DeepLinkError::InvalidParameter {
+            name,
+            value: value.to_string(),
+        })
+    }
+
+    #[test]
+    fn parses_app_and_web_links() {
+        for link in [
+            "bind://reader/12",
+            "https://bind.sh/reader/12",
+            "https://www.bind.sh/reader/12/",
+        ] {
+            assert_eq!(parse(link), Ok(Route::FeedReader { id: 12 }), "{link}");
+        }
+    }
+
+    #[test]
+    fn parses_routes_with_ids() {
+        assert_eq!(parse("bind://feed/3"), Ok(Route::FeedDetail { id: 3 }));
+        assert_eq!(parse("bind://list/5"), Ok(Route::List { id: 5 }));
+        assert_eq!(parse("bind://index/7"), Ok(Route::Index { id: 7 }));
+    }
+
+    #[test]
+    fn rejects_invalid_ids() {
+        assert_eq!(parse("bind://reader/abc"), invalid("item id", "abc"));
+        assert_eq!(parse("bind://feed/0"), invalid("feed id", "0"));
+        assert_eq!(parse("bind://list/-5"), invalid("list id", "-5"));
+        assert_eq!(
+            parse("https://bind.sh/index/1.5"),
+            invalid("index id", "1.5")
+        );
+    }
+
+    #[test]
+    fn parses_reset_password_links() {
+        for link in [
+            "bind://reset-password?email=user%40example.com&code=a1B2c3",
+            "https://bind.sh/auth/reset-password?code=a1B2c3&email=user@example.com",
+        ] {
+            assert_eq!(
+                parse(link),
+                Ok(Route::ResetPasswordConfirm {
+                    email: "user@example.com".to_string(),
+                    code: "a1B2c3".to_string(),
+                }),
+                "{link}"
+            );
+        }
+
+        // The code can be entered by hand instead
+        assert_eq!(
+            parse("bind://reset-password?email=user@example.com"),
+            Ok(Route::ResetPasswordConfirm {
+                email: "user@example.com".to_string(),
+                code: String::new(),
+            })
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_reset_password_parameters() {
+        assert_eq!(
+            parse("bind://reset-password?email=user&code=123456"),
+            invalid("email", "user")
+        );
+        assert_eq!(
+            parse("bind://reset-password?code=123456"),
+            invalid("email", "")
+        );
+        assert_eq!(
+            parse("bind://reset-password?email=user@example.com&code=12%2034"),
+            invalid("code", "12 34")
+        );
+        assert_eq!(
+            parse("bind://reset-password?email=user@example.com&code=12-34"),
+            invalid("code", "12-34")
+        );
+    }
+
+    #[test]
+    fn parses_oauth_callbacks_from_the_app_only() {
+        assert_eq!(
+            parse("bind://oauth/callback?code=abc&state=xyz"),
+            Ok(Route::OAuthCallback {
+                code: "abc".to_string(),
+                state: "xyz".to_string(),
+                token: String::new(),
+                error: String::new(),
+            })
+        );
+        assert_eq!(
+            parse("https://bind.sh/oauth/callback?code=abc&state=xyz"),
+            Err(DeepLinkError::Unsupported(
+                "https://bind.sh/oauth/callback?code=abc&state=xyz".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn rejects_other_links() {
+        assert!(matches!(
+            parse("not a link"),
+            Err(DeepLinkError::Invalid(_))
+        ));
+        for link in [
+            "https://example.com/feed/1",
+            "http://bind.sh/feed/1",
+            "bind://settings",
+            "bind://feed/1/items",
+            "bind://feed",
+        ] {
+            assert_eq!(
+                parse(link),
+                Err(DeepLinkError::Unsupported(link.to_string())),
+                "{link}"
+            );
+        }
+    }
+
+    #[test]
+    fn only_auth_routes_are_public() {
+        assert!(is_public(&Route::ResetPasswordConfirm {
+            email: String::new(),
+            code: String::new(),
+        }));
+        assert!(!is_public(&Route::FeedDetail { id: 1 }));
+    }
+}
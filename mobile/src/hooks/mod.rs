@@ -2,52 +2,72 @@ use std::sync::LazyLock;
 
 use dioxus::prelude::*;
 use serde::{de::DeserializeOwned, Serialize};
+use tokio::sync::broadcast::error::RecvError;
 
 use crate::api::ApiClient;
-use crate::platform::SecureStore;
+use crate::platform::{data_dir, Preferences, StoreKey};
+use crate::store::{use_store, DEFAULT_SERVER};
 
+mod use_connectivity_monitor;
 mod use_keyboard_open;
 
+pub use use_connectivity_monitor::use_connectivity_monitor;
 pub use use_keyboard_open::use_keyboard_open;
 
-static API: LazyLock<ApiClient> = LazyLock::new(|| ApiClient::new("https://api.bind.sh"));
-static TOKEN: GlobalSignal<Option<String>> =
-    Signal::global(|| SecureStore::get("token").unwrap_or(None));
+/// Directory in [data_dir] for cached API responses
+const API_CACHE_DIR: &str = "api_cache";
 
-pub fn use_token() -> Signal<Option<String>> {
-    let token = use_signal(|| TOKEN.read().clone());
-
-    use_effect(move || {
-        if *TOKEN.read() == token() {
-            return;
-        }
-        SecureStore::set("token", &token());
-        *TOKEN.write() = token();
-    });
+static API: LazyLock<ApiClient> =
+    LazyLock::new(|| ApiClient::new(DEFAULT_SERVER).with_cache_dir(data_dir().join(API_CACHE_DIR)));
 
-    token
+/// Token of the active account, kept in the [store](crate::store::Store)
+pub fn use_token() -> Signal<Option<String>> {
+    use_store().token()
 }
 
+/// The API client. Its token is kept in sync with the store.
 pub fn use_api() -> &'static ApiClient {
-    use_effect(move || {
-        API.set_token(TOKEN());
-    });
-
     &*API
 }
 
-pub fn use_persistent<T: Serialize + DeserializeOwned + Default + 'static>(
-    key: &str,
+/// Signal for a value in [Preferences], saved whenever it changes and updated whenever it's
+/// changed elsewhere
+pub fn use_persistent<T: Serialize + DeserializeOwned + PartialEq + 'static>(
+    key: StoreKey<T>,
     init: impl FnOnce() -> T,
 ) -> Signal<T> {
-    let state = use_signal(|| SecureStore::get(key).unwrap_or_else(init));
+    let mut state = use_signal(|| read_preference(&key).unwrap_or_else(init));
+
+    use_future(move || async move {
+        let storage_key = key.storage_key();
+        let mut changes = Preferences::subscribe();
+        loop {
+            match changes.recv().await {
+                Ok(changed) if changed != storage_key => continue,
+                Ok(_) | Err(RecvError::Lagged(_)) => {}
+                Err(RecvError::Closed) => break,
+            }
+            if let Some(value) = read_preference(&key) {
+                if *state.peek() != value {
+                    state.set(value);
+                }
+            }
+        }
+    });
 
-    // TODO: what the actual fu
-    let key_clone = key.to_string();
     use_effect(move || {
         let value = state.read();
-        SecureStore::set(&key_clone, &*value);
+        if let Err(err) = Preferences::set(&key, &*value) {
+            tracing::error!("Failed to save {}: {err}", key.name);
+        }
     });
 
     state
 }
+
+fn read_preference<T: Serialize + DeserializeOwned>(key: &StoreKey<T>) -> Option<T> {
+    Preferences::get(key).unwrap_or_else(|err| {
+        tracing::error!("Failed to read {}: {err}", key.name);
+        None
+    })
+}
@@ -0,0 +1,28 @@
+use dioxus::prelude::*;
+
+use super::use_api;
+
+/// Reports whether the device is online to the API client, so retries are paused while offline.
+/// Must be called once, at the root of the app.
+pub fn use_connectivity_monitor() -> ReadOnlySignal<bool> {
+    let api = use_api();
+    let mut online = use_signal(|| true);
+
+    use_future(move || async move {
+        let mut eval = document::eval(
+            r#"
+            dioxus.send(navigator.onLine);
+            window.addEventListener('online', () => dioxus.send(true));
+            window.addEventListener('offline', () => dioxus.send(false));
+            await new Promise(() => {});
+            "#,
+        );
+
+        while let Ok(is_online) = eval.recv::<bool>().await {
+            api.connectivity().set_online(is_online);
+            online.set(is_online);
+        }
+    });
+
+    online.into()
+}
@@ -0,0 +1,384 @@
+## Shared
+
+loading = جارٍ التحميل...
+remove = إزالة
+on = مفعّل
+off = معطّل
+
+## Dates and times
+## The formats of dates are strftime patterns, see chrono::format::strftime
+
+duration-less-than-a-second = أقل من ثانية
+duration-seconds = { $count ->
+    [one] ثانية واحدة
+    [two] ثانيتان
+    [few] { $count } ثوانٍ
+    [many] { $count } ثانية
+   *[other] { $count } ثانية
+}
+duration-minutes = { $count ->
+    [one] دقيقة واحدة
+    [two] دقيقتان
+    [few] { $count } دقائق
+    [many] { $count } دقيقة
+   *[other] { $count } دقيقة
+}
+duration-hours = { $count ->
+    [one] ساعة واحدة
+    [two] ساعتان
+    [few] { $count } ساعات
+    [many] { $count } ساعة
+   *[other] { $count } ساعة
+}
+duration-days = { $count ->
+    [one] يوم واحد
+    [two] يومان
+    [few] { $count } أيام
+    [many] { $count } يومًا
+   *[other] { $count } يوم
+}
+duration-months = { $count ->
+    [one] شهر واحد
+    [two] شهران
+    [few] { $count } أشهر
+    [many] { $count } شهرًا
+   *[other] { $count } شهر
+}
+duration-years = { $count ->
+    [one] سنة واحدة
+    [two] سنتان
+    [few] { $count } سنوات
+    [many] { $count } سنة
+   *[other] { $count } سنة
+}
+duration-centuries = قرون
+time-just-now = الآن
+time-ago = منذ { $duration }
+time-in = خلال { $duration }
+day-today = اليوم
+day-yesterday = أمس
+day-this-year = %A %-d %B
+day-other-year = %A %-d %B %Y
+day-undated = بلا تاريخ
+date = %-d %B %Y
+
+## Errors
+
+error-unknown-server = خطأ غير معروف من الخادم ({ $status })
+error-rate-limited = { $message }. حاول مرة أخرى بعد { $seconds } ث.
+error-offline = أنت غير متصل. تحقق من اتصالك وحاول مرة أخرى.
+error-timeout = استغرق الخادم وقتًا طويلًا للرد.
+error-invalid-response = وصلت استجابة غير صالحة من الخادم.
+error-feed-not-found = لم يُعثر على الموجز { $id }
+error-item-not-found = لم يُعثر على العنصر { $id }
+error-index-not-found = لم يُعثر على الفهرس { $id }
+error-no-feed-for-site = لم يُعثر على موجز لهذا الموقع
+error-page-not-in-feed = هذه الصفحة ليست في موجز موقعها
+error-logged-out = تم تسجيل الخروج
+error-logged-out-message = انتهت جلستك. سجّل الدخول مرة أخرى للمتابعة.
+error-not-allowed = غير مسموح
+error-not-allowed-message = ليس لديك صلاحية الوصول إلى هذا.
+error-not-found = غير موجود
+error-not-found-message = لم يعد هذا موجودًا، أو تم نقله.
+error-no-connection = لا يوجد اتصال
+error-something-went-wrong = حدث خطأ ما
+error-try-again = حاول مرة أخرى
+error-go-back = رجوع
+deep-link-unsupported = لا يمكن فتح هذا الرابط في التطبيق
+share-links-only = يمكن مشاركة الروابط فقط مع Bind
+
+## Logging in and signing up
+
+auth-email = البريد الإلكتروني
+auth-username = اسم المستخدم
+auth-password = كلمة المرور
+auth-login = تسجيل الدخول
+auth-sign-up = إنشاء حساب
+auth-reset-password = إعادة تعيين كلمة المرور
+auth-go-back = رجوع
+login-welcome-back = مرحبًا بعودتك إلى
+login-with-google = تسجيل الدخول باستخدام Google
+login-with-apple = تسجيل الدخول باستخدام Apple
+login-email-or-username = البريد الإلكتروني أو اسم المستخدم
+login-server = الخادم
+login-another-server = استخدام خادم آخر
+login-continue-as = أو تابع باسم
+login-continue = متابعة
+login-enter-name = أدخل بريدك الإلكتروني أو اسم المستخدم
+login-enter-password = أدخل كلمة المرور
+login-enter-server = أدخل عنوان الخادم، مثل https://bind.example.com
+sign-up-welcome = مرحبًا بك في
+sign-up-with-google = إنشاء حساب باستخدام Google
+sign-up-with-apple = إنشاء حساب باستخدام Apple
+sign-up-checking-username = جارٍ التحقق من توفر اسم المستخدم…
+sign-up-username-taken = اسم المستخدم هذا مستخدم بالفعل
+verify-email-subtitle = تأكيد
+verify-email-title = بريدك الإلكتروني
+verify-email-code-length = يجب أن يتكون الرمز من { $length } أرقام
+verify-email-code-expired = انتهت صلاحية هذا الرمز. أرسل رمزًا جديدًا لتأكيد بريدك الإلكتروني.
+verify-email-submit = إرسال
+verify-email-open-app = فتح تطبيق البريد
+resend = لم يصلك الرمز؟ إعادة الإرسال
+resend-in = إعادة إرسال الرمز خلال { $countdown }
+reset-password-subtitle = إعادة تعيين
+reset-password-title = كلمة المرور
+reset-password-enter-code = أدخل الرمز الموجود في البريد الإلكتروني
+reset-password-code-expired = انتهت صلاحية هذا الرمز. أرسل رمزًا جديدًا لإعادة تعيين كلمة المرور.
+reset-password-instructions = أدخل الرمز الذي أرسلناه إليك بالبريد الإلكتروني. يمكن استخدامه لمدة { $minutes } دقيقة.
+reset-password-new-password = كلمة المرور الجديدة
+oauth-logging-in = جارٍ تسجيل الدخول إلى
+oauth-just-a-moment = لحظة من فضلك...
+oauth-back-to-login = العودة إلى تسجيل الدخول
+oauth-denied = تم إلغاء تسجيل الدخول: { $reason }
+oauth-link-expired = رابط تسجيل الدخول هذا لم يعد صالحًا، يرجى المحاولة مرة أخرى.
+
+## Validation of forms
+
+validation-email-empty = لا يمكن ترك البريد الإلكتروني فارغًا
+validation-email-invalid = البريد الإلكتروني غير صالح
+validation-username-empty = لا يمكن ترك اسم المستخدم فارغًا
+validation-username-too-short = يجب أن يتكون اسم المستخدم من { $min } أحرف على الأقل
+validation-username-too-long = يجب ألا يتجاوز اسم المستخدم { $max } حرفًا
+validation-username-characters = يجب أن يحتوي اسم المستخدم على أحرف وأرقام ونقاط وشرطات وشرطات سفلية فقط
+validation-password-empty = لا يمكن ترك كلمة المرور فارغة
+validation-password-length = يجب أن يتراوح طول كلمة المرور بين { $min } و{ $max } حرفًا
+password-very-weak = ضعيفة جدًا
+password-weak = ضعيفة
+password-fair = مقبولة
+password-strong = قوية
+password-very-strong = قوية جدًا
+password-too-weak = كلمة المرور ضعيفة جدًا.
+password-too-weak-hint = { $warning } جرّب إضافة المزيد من الأحرف والأرقام والرموز. تجنّب الكلمات والعبارات الشائعة.
+password-min-length = استخدم { $min } أحرف على الأقل
+password-crack-time = يمكن كسرها خلال { $duration }
+
+## Navigation
+
+navbar-main = الرئيسية
+navbar-search = بحث
+navbar-feed = الموجز
+navbar-add = إضافة
+navbar-add-feed = إضافة موجز جديد
+navbar-create-list = إنشاء قائمة جديدة
+navbar-create-index = إنشاء فهرس جديد
+navbar-downloads = التنزيلات
+navbar-saved = المحفوظات
+navbar-lists = القوائم
+settings-title = الإعدادات
+
+## Feeds, lists and their items
+
+feed-title = الموجز
+feed-unread = ({ $count } غير مقروء)
+feed-unread-updated = ({ $count } غير مقروء، حُدّث { $updated })
+feed-unread-badge = { $count } غير مقروء
+refresh-updated = حُدّث { $updated }
+list-items = { $count ->
+    [zero] (لا عناصر)
+    [one] (عنصر واحد)
+    [two] (عنصران)
+    [few] ({ $count } عناصر)
+    [many] ({ $count } عنصرًا)
+   *[other] ({ $count } عنصر)
+}
+list-items-updated = { $count ->
+    [zero] (لا عناصر، حُدّث { $updated })
+    [one] (عنصر واحد، حُدّث { $updated })
+    [two] (عنصران، حُدّث { $updated })
+    [few] ({ $count } عناصر، حُدّث { $updated })
+    [many] ({ $count } عنصرًا، حُدّث { $updated })
+   *[other] ({ $count } عنصر، حُدّث { $updated })
+}
+index-no-feeds = لا توجد موجزات تطابق هذا الفهرس.
+add-feed-search = ابحث عن اسم أو موضوع أو عنوان URL
+item-archived = تمت الأرشفة
+item-mark-unread = تعيين كغير مقروء
+item-mark-read = تعيين كمقروء
+item-save-to-list = حفظ في قائمة
+item-share = مشاركة
+item-archive = أرشفة
+item-saved-to-list = تم الحفظ في { $list }
+lists-empty = ليست لديك أي قوائم بعد.
+lists-loading = جارٍ تحميل القوائم...
+activity-per-month = { $count } / شهر
+enclosure-audio = صوت
+enclosure-video = فيديو
+enclosure-image = صورة
+enclosure-attachment = مرفق
+enclosure-play = تشغيل
+enclosure-add-to-up-next = إضافة إلى التالي
+
+## Details of a feed
+
+feed-detail-title = الموجز
+feed-detail-unsubscribed = لم تعد مشتركًا في هذا الموجز.
+feed-detail-mark-read = تعيين كمقروء
+feed-detail-tags = الوسوم
+feed-detail-unsubscribe-title = إلغاء الاشتراك في هذا الموجز؟
+feed-detail-unsubscribe-message = ستُزال عناصره من مخططك الزمني.
+feed-detail-unsubscribe = إلغاء الاشتراك
+feed-detail-activity = النشاط
+tags-tagged-unread = موسوم · { $count } غير مقروء
+tags-unread = { $count } غير مقروء
+feed-status-active = نشط
+feed-status-completed = مكتمل
+feed-status-suspended = معلّق
+feed-status-broken = معطّل
+feed-health-broken = تعذّر جلب هذا الموجز. آخر تحديث ناجح كان { $updated }.
+feed-health-status = الحالة
+feed-health-last-fetched = آخر جلب
+feed-health-last-success = آخر جلب ناجح
+feed-health-next-fetch = الجلب التالي
+mark-read-done = { $count ->
+    [zero] لم يُعيَّن أي عنصر كمقروء
+    [one] تم تعيين عنصر واحد كمقروء
+    [two] تم تعيين عنصرين كمقروءين
+    [few] تم تعيين { $count } عناصر كمقروءة
+    [many] تم تعيين { $count } عنصرًا كمقروء
+   *[other] تم تعيين { $count } عنصر كمقروء
+}
+mark-read-all = كل العناصر
+mark-read-older-than = { $days ->
+    [one] أقدم من يوم
+    [two] أقدم من يومين
+    [few] أقدم من { $days } أيام
+    [many] أقدم من { $days } يومًا
+   *[other] أقدم من { $days } يوم
+}
+mark-read-tag = كل ما وُسم بـ{ $tag }
+mark-read-on-scroll = تعيين كمقروء عند التمرير بعده
+
+## Shared links
+
+shared-link-title = رابط مُشارَك
+shared-link-subscribe-title = الاشتراك في موجز هذا الموقع
+shared-link-no-feeds = لم يُعثر على موجزات لهذا الموقع.
+shared-link-subscribe = اشتراك
+shared-link-looking = جارٍ البحث عن موجزات...
+
+## Downloads and Up Next
+
+downloads-usage = ({ $used } من { $quota })
+downloads-auto = تنزيل العناصر المضافة إلى هذه القوائم تلقائيًا
+downloads-empty = لا توجد تنزيلات. نزّل العناصر من القارئ لقراءتها دون اتصال.
+downloads-enclosures = تنزيل البودكاست والمرفقات الأخرى
+downloads-size-cap = تخطي المرفقات الأكبر من
+downloads-quota = حد التخزين
+downloads-item = العنصر { $id }
+downloads-waiting = في الانتظار
+downloads-progress = جارٍ التنزيل { $percent }٪
+downloads-failed = فشل: { $message }
+downloads-retry = إعادة المحاولة
+up-next-title = التالي
+up-next-count = { $count ->
+    [zero] (لا عناصر)
+    [one] (عنصر واحد)
+    [two] (عنصران)
+    [few] ({ $count } عناصر)
+    [many] ({ $count } عنصرًا)
+   *[other] ({ $count } عنصر)
+}
+up-next-empty = لا شيء في قائمة الانتظار. أضف حلقات البودكاست من القارئ للاستماع إليها تاليًا.
+up-next-play = تشغيل
+up-next-move-up = لأعلى
+
+## Reader
+
+reader-navbar = القارئ
+reader-text-size = حجم النص
+reader-text-size-current = الحالي
+reader-feed-content = محتوى الموجز
+reader-web-page = صفحة الويب
+reader-bookmark = إشارة مرجعية
+reader-retry-download = إعادة محاولة التنزيل
+reader-downloads = التنزيلات
+reader-download = تنزيل
+reader-share = مشاركة
+reader-by = بقلم
+reader-minutes = { $count ->
+    [one] قراءة في دقيقة واحدة
+    [two] قراءة في دقيقتين
+    [few] قراءة في { $count } دقائق
+    [many] قراءة في { $count } دقيقة
+   *[other] قراءة في { $count } دقيقة
+}
+
+## Settings
+
+settings-about = حول
+settings-about-description = قارئ للموجزات والقوائم والفهارس التي تتابعها.
+settings-account = الحساب
+settings-change-password = تغيير كلمة المرور
+settings-switch-account = تبديل الحساب ({ $count })
+settings-add-account = إضافة حساب آخر
+settings-log-out-title = تسجيل الخروج؟
+settings-log-out-message = ستُزال تنزيلات هذا الحساب وإعداداته من هذا الجهاز.
+settings-log-out = تسجيل الخروج
+settings-appearance = المظهر
+settings-language = اللغة
+settings-theme = السمة
+settings-text-size = حجم النص
+settings-reader = القارئ
+settings-open-items-in = فتح العناصر في
+settings-swipe-left = السحب لليسار من أجل
+settings-swipe-right = السحب لليمين من أجل
+settings-mark-on-scroll = تعيين العناصر كمقروءة عند التمرير بعدها
+settings-notifications = الإشعارات والمزامنة
+settings-notify-new-items = إعلامي بالعناصر الجديدة في موجزاتي
+settings-auto-sync = تنزيل العناصر الجديدة في القوائم في الخلفية
+settings-storage = التخزين
+settings-downloads = التنزيلات
+settings-cache = ذاكرة التخزين المؤقت
+settings-manage-downloads = إدارة التنزيلات
+settings-cache-cleared = تم مسح ذاكرة التخزين المؤقت
+settings-clear-cache = مسح ذاكرة التخزين المؤقت
+settings-subscriptions = الاشتراكات
+settings-opml-copied = تم نسخ اشتراكاتك بتنسيق OPML
+settings-import-opml = استيراد من OPML
+settings-export-opml = تصدير بتنسيق OPML
+theme-system = النظام
+theme-light = فاتح
+theme-dark = داكن
+text-size-small = صغير
+text-size-medium = متوسط
+text-size-large = كبير
+reader-mode-feed = محتوى الموجز
+reader-mode-page = صفحة الويب
+item-action-toggle-read = تعيين كمقروء/غير مقروء
+item-action-nothing = لا شيء
+accounts-title = الحسابات
+accounts-add = إضافة حساب
+accounts-active = نشط
+accounts-switch = تبديل
+change-password-current = كلمة المرور الحالية
+change-password-new = كلمة المرور الجديدة
+change-password-confirm = تأكيد كلمة المرور الجديدة
+change-password-enter-current = أدخل كلمة المرور الحالية
+change-password-mismatch = كلمتا المرور الجديدتان غير متطابقتين
+change-password-done = تم تغيير كلمة المرور
+import-title = استيراد الاشتراكات
+import-progress = ({ $subscribed } من { $total })
+import-description = اختر ملف OPML مُصدَّرًا من قارئ موجزات آخر للاشتراك في موجزاته.
+import-no-feeds = لم يُعثر على موجزات في هذا الملف
+import-read-failed = تعذّرت قراءة { $name }
+import-subscribing = جارٍ الاشتراك…
+import-subscribe = { $count ->
+    [one] الاشتراك في موجز واحد
+    [two] الاشتراك في موجزين
+    [few] الاشتراك في { $count } موجزات
+    [many] الاشتراك في { $count } موجزًا
+   *[other] الاشتراك في { $count } موجز
+}
+import-done = تم
+
+## Components of the ui crate
+
+ui-cancel = إلغاء
+ui-confirm = تأكيد
+ui-undo = تراجع
+ui-refreshing = جارٍ التحديث...
+ui-release-to-refresh = أفلت للتحديث
+ui-pull-to-refresh = اسحب للتحديث
+ui-show-password = إظهار كلمة المرور
+ui-hide-password = إخفاء كلمة المرور
+ui-code-character = الخانة { $index } من { $length }
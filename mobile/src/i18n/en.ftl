@@ -0,0 +1,338 @@
+## Shared
+
+loading = Loading...
+remove = Remove
+on = On
+off = Off
+
+## Dates and times
+## The formats of dates are strftime patterns, see chrono::format::strftime
+
+duration-less-than-a-second = less than a second
+duration-seconds = { $count ->
+    [one] { $count } second
+   *[other] { $count } seconds
+}
+duration-minutes = { $count ->
+    [one] { $count } minute
+   *[other] { $count } minutes
+}
+duration-hours = { $count ->
+    [one] { $count } hour
+   *[other] { $count } hours
+}
+duration-days = { $count ->
+    [one] { $count } day
+   *[other] { $count } days
+}
+duration-months = { $count ->
+    [one] { $count } month
+   *[other] { $count } months
+}
+duration-years = { $count ->
+    [one] { $count } year
+   *[other] { $count } years
+}
+duration-centuries = centuries
+time-just-now = just now
+time-ago = { $duration } ago
+time-in = in { $duration }
+day-today = Today
+day-yesterday = Yesterday
+day-this-year = %A, %B %-d
+day-other-year = %A, %B %-d, %Y
+day-undated = Undated
+date = %-d %b %Y
+
+## Errors
+
+error-unknown-server = Unknown error from server ({ $status })
+error-rate-limited = { $message }. Try again in { $seconds }s.
+error-offline = You're offline. Check your connection and try again.
+error-timeout = The server took too long to respond.
+error-invalid-response = Received an invalid response from the server.
+error-feed-not-found = Feed { $id } not found
+error-item-not-found = Item { $id } not found
+error-index-not-found = Index { $id } not found
+error-no-feed-for-site = No feed found for this site
+error-page-not-in-feed = This page isn't in its site's feed
+error-logged-out = Logged out
+error-logged-out-message = Your session has expired. Log in again to continue.
+error-not-allowed = Not allowed
+error-not-allowed-message = You don't have access to this.
+error-not-found = Not found
+error-not-found-message = This doesn't exist anymore, or it was moved.
+error-no-connection = No connection
+error-something-went-wrong = Something went wrong
+error-try-again = Try again
+error-go-back = Go back
+deep-link-unsupported = This link can't be opened in the app
+share-links-only = Only links can be shared with Bind
+
+## Logging in and signing up
+
+auth-email = Email
+auth-username = Username
+auth-password = Password
+auth-login = Login
+auth-sign-up = Sign up
+auth-reset-password = Reset Password
+auth-go-back = Go Back
+login-welcome-back = Welcome back to
+login-with-google = Login with Google
+login-with-apple = Login with Apple
+login-email-or-username = Email or Username
+login-server = Server
+login-another-server = Use another server
+login-continue-as = Or continue as
+login-continue = Continue
+login-enter-name = Enter your email or username
+login-enter-password = Enter your password
+login-enter-server = Enter the address of the server, like https://bind.example.com
+sign-up-welcome = Welcome to
+sign-up-with-google = Sign up with Google
+sign-up-with-apple = Sign up with Apple
+sign-up-checking-username = Checking if the username is available…
+sign-up-username-taken = This username is taken
+verify-email-subtitle = Verify your
+verify-email-title = Email
+verify-email-code-length = Code must be { $length } digits long
+verify-email-code-expired = This code has expired. Send a new one to verify your email.
+verify-email-submit = Submit
+verify-email-open-app = Open Email App
+resend = Didn't receive the code? Resend
+resend-in = Resend the code in { $countdown }
+reset-password-subtitle = Reset your
+reset-password-title = Password
+reset-password-enter-code = Enter the code from the email
+reset-password-code-expired = This code has expired. Send a new one to reset your password.
+reset-password-instructions = Enter the code we emailed you. It can be used for { $minutes } minutes.
+reset-password-new-password = New Password
+oauth-logging-in = Logging in to
+oauth-just-a-moment = Just a moment...
+oauth-back-to-login = Back to login
+oauth-denied = Login was cancelled: { $reason }
+oauth-link-expired = This login link is no longer valid, please try again.
+
+## Validation of forms
+
+validation-email-empty = Email cannot be empty
+validation-email-invalid = Email is invalid
+validation-username-empty = Username cannot be empty
+validation-username-too-short = Username must be at least { $min } characters long
+validation-username-too-long = Username must be at most { $max } characters long
+validation-username-characters = Username must contain only letters, numbers, dots, dashes, and underscores
+validation-password-empty = Password cannot be empty
+validation-password-length = Password must be between { $min } and { $max } characters long
+password-very-weak = Very weak
+password-weak = Weak
+password-fair = Fair
+password-strong = Strong
+password-very-strong = Very strong
+password-too-weak = Password is too weak.
+password-too-weak-hint = { $warning } Try adding more letters, numbers and symbols. Avoid common words and phrases.
+password-min-length = Use at least { $min } characters
+password-crack-time = Cracked in { $duration }
+
+## Navigation
+
+navbar-main = Main
+navbar-search = Search
+navbar-feed = Feed
+navbar-add = Add
+navbar-add-feed = Add New Feed
+navbar-create-list = Create New List
+navbar-create-index = Create New Index
+navbar-downloads = Downloads
+navbar-saved = Saved
+navbar-lists = Lists
+settings-title = Settings
+
+## Feeds, lists and their items
+
+feed-title = Feed
+feed-unread = ({ $count } unread)
+feed-unread-updated = ({ $count } unread, updated { $updated })
+feed-unread-badge = { $count } unread
+refresh-updated = Updated { $updated }
+list-items = { $count ->
+    [one] ({ $count } item)
+   *[other] ({ $count } items)
+}
+list-items-updated = { $count ->
+    [one] ({ $count } item, updated { $updated })
+   *[other] ({ $count } items, updated { $updated })
+}
+index-no-feeds = No feeds match this index.
+add-feed-search = Search for a name, topic, or URL
+item-archived = Archived
+item-mark-unread = Mark as unread
+item-mark-read = Mark as read
+item-save-to-list = Save to list
+item-share = Share
+item-archive = Archive
+item-saved-to-list = Saved to { $list }
+lists-empty = You don't have any lists yet.
+lists-loading = Loading lists...
+activity-per-month = { $count } / mo
+enclosure-audio = Audio
+enclosure-video = Video
+enclosure-image = Image
+enclosure-attachment = Attachment
+enclosure-play = Play
+enclosure-add-to-up-next = Add to Up Next
+
+## Details of a feed
+
+feed-detail-title = Feed
+feed-detail-unsubscribed = You're not subscribed to this feed anymore.
+feed-detail-mark-read = Mark as read
+feed-detail-tags = Tags
+feed-detail-unsubscribe-title = Unsubscribe from this feed?
+feed-detail-unsubscribe-message = Its items are removed from your timeline.
+feed-detail-unsubscribe = Unsubscribe
+feed-detail-activity = Activity
+tags-tagged-unread = Tagged · { $count } unread
+tags-unread = { $count } unread
+feed-status-active = Active
+feed-status-completed = Completed
+feed-status-suspended = Suspended
+feed-status-broken = Broken
+feed-health-broken = This feed could not be fetched. It was last updated successfully { $updated }.
+feed-health-status = Status
+feed-health-last-fetched = Last fetched
+feed-health-last-success = Last successful fetch
+feed-health-next-fetch = Next fetch
+mark-read-done = { $count ->
+    [one] Marked { $count } item as read
+   *[other] Marked { $count } items as read
+}
+mark-read-all = All items
+mark-read-older-than = { $days ->
+    [one] Older than a day
+   *[other] Older than { $days } days
+}
+mark-read-tag = Everything tagged { $tag }
+mark-read-on-scroll = Mark as read when scrolled past
+
+## Shared links
+
+shared-link-title = Shared Link
+shared-link-subscribe-title = Subscribe to this site's feed
+shared-link-no-feeds = No feeds found for this site.
+shared-link-subscribe = Subscribe
+shared-link-looking = Looking for feeds...
+
+## Downloads and Up Next
+
+downloads-usage = ({ $used } of { $quota })
+downloads-auto = Automatically download items added to
+downloads-empty = Nothing downloaded. Download items from the reader to read them offline.
+downloads-enclosures = Download podcasts and other attachments
+downloads-size-cap = Skip attachments over
+downloads-quota = Storage limit
+downloads-item = Item { $id }
+downloads-waiting = Waiting
+downloads-progress = Downloading { $percent }%
+downloads-failed = Failed: { $message }
+downloads-retry = Retry
+up-next-title = Up Next
+up-next-count = { $count ->
+    [one] ({ $count } item)
+   *[other] ({ $count } items)
+}
+up-next-empty = Nothing queued. Add podcast episodes from the reader to listen to them next.
+up-next-play = Play
+up-next-move-up = Up
+
+## Reader
+
+reader-navbar = Reader
+reader-text-size = Text size
+reader-text-size-current = Current
+reader-feed-content = Feed content
+reader-web-page = Web page
+reader-bookmark = Bookmark
+reader-retry-download = Retry download
+reader-downloads = Downloads
+reader-download = Download
+reader-share = Share
+reader-by = By
+reader-minutes = { $count } min read
+
+## Settings
+
+settings-about = About
+settings-about-description = A reader for the feeds, lists and indexes you follow.
+settings-account = Account
+settings-change-password = Change password
+settings-switch-account = Switch account ({ $count })
+settings-add-account = Add another account
+settings-log-out-title = Log out?
+settings-log-out-message = Downloads and settings of this account are removed from this device.
+settings-log-out = Log out
+settings-appearance = Appearance
+settings-language = Language
+settings-theme = Theme
+settings-text-size = Text size
+settings-reader = Reader
+settings-open-items-in = Open items in
+settings-swipe-left = Swipe left to
+settings-swipe-right = Swipe right to
+settings-mark-on-scroll = Mark items as read when scrolling past them
+settings-notifications = Notifications and sync
+settings-notify-new-items = Notify me about new items in my feeds
+settings-auto-sync = Download new items of lists in the background
+settings-storage = Storage
+settings-downloads = Downloads
+settings-cache = Cache
+settings-manage-downloads = Manage downloads
+settings-cache-cleared = Cache cleared
+settings-clear-cache = Clear cache
+settings-subscriptions = Subscriptions
+settings-opml-copied = Copied your subscriptions as OPML
+settings-import-opml = Import from OPML
+settings-export-opml = Export as OPML
+theme-system = System
+theme-light = Light
+theme-dark = Dark
+text-size-small = Small
+text-size-medium = Medium
+text-size-large = Large
+reader-mode-feed = Feed content
+reader-mode-page = Web page
+item-action-toggle-read = Mark as read/unread
+item-action-nothing = Do nothing
+accounts-title = Accounts
+accounts-add = Add account
+accounts-active = Active
+accounts-switch = Switch
+change-password-current = Current password
+change-password-new = New password
+change-password-confirm = Confirm new password
+change-password-enter-current = Enter your current password
+change-password-mismatch = The new passwords don't match
+change-password-done = Password changed
+import-title = Import subscriptions
+import-progress = ({ $subscribed } of { $total })
+import-description = Choose an OPML file exported from another feed reader to subscribe to its feeds.
+import-no-feeds = No feeds found in this file
+import-read-failed = Failed to read { $name }
+import-subscribing = Subscribing…
+import-subscribe = { $count ->
+    [one] Subscribe to { $count } feed
+   *[other] Subscribe to { $count } feeds
+}
+import-done = Done
+
+## Components of the ui crate
+
+ui-cancel = Cancel
+ui-confirm = Confirm
+ui-undo = Undo
+ui-refreshing = Refreshing...
+ui-release-to-refresh = Release to refresh
+ui-pull-to-refresh = Pull to refresh
+ui-show-password = Show password
+ui-hide-password = Hide password
+ui-code-character = Character { $index } of { $length }
@@ -0,0 +1,302 @@
+## Shared
+
+loading = 読み込み中...
+remove = 削除
+on = オン
+off = オフ
+
+## Dates and times
+## The formats of dates are strftime patterns, see chrono::format::strftime
+
+duration-less-than-a-second = 1秒未満
+duration-seconds = { $count }秒
+duration-minutes = { $count }分
+duration-hours = { $count }時間
+duration-days = { $count }日
+duration-months = { $count }か月
+duration-years = { $count }年
+duration-centuries = 数世紀
+time-just-now = たった今
+time-ago = { $duration }前
+time-in = { $duration }後
+day-today = 今日
+day-yesterday = 昨日
+day-this-year = %-m月%-d日(%a)
+day-other-year = %Y年%-m月%-d日(%a)
+day-undated = 日付なし
+date = %Y年%-m月%-d日
+
+## Errors
+
+error-unknown-server = サーバーで不明なエラーが発生しました ({ $status })
+error-rate-limited = { $message }。{ $seconds }秒後にもう一度お試しください。
+error-offline = オフラインです。接続を確認してもう一度お試しください。
+error-timeout = サーバーの応答に時間がかかりすぎました。
+error-invalid-response = サーバーから無効な応答を受け取りました。
+error-feed-not-found = フィード { $id } が見つかりません
+error-item-not-found = アイテム { $id } が見つかりません
+error-index-not-found = インデックス { $id } が見つかりません
+error-no-feed-for-site = このサイトのフィードが見つかりません
+error-page-not-in-feed = このページはサイトのフィードに含まれていません
+error-logged-out = ログアウトしました
+error-logged-out-message = セッションの有効期限が切れました。続けるにはもう一度ログインしてください。
+error-not-allowed = 許可されていません
+error-not-allowed-message = これにアクセスする権限がありません。
+error-not-found = 見つかりません
+error-not-found-message = これは存在しないか、移動されました。
+error-no-connection = 接続がありません
+error-something-went-wrong = 問題が発生しました
+error-try-again = 再試行
+error-go-back = 戻る
+deep-link-unsupported = このリンクはアプリで開けません
+share-links-only = Bindで共有できるのはリンクのみです
+
+## Logging in and signing up
+
+auth-email = メールアドレス
+auth-username = ユーザー名
+auth-password = パスワード
+auth-login = ログイン
+auth-sign-up = 新規登録
+auth-reset-password = パスワードを再設定
+auth-go-back = 戻る
+login-welcome-back = おかえりなさい
+login-with-google = Googleでログイン
+login-with-apple = Appleでログイン
+login-email-or-username = メールアドレスまたはユーザー名
+login-server = サーバー
+login-another-server = 別のサーバーを使う
+login-continue-as = または次のアカウントで続ける
+login-continue = 続ける
+login-enter-name = メールアドレスまたはユーザー名を入力してください
+login-enter-password = パスワードを入力してください
+login-enter-server = サーバーのアドレスを入力してください (例: https://bind.example.com)
+sign-up-welcome = ようこそ
+sign-up-with-google = Googleで登録
+sign-up-with-apple = Appleで登録
+sign-up-checking-username = ユーザー名が使えるか確認しています…
+sign-up-username-taken = このユーザー名は使用されています
+verify-email-subtitle = 確認してください
+verify-email-title = メールアドレス
+verify-email-code-length = コードは{ $length }桁で入力してください
+verify-email-code-expired = このコードは有効期限が切れています。新しいコードを送信してメールアドレスを確認してください。
+verify-email-submit = 送信
+verify-email-open-app = メールアプリを開く
+resend = コードが届きませんか？再送信
+resend-in = { $countdown }後にコードを再送信できます
+reset-password-subtitle = 再設定
+reset-password-title = パスワード
+reset-password-enter-code = メールに記載されたコードを入力してください
+reset-password-code-expired = このコードは有効期限が切れています。新しいコードを送信してパスワードを再設定してください。
+reset-password-instructions = メールで送信したコードを入力してください。コードは{ $minutes }分間有効です。
+reset-password-new-password = 新しいパスワード
+oauth-logging-in = ログインしています
+oauth-just-a-moment = しばらくお待ちください...
+oauth-back-to-login = ログインに戻る
+oauth-denied = ログインがキャンセルされました: { $reason }
+oauth-link-expired = このログインリンクは無効になりました。もう一度お試しください。
+
+## Validation of forms
+
+validation-email-empty = メールアドレスを入力してください
+validation-email-invalid = メールアドレスが無効です
+validation-username-empty = ユーザー名を入力してください
+validation-username-too-short = ユーザー名は{ $min }文字以上にしてください
+validation-username-too-long = ユーザー名は{ $max }文字以下にしてください
+validation-username-characters = ユーザー名には英字、数字、ピリオド、ハイフン、アンダースコアのみ使用できます
+validation-password-empty = パスワードを入力してください
+validation-password-length = パスワードは{ $min }〜{ $max }文字にしてください
+password-very-weak = 非常に弱い
+password-weak = 弱い
+password-fair = 普通
+password-strong = 強い
+password-very-strong = 非常に強い
+password-too-weak = パスワードが弱すぎます。
+password-too-weak-hint = { $warning } 文字、数字、記号を増やしてみてください。よく使われる単語やフレーズは避けてください。
+password-min-length = { $min }文字以上にしてください
+password-crack-time = 解読までの時間: { $duration }
+
+## Navigation
+
+navbar-main = メイン
+navbar-search = 検索
+navbar-feed = フィード
+navbar-add = 追加
+navbar-add-feed = フィードを追加
+navbar-create-list = リストを作成
+navbar-create-index = インデックスを作成
+navbar-downloads = ダウンロード
+navbar-saved = 保存済み
+navbar-lists = リスト
+settings-title = 設定
+
+## Feeds, lists and their items
+
+feed-title = フィード
+feed-unread = (未読 { $count }件)
+feed-unread-updated = (未読 { $count }件、{ $updated }に更新)
+feed-unread-badge = 未読 { $count }件
+refresh-updated = { $updated }に更新
+list-items = ({ $count }件)
+list-items-updated = ({ $count }件、{ $updated }に更新)
+index-no-feeds = このインデックスに一致するフィードはありません。
+add-feed-search = 名前、トピック、URLで検索
+item-archived = アーカイブしました
+item-mark-unread = 未読にする
+item-mark-read = 既読にする
+item-save-to-list = リストに保存
+item-share = 共有
+item-archive = アーカイブ
+item-saved-to-list = { $list }に保存しました
+lists-empty = リストはまだありません。
+lists-loading = リストを読み込み中...
+activity-per-month = 月{ $count }件
+enclosure-audio = 音声
+enclosure-video = 動画
+enclosure-image = 画像
+enclosure-attachment = 添付ファイル
+enclosure-play = 再生
+enclosure-add-to-up-next = 次に再生に追加
+
+## Details of a feed
+
+feed-detail-title = フィード
+feed-detail-unsubscribed = このフィードの購読は解除されています。
+feed-detail-mark-read = 既読にする
+feed-detail-tags = タグ
+feed-detail-unsubscribe-title = このフィードの購読を解除しますか？
+feed-detail-unsubscribe-message = フィードのアイテムはタイムラインから削除されます。
+feed-detail-unsubscribe = 購読解除
+feed-detail-activity = アクティビティ
+tags-tagged-unread = タグ付け済み · 未読 { $count }件
+tags-unread = 未読 { $count }件
+feed-status-active = 有効
+feed-status-completed = 完了
+feed-status-suspended = 一時停止
+feed-status-broken = 取得不可
+feed-health-broken = このフィードを取得できませんでした。最後に取得に成功したのは{ $updated }です。
+feed-health-status = 状態
+feed-health-last-fetched = 最終取得
+feed-health-last-success = 最終取得成功
+feed-health-next-fetch = 次回取得
+mark-read-done = { $count }件を既読にしました
+mark-read-all = すべてのアイテム
+mark-read-older-than = { $days }日より前
+mark-read-tag = 「{ $tag }」タグのすべて
+mark-read-on-scroll = スクロールしたら既読にする
+
+## Shared links
+
+shared-link-title = 共有されたリンク
+shared-link-subscribe-title = このサイトのフィードを購読
+shared-link-no-feeds = このサイトのフィードは見つかりませんでした。
+shared-link-subscribe = 購読
+shared-link-looking = フィードを探しています...
+
+## Downloads and Up Next
+
+downloads-usage = ({ $used } / { $quota })
+downloads-auto = 次のリストに追加されたアイテムを自動でダウンロード
+downloads-empty = ダウンロードしたアイテムはありません。リーダーからダウンロードするとオフラインで読めます。
+downloads-enclosures = ポッドキャストなどの添付ファイルをダウンロード
+downloads-size-cap = 次のサイズを超える添付ファイルをスキップ
+downloads-quota = 保存容量の上限
+downloads-item = アイテム { $id }
+downloads-waiting = 待機中
+downloads-progress = ダウンロード中 { $percent }%
+downloads-failed = 失敗: { $message }
+downloads-retry = 再試行
+up-next-title = 次に再生
+up-next-count = ({ $count }件)
+up-next-empty = キューは空です。リーダーからポッドキャストのエピソードを追加すると、次に聴けます。
+up-next-play = 再生
+up-next-move-up = 上へ
+
+## Reader
+
+reader-navbar = リーダー
+reader-text-size = 文字サイズ
+reader-text-size-current = 現在
+reader-feed-content = フィードの内容
+reader-web-page = ウェブページ
+reader-bookmark = ブックマーク
+reader-retry-download = ダウンロードを再試行
+reader-downloads = ダウンロード
+reader-download = ダウンロード
+reader-share = 共有
+reader-by = 著者:
+reader-minutes = { $count }分で読めます
+
+## Settings
+
+settings-about = このアプリについて
+settings-about-description = フォローしているフィード、リスト、インデックスを読むためのリーダーです。
+settings-account = アカウント
+settings-change-password = パスワードを変更
+settings-switch-account = アカウントを切り替え ({ $count })
+settings-add-account = 別のアカウントを追加
+settings-log-out-title = ログアウトしますか？
+settings-log-out-message = このアカウントのダウンロードと設定はこのデバイスから削除されます。
+settings-log-out = ログアウト
+settings-appearance = 外観
+settings-language = 言語
+settings-theme = テーマ
+settings-text-size = 文字サイズ
+settings-reader = リーダー
+settings-open-items-in = アイテムを開く方法
+settings-swipe-left = 左スワイプの操作
+settings-swipe-right = 右スワイプの操作
+settings-mark-on-scroll = スクロールしたアイテムを既読にする
+settings-notifications = 通知と同期
+settings-notify-new-items = フィードの新着アイテムを通知する
+settings-auto-sync = リストの新着アイテムをバックグラウンドでダウンロード
+settings-storage = ストレージ
+settings-downloads = ダウンロード
+settings-cache = キャッシュ
+settings-manage-downloads = ダウンロードを管理
+settings-cache-cleared = キャッシュを消去しました
+settings-clear-cache = キャッシュを消去
+settings-subscriptions = 購読
+settings-opml-copied = 購読をOPMLとしてコピーしました
+settings-import-opml = OPMLからインポート
+settings-export-opml = OPMLとしてエクスポート
+theme-system = システム
+theme-light = ライト
+theme-dark = ダーク
+text-size-small = 小
+text-size-medium = 中
+text-size-large = 大
+reader-mode-feed = フィードの内容
+reader-mode-page = ウェブページ
+item-action-toggle-read = 既読/未読を切り替え
+item-action-nothing = 何もしない
+accounts-title = アカウント
+accounts-add = アカウントを追加
+accounts-active = 使用中
+accounts-switch = 切り替え
+change-password-current = 現在のパスワード
+change-password-new = 新しいパスワード
+change-password-confirm = 新しいパスワード (確認)
+change-password-enter-current = 現在のパスワードを入力してください
+change-password-mismatch = 新しいパスワードが一致しません
+change-password-done = パスワードを変更しました
+import-title = 購読をインポート
+import-progress = ({ $subscribed } / { $total })
+import-description = 他のフィードリーダーからエクスポートしたOPMLファイルを選ぶと、そのフィードを購読できます。
+import-no-feeds = このファイルにフィードは見つかりませんでした
+import-read-failed = { $name } を読み込めませんでした
+import-subscribing = 購読しています…
+import-subscribe = { $count }件のフィードを購読
+import-done = 完了
+
+## Components of the ui crate
+
+ui-cancel = キャンセル
+ui-confirm = 確認
+ui-undo = 元に戻す
+ui-refreshing = 更新中...
+ui-release-to-refresh = 離して更新
+ui-pull-to-refresh = 引っ張って更新
+ui-show-password = パスワードを表示
+ui-hide-password = パスワードを非表示
+ui-code-character = { $length }文字中{ $index }文字目
@@ -0,0 +1,185 @@
+//! Translations of the app's text into the languages the user can choose from.
+//!
+//! Text is looked up by its id in the Fluent catalog of the language, e.g.
+//! `t!("feed-unread", count = 3)`, which chooses the plural form of the language. Messages
+//! missing from a catalog are shown in English.
+
+use std::{cell::Cell, sync::LazyLock};
+
+use dioxus::prelude::*;
+use fluent_bundle::{concurrent::FluentBundle, FluentArgs, FluentResource};
+use serde::{Deserialize, Serialize};
+use ui::text::{use_texts_provider, UiText};
+use unic_langid::LanguageIdentifier;
+
+use crate::{
+    hooks::use_persistent,
+    platform::{Namespace, StoreKey},
+};
+
+/// Translates the message with the id into the user's language, with its arguments given by
+/// name, e.g. `t!("feed-unread", count = unread)`
+macro_rules! t {
+    ($id:literal $(, $name:ident = $value:expr)* $(,)?) => {{
+        #[allow(unused_mut)]
+        let mut args = fluent_bundle::FluentArgs::new();
+        $(args.set(stringify!($name), $value);)*
+        $crate::i18n::translate($id, &args)
+    }};
+}
+
+pub(crate) use t;
+
+const LANGUAGE: StoreKey<Language> = StoreKey::new(Namespace::Preferences, "language");
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Language {
+    #[default]
+    English,
+    Arabic,
+    Japanese,
+}
+
+impl Language {
+    pub const ALL: [Language; 3] = [Language::English, Language::Arabic, Language::Japanese];
+
+    /// Name of the language in itself, so it can be found by those who speak it
+    pub fn label(self) -> &'static str {
+        match self {
+            Language::English => "English",
+            Language::Arabic => "العربية",
+            Language::Japanese => "日本語",
+        }
+    }
+
+    /// BCP 47 tag of the language, for the `lang` attribute of the document
+    pub fn code(self) -> &'static str {
+        match self {
+            Language::English => "en",
+            Language::Arabic => "ar",
+            Language::Japanese => "ja",
+        }
+    }
+
+    /// The language is written from right to left
+    pub fn is_rtl(self) -> bool {
+        self == Language::Arabic
+    }
+
+    /// Locale dates are formatted in, with the names of the days and months of the language
+    pub fn locale(self) -> chrono::Locale {
+        match self {
+            Language::English => chrono::Locale::en_US,
+            Language::Arabic => chrono::Locale::ar_SA,
+            Language::Japanese => chrono::Locale::ja_JP,
+        }
+    }
+
+    fn catalog(self) -> &'static str {
+        match self {
+            Language::English => include_str!("en.ftl"),
+            Language::Arabic => include_str!("ar.ftl"),
+            Language::Japanese => include_str!("ja.ftl"),
+        }
+    }
+
+    fn index(self) -> usize {
+        Self::ALL
+            .iter()
+            .position(|language| *language == self)
+            .unwrap_or_default()
+    }
+}
+
+static BUNDLES: LazyLock<Vec<FluentBundle<FluentResource>>> = LazyLock::new(|| {
+    Language::ALL
+        .into_iter()
+        .map(|language| {
+            let id: LanguageIdentifier = language.code().parse().expect("invalid language code");
+            let mut bundle = FluentBundle::new_concurrent(vec![id]);
+            // Isolates the arguments from the text around them, so a name written left to
+            // right doesn't reorder the words of a message written right to left
+            bundle.set_use_isolating(language.is_rtl());
+            let resource = FluentResource::try_new(language.catalog().to_string()).unwrap_or_else(
+                |(resource, errors)| {
+                    tracing::error!("Invalid {language:?} catalog: {errors:?}");
+                    resource
+                },
+            );
+            if let Err(errors) = bundle.add_resource(resource) {
+                tracing::error!("Duplicate messages in {language:?} catalog: {errors:?}");
+            }
+            bundle
+        })
+        .collect()
+});
+
+thread_local! {
+    /// Language chosen by the user, once it's loaded by [use_language_provider]
+    static CURRENT: Cell<Option<Signal<Language>>> = const { Cell::new(None) };
+}
+
+/// Loads the language chosen by the user and applies it to the document, including the direction
+/// it's written in. Must be called once, in the root of the app, so everything can be translated.
+pub fn use_language_provider() -> Signal<Language> {
+    let language = use_persistent(LANGUAGE, Language::default);
+    use_hook(|| CURRENT.set(Some(language)));
+
+    use_effect(move || {
+        let language = language();
+        let dir = if language.is_rtl() { "rtl" } else { "ltr" };
+        document::eval(&format!(
+            "document.documentElement.lang = '{}'; document.documentElement.dir = '{dir}';",
+            language.code()
+        ));
+    });
+
+    use_texts_provider(|text| match text {
+        UiText::Cancel => t!("ui-cancel"),
+        UiText::Confirm => t!("ui-confirm"),
+        UiText::Undo => t!("ui-undo"),
+        UiText::Refreshing => t!("ui-refreshing"),
+        UiText::ReleaseToRefresh => t!("ui-release-to-refresh"),
+        UiText::PullToRefresh => t!("ui-pull-to-refresh"),
+        UiText::ShowPassword => t!("ui-show-password"),
+        UiText::HidePassword => t!("ui-hide-password"),
+        UiText::CodeCharacter { index, length } => {
+            t!("ui-code-character", index = index + 1, length = length)
+        }
+    });
+
+    use_context_provider(|| language)
+}
+
+/// The language chosen by the user, which can be changed through it
+pub fn use_language() -> Signal<Language> {
+    use_context::<Signal<Language>>()
+}
+
+/// The language chosen by the user. Components which read it are rendered again when it
+/// changes, as are those which translate text with [t].
+pub fn current_language() -> Language {
+    CURRENT.get().map(|language| language()).unwrap_or_default()
+}
+
+/// Formats the message with the id in the current language, see [t]
+pub fn translate(id: &str, args: &FluentArgs) -> String {
+    let language = current_language();
+    format_message(language, id, args)
+        .or_else(|| format_message(Language::English, id, args))
+        .unwrap_or_else(|| {
+            tracing::warn!("Missing message {id}");
+            id.to_string()
+        })
+}
+
+fn format_message(language: Language, id: &str, args: &FluentArgs) -> Option<String> {
+    let bundle = &BUNDLES[language.index()];
+    let pattern = bundle.get_message(id)?.value()?;
+    let mut errors = Vec::new();
+    let message = bundle.format_pattern(pattern, Some(args), &mut errors);
+    if !errors.is_empty() {
+        tracing::warn!("Failed to format {id} in {language:?}: {errors:?}");
+    }
+    Some(message.into_owned())
+}
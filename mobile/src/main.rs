@@ -1,7 +1,5 @@
 #![feature(lock_value_accessors)]
 
-use api::types::user::AuthUser;
-use api::ApiClient;
 use dioxus::dioxus_core::LaunchConfig;
 use dioxus::mobile::{use_window, window, wry::WebView, Config, WindowBuilder};
 use dioxus::prelude::*;
@@ -9,12 +7,24 @@ use dioxus_router::prelude::*;
 
 mod api;
 mod components;
+mod deep_link;
 mod hooks;
+mod i18n;
+mod oauth;
+mod offline;
+mod opml;
 mod platform;
+mod playback;
+mod read_state;
+mod settings;
+mod store;
 mod views;
 
 use components::container::FixedSizeContainer;
+use hooks::use_connectivity_monitor;
+use i18n::use_language_provider;
 use platform::use_platform_setup;
+use store::use_store_provider;
 use views::Route;
 
 const THEME_CSS: Asset = asset!("/assets/theme.css");
@@ -29,19 +39,12 @@ fn main() {
         .launch(App);
 }
 
-#[derive(Clone)]
-struct AuthContext {
-    user: Option<AuthUser>,
-}
-
-#[derive(Clone)]
-struct AppContext {
-    api_client: ApiClient,
-}
-
 #[component]
 fn App() -> Element {
     use_platform_setup();
+    use_language_provider();
+    use_connectivity_monitor();
+    use_store_provider();
 
     rsx! {
         // Global app resources
@@ -0,0 +1,89 @@
+//! Login with OAuth providers.
+//!
+//! [start] opens the provider's authorization page in the browser, which redirects back to the
+//! app with a `bind://oauth/callback` link once the user logs in. [complete] checks the callback
+//! against the saved [OAuthSession] and exchanges its code for a token.
+
+use chrono::Utc;
+use thiserror::Error;
+
+use crate::{
+    api::{types::user::OAuthProvider, ApiClient, ApiClientError},
+    i18n::t,
+    platform::{Namespace, SecureStore, StoreKey},
+};
+
+mod session;
+
+pub use session::{Callback, CallbackParams, OAuthSession};
+
+const SESSION: StoreKey<OAuthSession> = StoreKey::new(Namespace::Secrets, "oauth_session");
+
+#[derive(Error, Debug, Clone, PartialEq)]
+pub enum OAuthError {
+    #[error("Invalid callback: {0}")]
+    InvalidCallback(String),
+    /// The user or the provider cancelled the login
+    #[error("Login denied: {0}")]
+    Denied(String),
+    #[error("No login in progress")]
+    NoSession,
+    #[error("Login expired")]
+    Expired,
+    /// The callback is for a login the app didn't start
+    #[error("State mismatch")]
+    StateMismatch,
+    #[error("Failed to open the browser: {0}")]
+    Browser(String),
+    /// The session couldn't be saved or read
+    #[error("Storage error: {0}")]
+    Storage(String),
+    #[error(transparent)]
+    Api(#[from] ApiClientError),
+}
+
+impl OAuthError {
+    pub fn message(&self) -> String {
+        match self {
+            OAuthError::Denied(reason) => t!("oauth-denied", reason = reason.clone()),
+            OAuthError::NoSession | OAuthError::Expired | OAuthError::StateMismatch => {
+                t!("oauth-link-expired")
+            }
+            OAuthError::Api(err) => err.message(),
+            err => err.to_string(),
+        }
+    }
+}
+
+/// Starts logging in with the provider, opening its authorization page in the browser
+pub async fn start(api: &ApiClient, provider: OAuthProvider) -> Result<(), OAuthError> {
+    let session = OAuthSession::new(provider, &api.base_url(), Utc::now());
+    let location = api
+        .authorize_user(provider, &session.state, &session.code_challenge())
+        .await?;
+
+    SecureStore::set(&SESSION, &session).map_err(|err| OAuthError::Storage(err.to_string()))?;
+    webbrowser::open(&location).map_err(|err| OAuthError::Browser(err.to_string()))
+}
+
+/// Completes the login started with [start], returning the token
+pub async fn complete(api: &ApiClient, callback: Callback) -> Result<String, OAuthError> {
+    let session = SecureStore::get(&SESSION)
+        .map_err(|err| OAuthError::Storage(err.to_string()))?
+        .ok_or(OAuthError::NoSession)?;
+    // Each login can only be completed once
+    SecureStore::remove(&SESSION).map_err(|err| OAuthError::Storage(err.to_string()))?;
+    session.validate(&callback, Utc::now())?;
+    // The token is for the account on the server the login was started on
+    api.set_base_url(&session.server);
+
+    match callback {
+        Callback::Token { token, .. } => Ok(token),
+        Callback::Code { code, .. } => {
+            let response = api
+                .exchange_oauth_code(session.provider, &code, &session.code_verifier)
+                .await?;
+            Ok(response.token)
+        }
+    }
+}
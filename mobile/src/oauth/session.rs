@@ -0,0 +1,289 @@
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use chrono::{DateTime, Duration, Utc};
+use rand::{distr::Alphanumeric, Rng};
+use reqwest::Url;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use super::OAuthError;
+use crate::api::types::user::OAuthProvider;
+
+/// Scheme and host of the links the server redirects back to, e.g. `bind://oauth/callback?...`
+pub const CALLBACK_SCHEME: &str = "bind";
+pub const CALLBACK_HOST: &str = "oauth";
+
+/// How long the user has to log in with the provider
+const SESSION_TTL: Duration = Duration::minutes(10);
+
+/// A login with an OAuth provider which was started, but hasn't returned to the app yet.
+/// Saved while the user is in the browser, since the app may be closed in the meantime.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct OAuthSession {
+    pub provider: OAuthProvider,
+    /// Base URL of the server the login was started on, which the code has to be exchanged
+    /// with. The app may have been restarted with another server in the meantime.
+    pub server: String,
+    /// Random value the callback has to return, so callbacks the app didn't ask for are rejected
+    pub state: String,
+    /// PKCE verifier, proving the code is exchanged by the app which started the login
+    pub code_verifier: String,
+    pub started_at: DateTime<Utc>,
+}
+
+impl OAuthSession {
+    pub fn new(provider: OAuthProvider, server: &str, now: DateTime<Utc>) -> Self {
+        Self {
+            provider,
+            server: server.to_string(),
+            state: random_string(32),
+            code_verifier: random_string(64),
+            started_at: now,
+        }
+    }
+
+    /// PKCE challenge for the `S256` method
+    pub fn code_challenge(&self) -> String {
+        URL_SAFE_NO_PAD.encode(Sha256::digest(self.code_verifier.as_bytes()))
+    }
+
+    /// Checks the callback belongs to this login
+    pub fn validate(&self, callback: &Callback, now: DateTime<Utc>) -> Result<(), OAuthError> {
+        if now - self.started_at > SESSION_TTL {
+            return Err(OAuthError::Expired);
+        }
+        if !constant_time_eq(callback.state().as_bytes(), self.state.as_bytes()) {
+            return Err(OAuthError::StateMismatch);
+        }
+        Ok(())
+    }
+}
+
+/// Parameters the server redirects back to the app with
+#[derive(Clone, Debug, PartialEq)]
+pub enum Callback {
+    /// Code to exchange for a token, along with the PKCE verifier
+    Code { code: String, state: String },
+    /// Token issued directly by the server
+    Token { token: String, state: String },
+}
+
+/// Query parameters of a callback link, empty when missing
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct CallbackParams {
+    pub code: String,
+    pub state: String,
+    pub token: String,
+    pub error: String,
+}
+
+impl CallbackParams {
+    /// Reads the parameters of a callback link, e.g. `bind://oauth/callback?code=...&state=...`
+    pub fn parse(link: &str) -> Result<Self, OAuthError> {
+        let url = Url::parse(link).map_err(|err| OAuthError::InvalidCallback(err.to_string()))?;
+        if url.scheme() != CALLBACK_SCHEME || url.host_str() != Some(CALLBACK_HOST) {
+            return Err(OAuthError::InvalidCallback(format!(
+                "Not an OAuth callback: {link}"
+            )));
+        }
+
+        let mut params = Self::default();
+        let mut error_description = String::new();
+        for (key, value) in url.query_pairs() {
+            match &*key {
+                "code" => params.code = value.into_owned(),
+                "state" => params.state = value.into_owned(),
+                "token" => params.token = value.into_owned(),
+                "error" => params.error = value.into_owned(),
+                "error_description" => error_description = value.into_owned(),
+                _ => {}
+            }
+        }
+        // Prefer the readable description of the error
+        if !params.error.is_empty() && !error_description.is_empty() {
+            params.error = error_description;
+        }
+        Ok(params)
+    }
+}
+
+impl Callback {
+    /// Parses a callback link, e.g. `bind://oauth/callback?code=...&state=...`
+    pub fn parse(link: &str) -> Result<Self, OAuthError> {
+        let params = CallbackParams::parse(link)?;
+        Self::from_params(params.code, params.state, params.token, params.error)
+    }
+
+    /// Creates the callback from its query parameters, empty when missing
+    pub fn from_params(
+        code: String,
+        state: String,
+        token: String,
+        error: String,
+    ) -> Result<Self, OAuthError> {
+        if !error.is_empty() {
+            return Err(OAuthError::Denied(error));
+        }
+        if state.is_empty() {
+            return Err(OAuthError::InvalidCallback("Missing state".to_string()));
+        }
+
+        match (code.is_empty(), token.is_empty()) {
+            (false, _) => Ok(Callback::Code { code, state }),
+            (true, false) => Ok(Callback::Token { token, state }),
+            (true, true) => Err(OAuthError::InvalidCallback(
+                "Missing code or token".to_string(),
+            )),
+        }
+    }
+
+    pub fn state(&self) -> &str {
+        match self {
+            Callback::Code { state, .. } | Callback::Token { state, .. } => state,
+        }
+    }
+}
+
+/// Compares the values without stopping at the first difference, so the time taken doesn't
+/// reveal how much of a guessed state was right
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    a.iter().zip(b).fold(0, |diff, (a, b)| diff | (a ^ b)) == 0
+}
+
+fn random_string(length: usize) -> String {
+    rand::rng()
+        .sample_iter(Alphanumeric)
+        .take(length)
+        .map(char::from)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+
+    fn now() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2025, 6, 1, 12, 0, 0).unwrap()
+    }
+
+    fn session() -> OAuthSession {
+        OAuthSession::new(OAuthProvider::Google, "https://bind.example", now())
+    }
+
+    fn code(state: &str) -> Callback {
+        Callback::Code {
+            code: "code".to_string(),
+            state: state.to_string(),
+        }
+    }
+
+    #[test]
+    fn parses_code_callback() {
+        let callback = Callback::parse("bind://oauth/callback?code=abc&state=xyz").unwrap();
+        assert_eq!(
+            callback,
+            Callback::Code {
+                code: "abc".to_string(),
+                state: "xyz".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn parses_token_callback() {
+        let callback = Callback::parse("bind://oauth/callback?token=t%2B1&state=xyz").unwrap();
+        assert_eq!(
+            callback,
+            Callback::Token {
+                token: "t+1".to_string(),
+                state: "xyz".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn prefers_code_over_token() {
+        let callback = Callback::parse("bind://oauth/callback?token=t&code=c&state=s").unwrap();
+        assert!(matches!(callback, Callback::Code { .. }));
+    }
+
+    #[test]
+    fn reports_denied_login_with_its_description() {
+        let result = Callback::parse(
+            "bind://oauth/callback?error=access_denied&error_description=User%20cancelled&state=s",
+        );
+        assert_eq!(
+            result,
+            Err(OAuthError::Denied("User cancelled".to_string()))
+        );
+
+        let result = Callback::parse("bind://oauth/callback?error=access_denied");
+        assert_eq!(result, Err(OAuthError::Denied("access_denied".to_string())));
+    }
+
+    #[test]
+    fn rejects_invalid_callbacks() {
+        for link in [
+            "not a link",
+            "https://oauth/callback?code=c&state=s",
+            "bind://other/callback?code=c&state=s",
+            "bind://oauth/callback?code=c",
+            "bind://oauth/callback?state=s",
+        ] {
+            assert!(
+                matches!(Callback::parse(link), Err(OAuthError::InvalidCallback(_))),
+                "{link}"
+            );
+        }
+    }
+
+    #[test]
+    fn accepts_callback_with_its_state() {
+        let session = session();
+        let callback = code(&session.state);
+        assert_eq!(session.validate(&callback, now()), Ok(()));
+        assert_eq!(
+            session.validate(&callback, now() + Duration::minutes(9)),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn rejects_callback_with_another_state() {
+        let session = session();
+        let mut other = session.state.clone();
+        other.replace_range(31.., if other.ends_with('a') { "b" } else { "a" });
+        let longer = format!("{}a", session.state);
+
+        for state in [other.as_str(), "", &session.state[..31], longer.as_str()] {
+            assert_eq!(
+                session.validate(&code(state), now()),
+                Err(OAuthError::StateMismatch),
+                "{state}"
+            );
+        }
+    }
+
+    #[test]
+    fn rejects_expired_session() {
+        let session = session();
+        let callback = code(&session.state);
+        assert_eq!(
+            session.validate(&callback, now() + Duration::minutes(11)),
+            Err(OAuthError::Expired)
+        );
+    }
+
+    #[test]
+    fn sessions_have_their_own_state_and_verifier() {
+        let (first, second) = (session(), session());
+        assert_eq!(first.server, "https://bind.example");
+        assert_ne!(first.state, second.state);
+        assert_ne!(first.code_verifier, second.code_verifier);
+        assert_eq!(first.code_challenge().len(), 43);
+    }
+}
@@ -0,0 +1,474 @@
+use std::{
+    path::{Path, PathBuf},
+    sync::LazyLock,
+    time::Duration,
+};
+
+use regex::{Captures, Regex};
+use reqwest::{header::RANGE, StatusCode, Url};
+use thiserror::Error;
+use tokio::{fs::OpenOptions, io::AsyncWriteExt};
+
+use super::library::{DownloadFile, DownloadLibrary, DownloadSettings, FileKind};
+use crate::api::types::feed::FeedItem;
+
+pub type Result<T> = std::result::Result<T, DownloadError>;
+
+#[derive(Error, Debug)]
+pub enum DownloadError {
+    #[error("Request failed: {0}")]
+    Request(#[from] reqwest::Error),
+    #[error("Failed to write file: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Invalid item data: {0}")]
+    Serde(#[from] serde_json::Error),
+    #[error("Server responded with status {0}")]
+    Status(u16),
+    #[error("File is larger than the {0} byte limit")]
+    TooLarge(u64),
+    #[error("Download was cancelled")]
+    Cancelled,
+}
+
+const LIBRARY_FILE: &str = "library.json";
+const ITEM_FILE: &str = "item.json";
+const ARTICLE_FILE: &str = "article.html";
+
+static IMAGE_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r#"(?i)<img\b[^>]*?\bsrc\s*=\s*["']([^"']+)["']"#).unwrap());
+static MAIN_CONTENT_REGEX: LazyLock<[Regex; 3]> = LazyLock::new(|| {
+    ["article", "main", "body"]
+        .map(|tag| Regex::new(&format!(r"(?is)<{tag}\b[^>]*>(.*)</{tag}>")).unwrap())
+});
+
+/// Writes downloads to disk, one directory per item.
+///
+/// Doesn't depend on the API or any UI state, only on the URLs it is given, so it can be pointed
+/// at a local HTTP server.
+#[derive(Clone)]
+pub struct Downloader {
+    client: reqwest::Client,
+    root: PathBuf,
+}
+
+impl Downloader {
+    pub fn new(root: PathBuf) -> Self {
+        // No overall timeout since enclosures can take a long time to download
+        let client = reqwest::ClientBuilder::new()
+            .connect_timeout(Duration::from_secs(30))
+            .read_timeout(Duration::from_secs(30))
+            .build()
+            .unwrap();
+
+        Self { client, root }
+    }
+
+    pub fn item_dir(&self, item_id: i64) -> PathBuf {
+        self.root.join(item_id.to_string())
+    }
+
+    pub fn load_library(&self) -> DownloadLibrary {
+        std::fs::read(self.root.join(LIBRARY_FILE))
+            .ok()
+            .and_then(|data| serde_json::from_slice(&data).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save_library(&self, library: &DownloadLibrary) {
+        let result = std::fs::create_dir_all(&self.root).and_then(|_| {
+            let data = serde_json::to_vec(library).map_err(std::io::Error::other)?;
+            std::fs::write(self.root.join(LIBRARY_FILE), data)
+        });
+        if let Err(err) = result {
+            tracing::error!("Failed to save downloads: {err}");
+        }
+    }
+
+    /// Reads a downloaded item, with its content replaced by the downloaded article. Images
+    /// which were downloaded are shown from their files.
+    pub fn load_item(&self, item_id: i64, files: &[DownloadFile]) -> Option<FeedItem> {
+        let data = std::fs::read(self.item_dir(item_id).join(ITEM_FILE)).ok()?;
+        let mut item: FeedItem = serde_json::from_slice(&data).ok()?;
+
+        let base = item.base_link.as_deref().or(item.link.as_deref());
+        let base = base.and_then(|base| Url::parse(base).ok());
+        item.content = item.content.map(|content| {
+            rewrite_images(&content, base.as_ref(), |url| {
+                files
+                    .iter()
+                    .find(|file| file.kind == FileKind::Image && file.complete && file.url == url)
+                    .and_then(|file| self.local_url(item_id, file))
+            })
+        });
+        Some(item)
+    }
+
+    /// URL the webview loads the downloaded file from
+    pub fn local_url(&self, item_id: i64, file: &DownloadFile) -> Option<String> {
+        // The webview serves files from the disk by their absolute path
+        let path = std::path::absolute(self.item_dir(item_id).join(&file.path)).ok()?;
+        Url::from_file_path(path)
+            .ok()
+            .map(|url| url.path().to_string())
+    }
+
+    pub async fn remove_item(&self, item_id: i64) {
+        match tokio::fs::remove_dir_all(self.item_dir(item_id)).await {
+            Ok(()) => {}
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => {}
+            Err(err) => tracing::error!("Failed to remove download {item_id}: {err}"),
+        }
+    }
+
+    /// Saves the item and its article, and lists the images and enclosure left to download.
+    ///
+    /// The article is the item's content when the feed includes it, otherwise the main content
+    /// of the linked page.
+    pub async fn prepare(
+        &self,
+        item: &FeedItem,
+        settings: &DownloadSettings,
+    ) -> Result<Vec<DownloadFile>> {
+        let dir = self.item_dir(item.id);
+        tokio::fs::create_dir_all(&dir).await?;
+
+        let mut item = item.clone();
+        let article = match (&item.content, &item.link) {
+            (Some(content), _) => content.clone(),
+            (None, Some(link)) => {
+                let response = self.client.get(link).send().await?;
+                if !response.status().is_success() {
+                    return Err(DownloadError::Status(response.status().as_u16()));
+                }
+                extract_main_content(&response.text().await?).to_string()
+            }
+            (None, None) => String::new(),
+        };
+        tokio::fs::write(dir.join(ARTICLE_FILE), &article).await?;
+
+        let mut files = vec![DownloadFile {
+            url: item.link.clone().unwrap_or_default(),
+            kind: FileKind::Article,
+            path: ARTICLE_FILE.to_string(),
+            size: Some(article.len() as u64),
+            downloaded: article.len() as u64,
+            complete: true,
+        }];
+
+        let base = item.base_link.as_deref().or(item.link.as_deref());
+        for (index, url) in image_urls(&article, base).into_iter().enumerate() {
+            files.push(DownloadFile::new(
+                url,
+                FileKind::Image,
+                format!("images/{index}"),
+                None,
+            ));
+        }
+
+        if let Some(enclosure) = item
+            .enclosure
+            .as_ref()
+            .filter(|enclosure| settings.should_download(enclosure))
+        {
+            let size = (enclosure.length > 0).then_some(enclosure.length as u64);
+            files.push(DownloadFile::new(
+                enclosure.url.clone(),
+                FileKind::Enclosure,
+                "enclosure".to_string(),
+                size,
+            ));
+        }
+
+        item.content = Some(article);
+        tokio::fs::write(dir.join(ITEM_FILE), serde_json::to_vec(&item)?).await?;
+
+        Ok(files)
+    }
+
+    /// Downloads a file, resuming from a previous partial download if possible.
+    ///
+    /// `on_progress` is called with the total bytes written after every chunk, returning false
+    /// cancels the download and keeps the partial file. Returns the size of the file.
+    pub async fn download_file(
+        &self,
+        item_id: i64,
+        file: &DownloadFile,
+        max_size: Option<u64>,
+        mut on_progress: impl FnMut(u64) -> bool,
+    ) -> Result<u64> {
+        let path = self.item_dir(item_id).join(&file.path);
+        let part = part_path(&path);
+        if let Some(parent) = path.parent() {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+
+        let existing = tokio::fs::metadata(&part)
+            .await
+            .map(|metadata| metadata.len())
+            .unwrap_or(0);
+        let mut request = self.client.get(&file.url);
+        if existing > 0 {
+            request = request.header(RANGE, format!("bytes={existing}-"));
+        }
+
+        let mut response = request.send().await?;
+        let mut downloaded = match response.status() {
+            StatusCode::PARTIAL_CONTENT => existing,
+            // The previous attempt got the whole file but was interrupted before renaming it
+            StatusCode::RANGE_NOT_SATISFIABLE if existing > 0 => {
+                tokio::fs::rename(&part, &path).await?;
+                return Ok(existing);
+            }
+            status if status.is_success() => 0,
+            status => return Err(DownloadError::Status(status.as_u16())),
+        };
+
+        let total = response.content_length().map(|length| length + downloaded);
+        if let (Some(max_size), Some(total)) = (max_size, total) {
+            if total > max_size {
+                return Err(DownloadError::TooLarge(max_size));
+            }
+        }
+
+        let mut out = if downloaded > 0 {
+            OpenOptions::new().append(true).open(&part).await?
+        } else {
+            tokio::fs::File::create(&part).await?
+        };
+
+        while let Some(chunk) = response.chunk().await? {
+            out.write_all(&chunk).await?;
+            downloaded += chunk.len() as u64;
+
+            if max_size.is_some_and(|max_size| downloaded > max_size) {
+                drop(out);
+                tokio::fs::remove_file(&part).await?;
+                return Err(DownloadError::TooLarge(max_size.unwrap_or_default()));
+            }
+            if !on_progress(downloaded) {
+                out.flush().await?;
+                return Err(DownloadError::Cancelled);
+            }
+        }
+
+        out.flush().await?;
+        tokio::fs::rename(&part, &path).await?;
+        Ok(downloaded)
+    }
+}
+
+fn part_path(path: &Path) -> PathBuf {
+    let mut part = path.as_os_str().to_owned();
+    part.push(".part");
+    PathBuf::from(part)
+}
+
+/// Content of the page's `<article>`, `<main>` or `<body>`, whichever is found first
+fn extract_main_content(html: &str) -> &str {
+    MAIN_CONTENT_REGEX
+        .iter()
+        .find_map(|regex| regex.captures(html))
+        .and_then(|captures| captures.get(1))
+        .map(|content| content.as_str())
+        .unwrap_or(html)
+}
+
+/// Absolute URLs of the images in the HTML, without duplicates
+fn image_urls(html: &str, base: Option<&str>) -> Vec<String> {
+    let base = base.and_then(|base| Url::parse(base).ok());
+
+    let mut urls: Vec<String> = vec![];
+    for captures in IMAGE_REGEX.captures_iter(html) {
+        if let Some(url) = resolve_image(&captures[1], base.as_ref()) {
+            if !urls.contains(&url) {
+                urls.push(url);
+            }
+        }
+    }
+    urls
+}
+
+/// Replaces the sources of the images in the HTML which `local` has a URL for
+fn rewrite_images(
+    html: &str,
+    base: Option<&Url>,
+    local: impl Fn(&str) -> Option<String>,
+) -> String {
+    IMAGE_REGEX
+        .replace_all(html, |captures: &Captures| {
+            let (Some(tag), Some(src)) = (captures.get(0), captures.get(1)) else {
+                return String::new();
+            };
+            match resolve_image(src.as_str(), base).and_then(|url| local(&url)) {
+                Some(local) => {
+                    let tag_html = tag.as_str();
+                    let start = src.start() - tag.start();
+                    let end = src.end() - tag.start();
+                    format!("{}{local}{}", &tag_html[..start], &tag_html[end..])
+                }
+                None => tag.as_str().to_string(),
+            }
+        })
+        .into_owned()
+}
+
+/// Absolute URL of an image's source, if it can be downloaded
+fn resolve_image(src: &str, base: Option<&Url>) -> Option<String> {
+    let src = src.replace("&amp;", "&");
+    let url = match base {
+        Some(base) => base.join(&src),
+        None => Url::parse(&src),
+    };
+    url.ok()
+        .filter(|url| matches!(url.scheme(), "http" | "https"))
+        .map(|url| url.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use chrono::Utc;
+    use tokio::{
+        io::{AsyncReadExt, AsyncWriteExt},
+        net::TcpListener,
+    };
+
+    use super::*;
+    use crate::api::types::feed::FeedItemEnclosure;
+
+    const PAGE: &[u8] = br#"<html><body><article>
+        <p>Hello</p>
+        <img src="/image.png">
+        <img src="/missing.png">
+    </article></body></html>"#;
+    const IMAGE: &[u8] = b"not really a png";
+    const AUDIO: &[u8] = b"not really an mp3";
+
+    /// Serves the bodies by path, standing in for the site the item links to
+    async fn serve(bodies: HashMap<&'static str, &'static [u8]>) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            while let Ok((mut stream, _)) = listener.accept().await {
+                let bodies = bodies.clone();
+                tokio::spawn(async move {
+                    let mut request = Vec::new();
+                    let mut buffer = [0; 1024];
+                    while !request.ends_with(b"\r\n\r\n") {
+                        match stream.read(&mut buffer).await {
+                            Ok(0) | Err(_) => return,
+                            Ok(read) => request.extend_from_slice(&buffer[..read]),
+                        }
+                    }
+
+                    let request = String::from_utf8_lossy(&request);
+                    let path = request.split_whitespace().nth(1).unwrap_or("/");
+                    let (status, body) = match bodies.get(path) {
+                        Some(body) => ("200 OK", *body),
+                        None => ("404 Not Found", &b""[..]),
+                    };
+                    let head = format!(
+                        "HTTP/1.1 {status}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+                        body.len()
+                    );
+                    let _ = stream.write_all(&[head.as_bytes(), body].concat()).await;
+                });
+            }
+        });
+        format!("http://{address}")
+    }
+
+    fn item(server: &str) -> FeedItem {
+        FeedItem {
+            id: 1,
+            guid: "1".to_string(),
+            feed_id: 1,
+            index_in_feed: 0,
+            title: "Episode".to_string(),
+            link: Some(format!("{server}/article")),
+            description: None,
+            enclosure: Some(FeedItemEnclosure {
+                url: format!("{server}/episode.mp3"),
+                length: AUDIO.len() as i32,
+                mime_type: "audio/mpeg".to_string(),
+            }),
+            categories: vec![],
+            comments_link: None,
+            published_at: None,
+            content: None,
+            content_type: None,
+            base_link: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    #[tokio::test]
+    async fn downloads_item_for_reading_offline() {
+        let server = serve(HashMap::from([
+            ("/article", PAGE),
+            ("/image.png", IMAGE),
+            ("/episode.mp3", AUDIO),
+        ]))
+        .await;
+        let root = std::env::temp_dir().join(format!("bind-downloads-{}", std::process::id()));
+        let downloader = Downloader::new(root.clone());
+        let settings = DownloadSettings {
+            enclosures: true,
+            ..Default::default()
+        };
+
+        let mut files = downloader.prepare(&item(&server), &settings).await.unwrap();
+        let kinds: Vec<FileKind> = files.iter().map(|file| file.kind).collect();
+        assert_eq!(
+            kinds,
+            [
+                FileKind::Article,
+                FileKind::Image,
+                FileKind::Image,
+                FileKind::Enclosure
+            ]
+        );
+
+        for file in files.iter_mut().filter(|file| !file.complete) {
+            if let Ok(size) = downloader.download_file(1, file, None, |_| true).await {
+                file.downloaded = size;
+                file.complete = true;
+            }
+        }
+        assert!(files[1].complete);
+        assert!(!files[2].complete, "The missing image fails to download");
+        assert!(files[3].complete);
+
+        let image = downloader.local_url(1, &files[1]).unwrap();
+        let enclosure = downloader.local_url(1, &files[3]).unwrap();
+        assert_eq!(
+            std::fs::read(root.join("1").join(&files[3].path)).unwrap(),
+            AUDIO
+        );
+        assert!(enclosure.ends_with("/1/enclosure"));
+
+        // Downloaded images are shown from their files, the others still from the site
+        let content = downloader.load_item(1, &files).unwrap().content.unwrap();
+        assert!(content.contains("<p>Hello</p>"));
+        assert!(content.contains(&format!(r#"<img src="{image}">"#)));
+        assert!(content.contains(r#"<img src="/missing.png">"#));
+
+        std::fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn rewrites_only_local_images() {
+        let base = Url::parse("https://example.com/posts/1").unwrap();
+        let html = r#"<p><img alt="a" src="a.png" width="10"><img src='https://cdn.example.com/b.png'></p>"#;
+
+        let rewritten = rewrite_images(html, Some(&base), |url| {
+            (url == "https://example.com/posts/a.png").then(|| "/local/a".to_string())
+        });
+        assert_eq!(
+            rewritten,
+            r#"<p><img alt="a" src="/local/a" width="10"><img src='https://cdn.example.com/b.png'></p>"#
+        );
+    }
+}
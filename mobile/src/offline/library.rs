@@ -0,0 +1,251 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::api::types::feed::FeedItemEnclosure;
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct DownloadSettings {
+    /// Maximum space used by downloads in bytes, older downloads are evicted past this
+    pub quota: u64,
+    /// Whether to download enclosures along with the article
+    pub enclosures: bool,
+    /// Enclosures larger than this are skipped, in bytes
+    pub enclosure_size_cap: u64,
+}
+
+impl Default for DownloadSettings {
+    fn default() -> Self {
+        Self {
+            quota: 500_000_000,
+            enclosures: false,
+            enclosure_size_cap: 100_000_000,
+        }
+    }
+}
+
+impl DownloadSettings {
+    pub fn should_download(&self, enclosure: &FeedItemEnclosure) -> bool {
+        // Enclosures without a length are checked against the cap while downloading
+        self.enclosures
+            && (enclosure.length <= 0 || enclosure.length as u64 <= self.enclosure_size_cap)
+    }
+}
+
+/// Automatically downloads every item added to the list
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct AutoDownloadRule {
+    pub list_id: i32,
+    pub title: String,
+}
+
+/// Why an item was downloaded
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub enum DownloadSource {
+    Manual,
+    List(i32),
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum DownloadStatus {
+    Queued,
+    Downloading,
+    Complete,
+    Failed { message: String },
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub enum FileKind {
+    Article,
+    Image,
+    Enclosure,
+}
+
+/// A single file belonging to a download
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct DownloadFile {
+    pub url: String,
+    pub kind: FileKind,
+    /// Path relative to the item's directory
+    pub path: String,
+    /// Expected size in bytes, if known
+    pub size: Option<u64>,
+    /// Bytes written so far, used to resume the download
+    pub downloaded: u64,
+    pub complete: bool,
+}
+
+impl DownloadFile {
+    pub fn new(url: String, kind: FileKind, path: String, size: Option<u64>) -> Self {
+        Self {
+            url,
+            kind,
+            path,
+            size,
+            downloaded: 0,
+            complete: false,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct DownloadEntry {
+    pub item_id: i64,
+    /// Title of the item, empty until the item has been fetched
+    pub title: String,
+    pub source: DownloadSource,
+    pub status: DownloadStatus,
+    /// Files to download, empty until the item has been fetched
+    pub files: Vec<DownloadFile>,
+    pub requested_at: DateTime<Utc>,
+    /// Last time the download was read, used for eviction
+    pub accessed_at: DateTime<Utc>,
+}
+
+impl DownloadEntry {
+    /// Space used on disk in bytes
+    pub fn size(&self) -> u64 {
+        self.files.iter().map(|file| file.downloaded).sum()
+    }
+
+    /// Progress through the download, 0 - 1
+    pub fn progress(&self) -> f64 {
+        if self.status == DownloadStatus::Complete {
+            return 1.;
+        }
+
+        let expected = self
+            .files
+            .iter()
+            .map(|file| file.size.unwrap_or(file.downloaded).max(file.downloaded))
+            .sum::<u64>();
+        if expected == 0 {
+            return 0.;
+        }
+        self.size() as f64 / expected as f64
+    }
+}
+
+/// Downloaded items and the rules for downloading them.
+///
+/// Only tracks state, the files themselves are managed by the [super::Downloader].
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct DownloadLibrary {
+    /// Downloads in the order they were requested
+    pub entries: Vec<DownloadEntry>,
+    pub rules: Vec<AutoDownloadRule>,
+    pub settings: DownloadSettings,
+}
+
+impl DownloadLibrary {
+    pub fn get(&self, item_id: i64) -> Option<&DownloadEntry> {
+        self.entries.iter().find(|entry| entry.item_id == item_id)
+    }
+
+    pub fn get_mut(&mut self, item_id: i64) -> Option<&mut DownloadEntry> {
+        self.entries
+            .iter_mut()
+            .find(|entry| entry.item_id == item_id)
+    }
+
+    pub fn is_available(&self, item_id: i64) -> bool {
+        self.get(item_id)
+            .is_some_and(|entry| entry.status == DownloadStatus::Complete)
+    }
+
+    /// Queues the item for download. Returns false if it was already in the library.
+    pub fn request(&mut self, item_id: i64, source: DownloadSource, now: DateTime<Utc>) -> bool {
+        if self.get(item_id).is_some() {
+            return false;
+        }
+
+        self.entries.push(DownloadEntry {
+            item_id,
+            title: String::new(),
+            source,
+            status: DownloadStatus::Queued,
+            files: vec![],
+            requested_at: now,
+            accessed_at: now,
+        });
+        true
+    }
+
+    /// Queues a failed download again, keeping the parts already downloaded
+    pub fn retry(&mut self, item_id: i64) {
+        if let Some(entry) = self.get_mut(item_id) {
+            if matches!(entry.status, DownloadStatus::Failed { .. }) {
+                entry.status = DownloadStatus::Queued;
+            }
+        }
+    }
+
+    pub fn remove(&mut self, item_id: i64) -> Option<DownloadEntry> {
+        let index = self
+            .entries
+            .iter()
+            .position(|entry| entry.item_id == item_id)?;
+        Some(self.entries.remove(index))
+    }
+
+    /// Marks the download as read, so it is evicted last
+    pub fn touch(&mut self, item_id: i64, now: DateTime<Utc>) {
+        if let Some(entry) = self.get_mut(item_id) {
+            entry.accessed_at = now;
+        }
+    }
+
+    /// Oldest download waiting to be downloaded
+    pub fn next_queued(&self) -> Option<i64> {
+        self.entries
+            .iter()
+            .find(|entry| entry.status == DownloadStatus::Queued)
+            .map(|entry| entry.item_id)
+    }
+
+    /// Downloads interrupted by the app closing are queued again, to be resumed
+    pub fn restore(&mut self) {
+        for entry in &mut self.entries {
+            if entry.status == DownloadStatus::Downloading {
+                entry.status = DownloadStatus::Queued;
+            }
+        }
+    }
+
+    /// Space used by all downloads in bytes
+    pub fn used(&self) -> u64 {
+        self.entries.iter().map(DownloadEntry::size).sum()
+    }
+
+    /// Removes the least recently read downloads until the library fits in the quota.
+    /// Only completed downloads are evicted, and never the `keep` item.
+    pub fn evict(&mut self, keep: Option<i64>) -> Vec<DownloadEntry> {
+        let mut evicted = vec![];
+        while self.used() > self.settings.quota {
+            let oldest = self
+                .entries
+                .iter()
+                .filter(|entry| entry.status == DownloadStatus::Complete)
+                .filter(|entry| Some(entry.item_id) != keep)
+                .min_by_key(|entry| entry.accessed_at)
+                .map(|entry| entry.item_id);
+
+            match oldest.and_then(|item_id| self.remove(item_id)) {
+                Some(entry) => evicted.push(entry),
+                None => break,
+            }
+        }
+        evicted
+    }
+
+    pub fn has_rule(&self, list_id: i32) -> bool {
+        self.rules.iter().any(|rule| rule.list_id == list_id)
+    }
+
+    pub fn set_rule(&mut self, rule: AutoDownloadRule, enabled: bool) {
+        self.rules
+            .retain(|existing| existing.list_id != rule.list_id);
+        if enabled {
+            self.rules.push(rule);
+        }
+    }
+}
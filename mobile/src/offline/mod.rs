@@ -0,0 +1,289 @@
+//! Offline downloads of articles, their images and enclosures.
+//!
+//! [library] tracks what is downloaded and the rules for downloading, [downloader] writes the
+//! files. The [Downloads] handle ties them to the UI and runs a single background worker which
+//! downloads one item at a time.
+
+use std::time::Duration;
+
+use chrono::Utc;
+use dioxus::prelude::*;
+use futures_util::StreamExt;
+
+use crate::{
+    api::{types::feed::FeedItem, ApiClient},
+    hooks::use_api,
+    settings::use_settings,
+    store::{files_dir, use_store},
+};
+
+mod downloader;
+mod library;
+
+pub use downloader::{DownloadError, Downloader};
+pub use library::{
+    AutoDownloadRule, DownloadEntry, DownloadLibrary, DownloadSettings, DownloadSource,
+    DownloadStatus, FileKind,
+};
+
+/// How often lists with an auto-download rule are checked for new items
+const SYNC_INTERVAL: Duration = Duration::from_secs(15 * 60);
+
+/// Bytes between updates of a download's progress
+const PROGRESS_INTERVAL: u64 = 256 * 1024;
+
+/// Library and the downloader, shared by the [Downloads] handle and the worker
+#[derive(Clone, Copy)]
+struct State {
+    downloader: Signal<Downloader>,
+    library: Signal<DownloadLibrary>,
+}
+
+impl State {
+    fn update(&mut self, update: impl FnOnce(&mut DownloadLibrary)) {
+        update(&mut self.library.write());
+        self.downloader.peek().save_library(&self.library.peek());
+    }
+
+    fn set_status(&mut self, item_id: i64, status: DownloadStatus) {
+        self.update(|library| {
+            if let Some(entry) = library.get_mut(item_id) {
+                entry.status = status;
+            }
+        });
+    }
+
+    /// Evicts downloads until the library fits in the quota again
+    fn evict(&mut self, keep: Option<i64>) {
+        let mut evicted = vec![];
+        self.update(|library| evicted = library.evict(keep));
+        for entry in evicted {
+            self.remove_files(entry.item_id);
+        }
+    }
+
+    fn remove_files(&self, item_id: i64) {
+        let downloader = self.downloader.peek().clone();
+        spawn(async move { downloader.remove_item(item_id).await });
+    }
+}
+
+#[derive(Clone, Copy)]
+pub struct Downloads {
+    state: State,
+    worker: Coroutine<()>,
+}
+
+impl Downloads {
+    pub fn library(&self) -> ReadOnlySignal<DownloadLibrary> {
+        self.state.library.into()
+    }
+
+    pub fn download(&mut self, item_id: i64) {
+        self.state.update(|library| {
+            library.request(item_id, DownloadSource::Manual, Utc::now());
+        });
+        self.worker.send(());
+    }
+
+    pub fn retry(&mut self, item_id: i64) {
+        self.state.update(|library| library.retry(item_id));
+        self.worker.send(());
+    }
+
+    /// Removes the download and its files. Cancels it if it is in progress.
+    pub fn remove(&mut self, item_id: i64) {
+        self.state.update(|library| {
+            library.remove(item_id);
+        });
+        self.state.remove_files(item_id);
+    }
+
+    /// Downloaded copy of the item, if it has been downloaded
+    pub fn item(&mut self, item_id: i64) -> Option<FeedItem> {
+        if !self.state.library.peek().is_available(item_id) {
+            return None;
+        }
+
+        self.state
+            .update(|library| library.touch(item_id, Utc::now()));
+        let library = self.state.library.peek();
+        let files = library
+            .get(item_id)
+            .map_or(&[][..], |entry| entry.files.as_slice());
+        self.state.downloader.peek().load_item(item_id, files)
+    }
+
+    /// URL to play the item's enclosure from, if it has been downloaded
+    pub fn enclosure_url(&self, item_id: i64) -> Option<String> {
+        let library = self.state.library.peek();
+        let file = library
+            .get(item_id)?
+            .files
+            .iter()
+            .find(|file| file.kind == FileKind::Enclosure && file.complete)?;
+        self.state.downloader.peek().local_url(item_id, file)
+    }
+
+    pub fn set_rule(&mut self, rule: AutoDownloadRule, enabled: bool) {
+        self.state.update(|library| library.set_rule(rule, enabled));
+        self.worker.send(());
+    }
+
+    pub fn set_settings(&mut self, settings: DownloadSettings) {
+        self.state.update(|library| library.settings = settings);
+        self.state.evict(None);
+    }
+}
+
+/// Loads the download library and starts the download worker. Must be called once, above every
+/// view which uses [use_downloads], so that downloads continue across navigation.
+pub fn use_downloads_provider() -> Downloads {
+    let api = use_api();
+    let account = use_store().active_account();
+    let settings = use_settings();
+    let downloader =
+        use_signal(|| Downloader::new(files_dir(account.peek().as_ref()).join("downloads")));
+    let library = use_signal(|| {
+        let mut library = downloader.peek().load_library();
+        library.restore();
+        library
+    });
+
+    let mut state = State {
+        downloader,
+        library,
+    };
+
+    let worker = use_coroutine(move |mut rx: UnboundedReceiver<()>| async move {
+        loop {
+            if (settings.auto_sync)() {
+                sync_rules(api, &mut state).await;
+            }
+            while let Some(item_id) = library.peek().next_queued() {
+                download_item(api, &mut state, item_id).await;
+            }
+
+            tokio::select! {
+                Some(()) = rx.next() => {}
+                _ = tokio::time::sleep(SYNC_INTERVAL) => {}
+            }
+        }
+    });
+
+    use_context_provider(|| Downloads { state, worker })
+}
+
+pub fn use_downloads() -> Downloads {
+    use_context::<Downloads>()
+}
+
+/// Queues the items of every list with an auto-download rule
+async fn sync_rules(api: &'static ApiClient, state: &mut State) {
+    let rules = state.library.peek().rules.clone();
+    for rule in rules {
+        match api.list_list_items(rule.list_id, 0).await {
+            Ok(items) => state.update(|library| {
+                for item in items {
+                    library.request(item.item, DownloadSource::List(rule.list_id), Utc::now());
+                }
+            }),
+            Err(err) => tracing::error!(
+                "Failed to sync downloads for list {}: {}",
+                rule.list_id,
+                err.message()
+            ),
+        }
+    }
+}
+
+async fn download_item(api: &'static ApiClient, state: &mut State, item_id: i64) {
+    let mut library = state.library;
+    let downloader = state.downloader.peek().clone();
+    state.set_status(item_id, DownloadStatus::Downloading);
+
+    // Plan the files on the first attempt, later attempts resume the remaining files
+    let planned = library
+        .peek()
+        .get(item_id)
+        .is_some_and(|entry| !entry.files.is_empty());
+    if !planned {
+        let settings = library.peek().settings.clone();
+        let result = match api.get_item(item_id).await {
+            Ok(item) => downloader
+                .prepare(&item, &settings)
+                .await
+                .map(|files| (item.title, files))
+                .map_err(|err| err.to_string()),
+            Err(err) => Err(err.message()),
+        };
+
+        match result {
+            Ok((title, files)) => state.update(|library| {
+                if let Some(entry) = library.get_mut(item_id) {
+                    entry.title = title;
+                    entry.files = files;
+                }
+            }),
+            Err(message) => {
+                state.set_status(item_id, DownloadStatus::Failed { message });
+                return;
+            }
+        }
+    }
+
+    let files = match library.peek().get(item_id) {
+        Some(entry) => entry.files.clone(),
+        // Removed while the item was being fetched
+        None => return,
+    };
+    let enclosure_size_cap = library.peek().settings.enclosure_size_cap;
+
+    for (index, file) in files.into_iter().enumerate() {
+        if file.complete {
+            continue;
+        }
+
+        let max_size = (file.kind == FileKind::Enclosure).then_some(enclosure_size_cap);
+        let mut reported = file.downloaded;
+        let result = downloader
+            .download_file(item_id, &file, max_size, |downloaded| {
+                if library.peek().get(item_id).is_none() {
+                    return false;
+                }
+                if downloaded.saturating_sub(reported) >= PROGRESS_INTERVAL {
+                    reported = downloaded;
+                    if let Some(entry) = library.write().get_mut(item_id) {
+                        entry.files[index].downloaded = downloaded;
+                    }
+                }
+                true
+            })
+            .await;
+
+        match result {
+            Ok(size) => state.update(|library| {
+                if let Some(entry) = library.get_mut(item_id) {
+                    entry.files[index].downloaded = size;
+                    entry.files[index].complete = true;
+                }
+            }),
+            Err(DownloadError::Cancelled) => return,
+            // The article can still be read without its images or an oversized enclosure
+            Err(err) if file.kind == FileKind::Image => {
+                tracing::warn!("Skipping image {} of item {item_id}: {err}", file.url);
+            }
+            Err(err @ DownloadError::TooLarge(_)) => {
+                tracing::warn!("Skipping enclosure of item {item_id}: {err}");
+            }
+            Err(err) => {
+                let message = err.to_string();
+                state.set_status(item_id, DownloadStatus::Failed { message });
+                return;
+            }
+        }
+    }
+
+    state.set_status(item_id, DownloadStatus::Complete);
+    state.evict(Some(item_id));
+}
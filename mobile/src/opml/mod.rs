@@ -0,0 +1,83 @@
+//! Reading and writing subscriptions as OPML, the format feed readers exchange them in.
+//!
+//! Only the feeds are kept: folders are flattened, since tags are organized in the app.
+
+use std::sync::LazyLock;
+
+use regex::Regex;
+
+use crate::api::types::feed::Feed;
+
+static OUTLINE_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?s)<outline\b[^>]*>").unwrap());
+
+static ATTRIBUTE_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r#"([\w:-]+)\s*=\s*(?:"([^"]*)"|'([^']*)')"#).unwrap());
+
+/// A feed from an OPML file
+#[derive(Clone, Debug, PartialEq)]
+pub struct Outline {
+    pub title: Option<String>,
+    pub xml_url: String,
+}
+
+/// The feeds in an OPML file, in the order they appear. Outlines without a feed, like folders,
+/// are skipped.
+pub fn parse(opml: &str) -> Vec<Outline> {
+    OUTLINE_REGEX
+        .find_iter(opml)
+        .filter_map(|outline| {
+            let mut title = None;
+            let mut xml_url = None;
+            for attribute in ATTRIBUTE_REGEX.captures_iter(outline.as_str()) {
+                let value = attribute
+                    .get(2)
+                    .or(attribute.get(3))
+                    .map(|value| unescape(value.as_str()));
+                match &attribute[1] {
+                    "xmlUrl" => xml_url = value,
+                    "title" => title = value,
+                    "text" if title.is_none() => title = value,
+                    _ => {}
+                }
+            }
+            Some(Outline {
+                title: title.filter(|title| !title.is_empty()),
+                xml_url: xml_url.filter(|url| !url.is_empty())?,
+            })
+        })
+        .collect()
+}
+
+/// An OPML file with the feeds
+pub fn build(title: &str, feeds: &[Feed]) -> String {
+    let mut opml = format!(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<opml version=\"2.0\">\n  <head>\n    <title>{}</title>\n  </head>\n  <body>\n",
+        escape(title)
+    );
+    for feed in feeds {
+        opml.push_str(&format!(
+            "    <outline type=\"rss\" text=\"{title}\" title=\"{title}\" xmlUrl=\"{url}\"/>\n",
+            title = escape(&feed.title),
+            url = escape(&feed.link),
+        ));
+    }
+    opml.push_str("  </body>\n</opml>\n");
+    opml
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+fn unescape(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
@@ -2,7 +2,7 @@ pub fn setup_decor() {
     use dioxus::mobile::wry::prelude::dispatch;
 
     // TODO: remove unwraps
-    dispatch(|env, activity, _webview| {
+    dispatch(|env, activity, webview| {
         // Get the window
         let window = env
             .call_method(activity, "getWindow", "()Landroid/view/Window;", &[])
@@ -46,5 +46,45 @@ pub fn setup_decor() {
         // Set navigation bar color
         env.call_method(&window, "setNavigationBarColor", "(I)V", &[color.into()])
             .unwrap();
+
+        // Scale the text like the rest of the system, which the web view doesn't by itself
+        let resources = env
+            .call_method(
+                activity,
+                "getResources",
+                "()Landroid/content/res/Resources;",
+                &[],
+            )
+            .unwrap()
+            .l()
+            .unwrap();
+        let configuration = env
+            .call_method(
+                &resources,
+                "getConfiguration",
+                "()Landroid/content/res/Configuration;",
+                &[],
+            )
+            .unwrap()
+            .l()
+            .unwrap();
+        let font_scale = env
+            .get_field(&configuration, "fontScale", "F")
+            .unwrap()
+            .f()
+            .unwrap();
+        let settings = env
+            .call_method(
+                webview,
+                "getSettings",
+                "()Landroid/webkit/WebSettings;",
+                &[],
+            )
+            .unwrap()
+            .l()
+            .unwrap();
+        let text_zoom = (font_scale * 100.0).round() as i32;
+        env.call_method(&settings, "setTextZoom", "(I)V", &[text_zoom.into()])
+            .unwrap();
     });
 }
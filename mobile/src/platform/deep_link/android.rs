@@ -0,0 +1,41 @@
+use jni::{
+    objects::{JClass, JString},
+    JNIEnv,
+};
+use std::sync::{mpsc, LazyLock, Mutex};
+
+// Channel for sending opened links. Created up front, so a link which launched the app
+// is kept until the listener is initialized.
+static DEEP_LINKS: LazyLock<(mpsc::Sender<String>, Mutex<Option<mpsc::Receiver<String>>>)> =
+    LazyLock::new(|| {
+        let (tx, rx) = mpsc::channel();
+        (tx, Mutex::new(Some(rx)))
+    });
+
+// Initialize the deep link listener, receiving every `bind://` link the app is opened with
+pub fn init_deep_link_listener() -> mpsc::Receiver<String> {
+    DEEP_LINKS
+        .1
+        .lock()
+        .unwrap_or_else(|err| err.into_inner())
+        .take()
+        .expect("Deep link listener already initialized")
+}
+
+// JNI function that gets called from Kotlin via `MainActivity.onDeepLink_native(url)`,
+// with the data of the intent in `onCreate` and `onNewIntent`
+#[no_mangle]
+pub extern "C" fn Java_dev_dioxus_main_MainActivity_onDeepLink_1native(
+    mut env: JNIEnv,
+    _class: JClass,
+    url: JString,
+) {
+    let url: String = match env.get_string(&url) {
+        Ok(url) => url.into(),
+        Err(err) => {
+            tracing::error!("Failed to read deep link: {err}");
+            return;
+        }
+    };
+    let _ = DEEP_LINKS.0.send(url);
+}
@@ -0,0 +1,3 @@
+mod android;
+
+pub use android::init_deep_link_listener;
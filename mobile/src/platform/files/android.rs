@@ -0,0 +1,31 @@
+//! Android app storage location using `Context.getFilesDir()`.
+
+use std::{path::PathBuf, sync::mpsc};
+
+use dioxus::mobile::wry::prelude::dispatch;
+use jni::{errors::Error, objects::JObject, objects::JString, JNIEnv};
+
+pub fn files_dir() -> Result<PathBuf, String> {
+    let (tx, rx) = mpsc::channel::<Result<String, Error>>();
+
+    dispatch(move |env: &mut JNIEnv, activity: &JObject, _webview| {
+        let result = (|| -> Result<String, Error> {
+            let dir = env
+                .call_method(activity, "getFilesDir", "()Ljava/io/File;", &[])?
+                .l()?;
+            let path = env
+                .call_method(&dir, "getAbsolutePath", "()Ljava/lang/String;", &[])?
+                .l()?;
+            let path: String = env.get_string(&JString::from(path))?.into();
+            Ok(path)
+        })();
+
+        let _ = tx.send(result);
+    });
+
+    match rx.recv() {
+        Ok(Ok(path)) => Ok(PathBuf::from(path)),
+        Ok(Err(err)) => Err(err.to_string()),
+        Err(err) => Err(err.to_string()),
+    }
+}
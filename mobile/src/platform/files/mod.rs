@@ -0,0 +1,34 @@
+use std::{path::PathBuf, sync::LazyLock};
+
+#[cfg(target_os = "android")]
+mod android;
+
+static DATA_DIR: LazyLock<PathBuf> = LazyLock::new(|| {
+    #[cfg(target_os = "android")]
+    let dir = android::files_dir().unwrap_or_else(|err| {
+        tracing::error!("Failed to get the app's files directory: {err}");
+        std::env::temp_dir()
+    });
+    // The home directory of an iOS app is its sandbox container
+    #[cfg(target_os = "ios")]
+    let dir = std::env::var_os("HOME")
+        .map(|home| {
+            PathBuf::from(home)
+                .join("Library")
+                .join("Application Support")
+        })
+        .unwrap_or_else(std::env::temp_dir);
+    #[cfg(not(any(target_os = "android", target_os = "ios")))]
+    let dir = std::env::var_os("XDG_DATA_HOME")
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".local/share")))
+        .unwrap_or_else(std::env::temp_dir)
+        .join("bind");
+
+    dir
+});
+
+/// Private directory for files the app keeps between launches
+pub fn data_dir() -> PathBuf {
+    DATA_DIR.clone()
+}
@@ -0,0 +1,42 @@
+//! Android haptics, using the haptic feedback of the activity's views.
+
+use dioxus::mobile::wry::prelude::dispatch;
+use jni::objects::{JObject, JValue};
+use jni::JNIEnv;
+use ui::gesture::Haptic;
+
+/// `HapticFeedbackConstants.CLOCK_TICK`
+const CLOCK_TICK: i32 = 4;
+/// `HapticFeedbackConstants.CONTEXT_CLICK`
+const CONTEXT_CLICK: i32 = 6;
+
+pub fn perform_haptic_feedback(haptic: Haptic) {
+    let constant = match haptic {
+        Haptic::Threshold => CLOCK_TICK,
+        Haptic::Action => CONTEXT_CLICK,
+    };
+
+    dispatch(move |env: &mut JNIEnv, activity: &JObject, _webview| {
+        // activity.getWindow().getDecorView().performHapticFeedback(constant)
+        let result: Result<(), jni::errors::Error> = (|| {
+            let window = env
+                .call_method(activity, "getWindow", "()Landroid/view/Window;", &[])?
+                .l()?;
+            let decor_view = env
+                .call_method(&window, "getDecorView", "()Landroid/view/View;", &[])?
+                .l()?;
+            env.call_method(
+                &decor_view,
+                "performHapticFeedback",
+                "(I)Z",
+                &[JValue::Int(constant)],
+            )?
+            .z()?;
+            Ok(())
+        })();
+
+        if let Err(err) = result {
+            tracing::error!("Failed to perform haptic feedback: {err}");
+        }
+    });
+}
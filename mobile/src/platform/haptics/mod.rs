@@ -0,0 +1,21 @@
+use ui::gesture::Haptic;
+
+#[cfg(target_os = "android")]
+mod android;
+
+/// Plays the haptic feedback on the device, where it has a way to
+pub fn haptic_feedback(haptic: Haptic) {
+    #[cfg(target_os = "android")]
+    android::perform_haptic_feedback(haptic);
+    // Elsewhere the web view's vibration is used, which does nothing where it isn't supported
+    #[cfg(not(target_os = "android"))]
+    {
+        let duration = match haptic {
+            Haptic::Threshold => 5,
+            Haptic::Action => 15,
+        };
+        dioxus::document::eval(&format!(
+            "navigator.vibrate && navigator.vibrate({duration});"
+        ));
+    }
+}
@@ -0,0 +1,32 @@
+use jni::{
+    objects::{JClass, JString},
+    JNIEnv,
+};
+
+use super::{receive_share, IncomingShare};
+
+// JNI function that gets called from Kotlin via `MainActivity.onShare_native(text, subject)`,
+// with `EXTRA_TEXT` and `EXTRA_SUBJECT` of `ACTION_SEND` intents in `onCreate` and `onNewIntent`.
+// `subject` may be null.
+#[no_mangle]
+pub extern "C" fn Java_dev_dioxus_main_MainActivity_onShare_1native(
+    mut env: JNIEnv,
+    _class: JClass,
+    text: JString,
+    subject: JString,
+) {
+    let text: String = match env.get_string(&text) {
+        Ok(text) => text.into(),
+        Err(err) => {
+            tracing::error!("Failed to read shared text: {err}");
+            return;
+        }
+    };
+    let subject = if subject.is_null() {
+        None
+    } else {
+        env.get_string(&subject).ok().map(String::from)
+    };
+
+    receive_share(IncomingShare { text, subject });
+}
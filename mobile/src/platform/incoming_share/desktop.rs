@@ -0,0 +1,15 @@
+//! Stand-in for testing shares without a device: launching the app with
+//! `BIND_SHARED_TEXT="https://example.com/post"` shares the text with it on startup.
+
+use super::{receive_share, IncomingShare};
+
+const SHARED_TEXT_VAR: &str = "BIND_SHARED_TEXT";
+
+pub fn share_from_env() {
+    if let Ok(text) = std::env::var(SHARED_TEXT_VAR) {
+        receive_share(IncomingShare {
+            text,
+            subject: None,
+        });
+    }
+}
@@ -0,0 +1,52 @@
+#[cfg(target_os = "android")]
+mod android;
+#[cfg(not(any(target_os = "android", target_os = "ios")))]
+mod desktop;
+
+use reqwest::Url;
+use std::sync::{mpsc, LazyLock, Mutex};
+
+/// Text another app shared with this one, e.g. from the Android Sharesheet
+#[derive(Debug, Clone, PartialEq)]
+pub struct IncomingShare {
+    pub text: String,
+    /// Subject of the share, which browsers set to the title of the page
+    pub subject: Option<String>,
+}
+
+impl IncomingShare {
+    /// The first web link in the text. Apps often share a title along with the link.
+    pub fn url(&self) -> Option<Url> {
+        self.text
+            .split_whitespace()
+            .filter_map(|word| Url::parse(word).ok())
+            .find(|url| matches!(url.scheme(), "http" | "https"))
+    }
+}
+
+// Channel for sending shares. Created up front, so a share which launched the app
+// is kept until the listener is initialized.
+static INCOMING_SHARES: LazyLock<(
+    mpsc::Sender<IncomingShare>,
+    Mutex<Option<mpsc::Receiver<IncomingShare>>>,
+)> = LazyLock::new(|| {
+    let (tx, rx) = mpsc::channel();
+    (tx, Mutex::new(Some(rx)))
+});
+
+// Initialize the share listener, receiving everything shared with the app
+pub fn init_share_listener() -> mpsc::Receiver<IncomingShare> {
+    #[cfg(not(any(target_os = "android", target_os = "ios")))]
+    desktop::share_from_env();
+
+    INCOMING_SHARES
+        .1
+        .lock()
+        .unwrap_or_else(|err| err.into_inner())
+        .take()
+        .expect("Share listener already initialized")
+}
+
+fn receive_share(share: IncomingShare) {
+    let _ = INCOMING_SHARES.0.send(share);
+}
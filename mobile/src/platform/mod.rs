@@ -1,16 +1,28 @@
+use dioxus::mobile::{
+    tao::event::{Event, WindowEvent},
+    use_wry_event_handler,
+};
 use dioxus::prelude::*;
 use std::sync::atomic::AtomicBool;
 
 mod decor;
+mod deep_link;
 mod email;
+mod files;
 mod gesture;
+mod haptics;
+mod incoming_share;
 mod share;
 mod storage;
 
+pub use deep_link::init_deep_link_listener;
 pub use email::open_email;
+pub use files::data_dir;
 pub use gesture::init_back_press_listener;
+pub use haptics::haptic_feedback;
+pub use incoming_share::{init_share_listener, IncomingShare};
 pub use share::share_feed_item;
-pub use storage::SecureStore;
+pub use storage::{set_active_account, Namespace, Preferences, SecureStore, StoreKey};
 
 static HAS_SETUP: AtomicBool = AtomicBool::new(false);
 
@@ -23,4 +35,23 @@ pub fn use_platform_setup() {
 
         decor::setup_decor();
     });
+
+    // The app may be killed once it's in the background, before the pending changes to the
+    // preferences are written in their batch
+    use_wry_event_handler(|event, _| {
+        let leaving = matches!(
+            event,
+            Event::Suspended
+                | Event::LoopDestroyed
+                | Event::WindowEvent {
+                    event: WindowEvent::CloseRequested | WindowEvent::Destroyed,
+                    ..
+                }
+        );
+        if leaving {
+            if let Err(err) = Preferences::flush() {
+                tracing::error!("Failed to save preferences: {err}");
+            }
+        }
+    });
 }
@@ -1,15 +1,19 @@
 //! Android Secure Storage Implementation using EncryptedSharedPreferences.
 
 use dioxus::mobile::wry::prelude::{dispatch, find_class};
-use jni::objects::{JByteArray, JObject, JString, JValue};
+use jni::objects::{JByteArray, JObject, JObjectArray, JString, JValue};
 use jni::JNIEnv;
 use std::sync::mpsc;
 
+use super::{SecretBackend, StorageError};
+
 #[derive(Debug)]
 pub enum SecureStorageError {
     Jni(jni::errors::Error),
     RecvError(mpsc::RecvError),
     FromUtf8Error(std::string::FromUtf8Error),
+    /// The value was encrypted with a key which is gone, e.g. after a restore from backup
+    Decryption,
 }
 
 impl From<jni::errors::Error> for SecureStorageError {
@@ -37,8 +41,36 @@ const PREFS_NAME: &str = "jwt_secure_prefs";
 const AES_TRANSFORMATION: &str = "AES/GCM/NoPadding";
 const GCM_TAG_LENGTH: i32 = 128;
 
+/// Values encrypted with a key from the Android Keystore, kept in shared preferences
+pub struct AndroidBackend;
+
+impl SecretBackend for AndroidBackend {
+    fn store(&self, key: &str, value: &str) -> std::result::Result<(), StorageError> {
+        secure_store(key, value).map_err(platform_error)
+    }
+
+    fn retrieve(&self, key: &str) -> std::result::Result<Option<String>, StorageError> {
+        secure_retrieve(key).map_err(platform_error)
+    }
+
+    fn remove(&self, key: &str) -> std::result::Result<(), StorageError> {
+        secure_remove(key).map_err(platform_error)
+    }
+
+    fn keys(&self) -> std::result::Result<Vec<String>, StorageError> {
+        secure_keys().map_err(platform_error)
+    }
+}
+
+fn platform_error(err: SecureStorageError) -> StorageError {
+    match err {
+        SecureStorageError::Decryption => StorageError::Decryption,
+        err => StorageError::Platform(format!("{err:?}")),
+    }
+}
+
 /// Stores a value securely using regular SharedPreferences with AES encryption
-pub fn secure_store(key: &str, value: &str) -> Result<()> {
+fn secure_store(key: &str, value: &str) -> Result<()> {
     let key_str = key.to_string();
     let value_str = value.to_string();
     let (tx, rx) = mpsc::channel::<Result<()>>();
@@ -48,19 +80,7 @@ pub fn secure_store(key: &str, value: &str) -> Result<()> {
             // Encrypt the value
             let encrypted_data = encrypt_with_keystore_key(env, &value_str)?;
 
-            // Get SharedPreferences
-            let prefs_name = env.new_string(PREFS_NAME)?;
-            let prefs = env
-                .call_method(
-                    activity,
-                    "getSharedPreferences",
-                    "(Ljava/lang/String;I)Landroid/content/SharedPreferences;",
-                    &[
-                        JValue::Object(&prefs_name),
-                        JValue::Int(0), // MODE_PRIVATE
-                    ],
-                )?
-                .l()?;
+            let prefs = shared_preferences(env, activity)?;
 
             // Get editor
             let editor = env
@@ -98,25 +118,13 @@ pub fn secure_store(key: &str, value: &str) -> Result<()> {
 }
 
 /// Retrieves a value securely using regular SharedPreferences with AES decryption
-pub fn secure_retrieve(key: &str) -> Result<Option<String>> {
+fn secure_retrieve(key: &str) -> Result<Option<String>> {
     let key_str = key.to_string();
     let (tx, rx) = mpsc::channel::<Result<Option<String>>>();
 
     dispatch(move |env: &mut JNIEnv, activity: &JObject, _webview| {
         let result = (|| -> Result<Option<String>> {
-            // Get SharedPreferences
-            let prefs_name = env.new_string(PREFS_NAME)?;
-            let prefs = env
-                .call_method(
-                    activity,
-                    "getSharedPreferences",
-                    "(Ljava/lang/String;I)Landroid/content/SharedPreferences;",
-                    &[
-                        JValue::Object(&prefs_name),
-                        JValue::Int(0), // MODE_PRIVATE
-                    ],
-                )?
-                .l()?;
+            let prefs = shared_preferences(env, activity)?;
 
             // Get encrypted value
             let key_jstring = env.new_string(&key_str)?;
@@ -136,36 +144,16 @@ pub fn secure_retrieve(key: &str) -> Result<Option<String>> {
                 return Ok(None);
             }
 
-            // Try to decrypt the value
+            // Try to decrypt the value. The value is left for the caller to remove, since the
+            // others may still be readable.
             match decrypt_with_keystore_key(env, encrypted_value) {
                 Ok(decrypted) => Ok(Some(decrypted)),
-                Err(e) => {
-                    // Check if the error is an AEADBadTagException
-                    if let SecureStorageError::Jni(jni_err) = &e {
-                        if is_aead_bad_tag_exception(env, jni_err) {
-                            // Clear the shared preferences
-                            let editor = env
-                                .call_method(
-                                    &prefs,
-                                    "edit",
-                                    "()Landroid/content/SharedPreferences$Editor;",
-                                    &[],
-                                )?
-                                .l()?;
-
-                            env.call_method(
-                                &editor,
-                                "clear",
-                                "()Landroid/content/SharedPreferences$Editor;",
-                                &[],
-                            )?;
-                            env.call_method(&editor, "apply", "()V", &[])?;
-
-                            return Ok(None);
-                        }
-                    }
-                    Err(e)
+                Err(SecureStorageError::Jni(jni_err))
+                    if is_aead_bad_tag_exception(env, &jni_err) =>
+                {
+                    Err(SecureStorageError::Decryption)
                 }
+                Err(e) => Err(e),
             }
         })();
 
@@ -175,6 +163,94 @@ pub fn secure_retrieve(key: &str) -> Result<Option<String>> {
     rx.recv()?
 }
 
+/// Removes a value from SharedPreferences
+fn secure_remove(key: &str) -> Result<()> {
+    let key_str = key.to_string();
+    let (tx, rx) = mpsc::channel::<Result<()>>();
+
+    dispatch(move |env: &mut JNIEnv, activity: &JObject, _webview| {
+        let result = (|| -> Result<()> {
+            let prefs = shared_preferences(env, activity)?;
+            let editor = env
+                .call_method(
+                    &prefs,
+                    "edit",
+                    "()Landroid/content/SharedPreferences$Editor;",
+                    &[],
+                )?
+                .l()?;
+
+            let key_jstring = env.new_string(&key_str)?;
+            env.call_method(
+                &editor,
+                "remove",
+                "(Ljava/lang/String;)Landroid/content/SharedPreferences$Editor;",
+                &[JValue::Object(&key_jstring.into())],
+            )?;
+            env.call_method(&editor, "apply", "()V", &[])?;
+
+            Ok(())
+        })();
+
+        let _ = tx.send(result);
+    });
+
+    rx.recv()?
+}
+
+/// Lists the keys of the values in SharedPreferences
+fn secure_keys() -> Result<Vec<String>> {
+    let (tx, rx) = mpsc::channel::<Result<Vec<String>>>();
+
+    dispatch(move |env: &mut JNIEnv, activity: &JObject, _webview| {
+        let result = (|| -> Result<Vec<String>> {
+            let prefs = shared_preferences(env, activity)?;
+            let all = env
+                .call_method(&prefs, "getAll", "()Ljava/util/Map;", &[])?
+                .l()?;
+            let key_set = env
+                .call_method(&all, "keySet", "()Ljava/util/Set;", &[])?
+                .l()?;
+            let keys = env
+                .call_method(&key_set, "toArray", "()[Ljava/lang/Object;", &[])?
+                .l()?;
+            let keys = JObjectArray::from(keys);
+
+            let length = env.get_array_length(&keys)?;
+            let mut result = Vec::with_capacity(length as usize);
+            for i in 0..length {
+                let key = env.get_object_array_element(&keys, i)?;
+                result.push(env.get_string(&JString::from(key))?.into());
+            }
+            Ok(result)
+        })();
+
+        let _ = tx.send(result);
+    });
+
+    rx.recv()?
+}
+
+/// The shared preferences the encrypted values are kept in
+fn shared_preferences<'local>(
+    env: &mut JNIEnv<'local>,
+    activity: &JObject,
+) -> Result<JObject<'local>> {
+    let prefs_name = env.new_string(PREFS_NAME)?;
+    let prefs = env
+        .call_method(
+            activity,
+            "getSharedPreferences",
+            "(Ljava/lang/String;I)Landroid/content/SharedPreferences;",
+            &[
+                JValue::Object(&prefs_name),
+                JValue::Int(0), // MODE_PRIVATE
+            ],
+        )?
+        .l()?;
+    Ok(prefs)
+}
+
 /// Helper function to check if a JNI error is caused by AEADBadTagException
 fn is_aead_bad_tag_exception(env: &mut JNIEnv, _jni_err: &jni::errors::Error) -> bool {
     // Check if there's a pending exception
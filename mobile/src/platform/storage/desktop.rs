@@ -0,0 +1,160 @@
+//! Desktop storage, using the system keyring where there is one and an encrypted file in the
+//! data directory otherwise.
+//!
+//! The file's key is derived from a random secret kept next to it, which only the user can read.
+//! That doesn't protect against the user's other programs, but keeps values out of plain sight
+//! and out of backups of the file alone.
+
+use std::{
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+    sync::Mutex,
+};
+
+use aes_gcm::{
+    aead::{rand_core::RngCore, Aead, AeadCore, KeyInit, OsRng},
+    Aes256Gcm, Key, Nonce,
+};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use hkdf::Hkdf;
+use sha2::Sha256;
+
+use super::{keyring::KeyringBackend, MemoryBackend, SecretBackend, StorageError};
+use crate::platform::data_dir;
+
+const SECRET_FILE: &str = "secret.key";
+const VALUES_FILE: &str = "secrets.json";
+/// Context for deriving the key, so the secret could be used for other keys later
+const KEY_INFO: &[u8] = b"bind secure store v1";
+const NONCE_LENGTH: usize = 12;
+
+pub fn backend() -> Box<dyn SecretBackend> {
+    let keyring = KeyringBackend::default();
+    if keyring.is_available() {
+        return Box::new(keyring);
+    }
+
+    tracing::info!("No system keyring available, storing secrets in an encrypted file");
+    match EncryptedFileBackend::open(&data_dir()) {
+        Ok(backend) => Box::new(backend),
+        Err(err) => {
+            tracing::error!("Failed to open secrets file, values won't be kept: {err}");
+            Box::new(MemoryBackend::default())
+        }
+    }
+}
+
+/// Values encrypted with AES-256-GCM, kept in a JSON file as base64 of the nonce followed by
+/// the ciphertext
+pub struct EncryptedFileBackend {
+    path: PathBuf,
+    cipher: Aes256Gcm,
+    values: Mutex<HashMap<String, String>>,
+}
+
+impl EncryptedFileBackend {
+    pub fn open(dir: &Path) -> Result<Self, StorageError> {
+        fs::create_dir_all(dir)?;
+
+        let secret = read_or_create_secret(&dir.join(SECRET_FILE))?;
+        let mut key = Key::<Aes256Gcm>::default();
+        Hkdf::<Sha256>::new(None, &secret)
+            .expand(KEY_INFO, &mut key)
+            .expect("32 bytes is a valid length for HKDF-SHA256");
+
+        let path = dir.join(VALUES_FILE);
+        let values = match fs::read(&path) {
+            Ok(contents) => serde_json::from_slice(&contents).unwrap_or_else(|err| {
+                tracing::error!("Secrets file is corrupted, starting over: {err}");
+                HashMap::new()
+            }),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => HashMap::new(),
+            Err(err) => return Err(err.into()),
+        };
+
+        Ok(Self {
+            path,
+            cipher: Aes256Gcm::new(&key),
+            values: Mutex::new(values),
+        })
+    }
+
+    /// Writes the values to a temporary file first, so a crash can't leave the file half written
+    fn save(&self, values: &HashMap<String, String>) -> Result<(), StorageError> {
+        let contents = serde_json::to_vec(values).expect("A map of strings always serializes");
+        let temporary = self.path.with_extension("json.tmp");
+        fs::write(&temporary, contents)?;
+        fs::rename(&temporary, &self.path)?;
+        Ok(())
+    }
+}
+
+impl SecretBackend for EncryptedFileBackend {
+    fn store(&self, key: &str, value: &str) -> Result<(), StorageError> {
+        let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+        let ciphertext = self
+            .cipher
+            .encrypt(&nonce, value.as_bytes())
+            .map_err(|_| StorageError::Platform("Failed to encrypt value".to_string()))?;
+        let encoded = STANDARD.encode([nonce.as_slice(), &ciphertext].concat());
+
+        let mut values = self.values.lock().unwrap_or_else(|err| err.into_inner());
+        values.insert(key.to_string(), encoded);
+        self.save(&values)
+    }
+
+    fn retrieve(&self, key: &str) -> Result<Option<String>, StorageError> {
+        let values = self.values.lock().unwrap_or_else(|err| err.into_inner());
+        let Some(encoded) = values.get(key) else {
+            return Ok(None);
+        };
+
+        let data = STANDARD
+            .decode(encoded)
+            .map_err(|_| StorageError::Decryption)?;
+        if data.len() < NONCE_LENGTH {
+            return Err(StorageError::Decryption);
+        }
+        let (nonce, ciphertext) = data.split_at(NONCE_LENGTH);
+        let plaintext = self
+            .cipher
+            .decrypt(Nonce::from_slice(nonce), ciphertext)
+            .map_err(|_| StorageError::Decryption)?;
+        String::from_utf8(plaintext)
+            .map(Some)
+            .map_err(|_| StorageError::Decryption)
+    }
+
+    fn remove(&self, key: &str) -> Result<(), StorageError> {
+        let mut values = self.values.lock().unwrap_or_else(|err| err.into_inner());
+        if values.remove(key).is_some() {
+            self.save(&values)?;
+        }
+        Ok(())
+    }
+
+    fn keys(&self) -> Result<Vec<String>, StorageError> {
+        let values = self.values.lock().unwrap_or_else(|err| err.into_inner());
+        Ok(values.keys().cloned().collect())
+    }
+}
+
+fn read_or_create_secret(path: &Path) -> Result<Vec<u8>, StorageError> {
+    match fs::read(path) {
+        Ok(secret) => return Ok(secret),
+        Err(err) if err.kind() != std::io::ErrorKind::NotFound => return Err(err.into()),
+        Err(_) => {}
+    }
+
+    let mut secret = vec![0; 32];
+    OsRng.fill_bytes(&mut secret);
+
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+    std::io::Write::write_all(&mut options.open(path)?, &secret)?;
+
+    Ok(secret)
+}
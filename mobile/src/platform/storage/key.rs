@@ -0,0 +1,170 @@
+use std::{marker::PhantomData, sync::RwLock};
+
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_json::Value;
+
+use super::StorageError;
+
+/// Account whose values are read and written in the namespaces which are partitioned by account
+static ACCOUNT: RwLock<Option<String>> = RwLock::new(None);
+
+/// Group of values, which decides what happens to them when the user logs out
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Namespace {
+    /// Credentials, like the tokens of the accounts
+    Secrets,
+    /// Settings of the device, shared by every account
+    Preferences,
+    /// Settings of the active account
+    Account,
+    /// Data of the active account which can be fetched or built again
+    Cache,
+}
+
+impl Namespace {
+    pub const ALL: [Namespace; 4] = [
+        Namespace::Secrets,
+        Namespace::Preferences,
+        Namespace::Account,
+        Namespace::Cache,
+    ];
+
+    /// Prefix of the storage keys in the namespace, for the active account
+    pub(super) fn prefix(self) -> String {
+        let account = ACCOUNT.read().unwrap_or_else(|err| err.into_inner());
+        self.prefix_for(account.as_deref())
+    }
+
+    pub(super) fn prefix_for(self, account: Option<&str>) -> String {
+        let namespace = match self {
+            Namespace::Secrets => return "secrets.".to_string(),
+            Namespace::Preferences => return "preferences.".to_string(),
+            Namespace::Account => "account",
+            Namespace::Cache => "cache",
+        };
+        // Values used while logged out are kept apart from every account's
+        format!("{namespace}.{}.", account.unwrap_or("-"))
+    }
+
+    /// Whether every account has its own values, which are removed when it logs out
+    pub fn is_account_scoped(self) -> bool {
+        matches!(self, Namespace::Account | Namespace::Cache)
+    }
+}
+
+/// Reads and writes the values of the account from now on, or the values used while logged
+/// out for `None`
+pub fn set_active_account(account: Option<&str>) {
+    *ACCOUNT.write().unwrap_or_else(|err| err.into_inner()) = account.map(str::to_string);
+}
+
+/// Upgrades a value stored with an older version of its schema to the current one, or
+/// returns `None` if it can't be.
+///
+/// Version 0 is a value stored before values were versioned.
+pub type Migration = fn(version: u32, value: Value) -> Option<Value>;
+
+/// Key of a value of type `T` in the [SecureStore](super::SecureStore) or the
+/// [Preferences](super::Preferences)
+pub struct StoreKey<T> {
+    pub namespace: Namespace,
+    pub name: &'static str,
+    /// Version of the value's schema. Increased with changes which stored values can't be
+    /// deserialized with anymore, along with a [Migration] for them.
+    pub version: u32,
+    pub migrate: Migration,
+    value: PhantomData<fn() -> T>,
+}
+
+impl<T> StoreKey<T> {
+    /// Key of a value at version 1, which takes values stored before versioning as they are
+    pub const fn new(namespace: Namespace, name: &'static str) -> Self {
+        Self {
+            namespace,
+            name,
+            version: 1,
+            migrate: keep_unversioned,
+            value: PhantomData,
+        }
+    }
+
+    pub const fn with_version(self, version: u32, migrate: Migration) -> Self {
+        Self {
+            version,
+            migrate,
+            ..self
+        }
+    }
+
+    /// Key the value is stored under in the backend
+    pub fn storage_key(&self) -> String {
+        format!("{}{}", self.namespace.prefix(), self.name)
+    }
+}
+
+impl<T: Serialize + DeserializeOwned> StoreKey<T> {
+    /// Serializes the value along with the version of its schema
+    pub(super) fn encode(&self, value: &T) -> Result<String, StorageError> {
+        serde_json::to_string(&Versioned {
+            version: self.version,
+            value,
+        })
+        .map_err(|err| self.invalid(err))
+    }
+
+    /// Deserializes a value stored by [encode](Self::encode). Returns whether it was migrated
+    /// from an older version, so it can be stored again.
+    pub(super) fn decode(&self, stored: &str) -> Result<(T, bool), StorageError> {
+        let stored: Versioned<Value> =
+            serde_json::from_str(stored).map_err(|err| self.invalid(err))?;
+        self.decode_value(stored.version, stored.value)
+    }
+
+    pub(super) fn decode_value(
+        &self,
+        version: u32,
+        value: Value,
+    ) -> Result<(T, bool), StorageError> {
+        if version == self.version {
+            let value = serde_json::from_value(value).map_err(|err| self.invalid(err))?;
+            return Ok((value, false));
+        }
+
+        let migrated = (version < self.version)
+            .then(|| (self.migrate)(version, value))
+            .flatten()
+            .ok_or_else(|| StorageError::Migration {
+                key: self.storage_key(),
+                version,
+            })?;
+        let value = serde_json::from_value(migrated).map_err(|err| self.invalid(err))?;
+        Ok((value, true))
+    }
+
+    fn invalid(&self, err: serde_json::Error) -> StorageError {
+        StorageError::InvalidValue {
+            key: self.storage_key(),
+            message: err.to_string(),
+        }
+    }
+}
+
+/// A value along with the version of its schema, as it is stored
+#[derive(Serialize, Deserialize)]
+struct Versioned<T> {
+    version: u32,
+    value: T,
+}
+
+// Not derived, which would require `T: Clone`
+impl<T> Clone for StoreKey<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for StoreKey<T> {}
+
+fn keep_unversioned(version: u32, value: Value) -> Option<Value> {
+    (version == 0).then_some(value)
+}
@@ -0,0 +1,90 @@
+//! Storage in the keyring of the operating system: the Keychain on iOS and macOS, the Secret
+//! Service on Linux and the Credential Manager on Windows.
+
+use std::sync::Mutex;
+
+use keyring::{Entry, Error};
+
+use super::{SecretBackend, StorageError};
+
+/// Service the values are stored under, one entry per key
+const SERVICE: &str = "sh.bind.app";
+
+/// Entry listing the keys of the other entries, since keyrings can't list them
+const INDEX_KEY: &str = "__index";
+
+#[derive(Default)]
+pub struct KeyringBackend {
+    /// Held while the index is updated, so concurrent changes can't lose keys
+    index_lock: Mutex<()>,
+}
+
+impl KeyringBackend {
+    /// Whether the keyring can be used, which it can't on Linux without a Secret Service
+    #[cfg(not(target_os = "ios"))]
+    pub fn is_available(&self) -> bool {
+        self.retrieve(INDEX_KEY).is_ok()
+    }
+
+    fn update_index(&self, update: impl FnOnce(&mut Vec<String>)) -> Result<(), StorageError> {
+        let _lock = self
+            .index_lock
+            .lock()
+            .unwrap_or_else(|err| err.into_inner());
+        let mut keys = self.keys()?;
+        let previous = keys.clone();
+        update(&mut keys);
+        if keys != previous {
+            let index = serde_json::to_string(&keys).expect("A list of strings always serializes");
+            entry(INDEX_KEY)?
+                .set_password(&index)
+                .map_err(platform_error)?;
+        }
+        Ok(())
+    }
+}
+
+impl SecretBackend for KeyringBackend {
+    fn store(&self, key: &str, value: &str) -> Result<(), StorageError> {
+        entry(key)?.set_password(value).map_err(platform_error)?;
+        self.update_index(|keys| {
+            if !keys.iter().any(|existing| existing == key) {
+                keys.push(key.to_string());
+            }
+        })
+    }
+
+    fn retrieve(&self, key: &str) -> Result<Option<String>, StorageError> {
+        match entry(key)?.get_password() {
+            Ok(value) => Ok(Some(value)),
+            Err(Error::NoEntry) => Ok(None),
+            Err(err) => Err(platform_error(err)),
+        }
+    }
+
+    fn remove(&self, key: &str) -> Result<(), StorageError> {
+        match entry(key)?.delete_credential() {
+            Ok(()) | Err(Error::NoEntry) => {}
+            Err(err) => return Err(platform_error(err)),
+        }
+        self.update_index(|keys| keys.retain(|existing| existing != key))
+    }
+
+    fn keys(&self) -> Result<Vec<String>, StorageError> {
+        let Some(index) = self.retrieve(INDEX_KEY)? else {
+            return Ok(Vec::new());
+        };
+        serde_json::from_str(&index).map_err(|err| StorageError::InvalidValue {
+            key: INDEX_KEY.to_string(),
+            message: err.to_string(),
+        })
+    }
+}
+
+fn entry(key: &str) -> Result<Entry, StorageError> {
+    Entry::new(SERVICE, key).map_err(platform_error)
+}
+
+fn platform_error(err: Error) -> StorageError {
+    StorageError::Platform(err.to_string())
+}
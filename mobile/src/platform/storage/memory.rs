@@ -0,0 +1,33 @@
+use std::{collections::HashMap, sync::Mutex};
+
+use super::{SecretBackend, StorageError};
+
+/// Keeps values in memory only, for tests and as a last resort when nothing else works
+#[derive(Default)]
+pub struct MemoryBackend {
+    values: Mutex<HashMap<String, String>>,
+}
+
+impl SecretBackend for MemoryBackend {
+    fn store(&self, key: &str, value: &str) -> Result<(), StorageError> {
+        let mut values = self.values.lock().unwrap_or_else(|err| err.into_inner());
+        values.insert(key.to_string(), value.to_string());
+        Ok(())
+    }
+
+    fn retrieve(&self, key: &str) -> Result<Option<String>, StorageError> {
+        let values = self.values.lock().unwrap_or_else(|err| err.into_inner());
+        Ok(values.get(key).cloned())
+    }
+
+    fn remove(&self, key: &str) -> Result<(), StorageError> {
+        let mut values = self.values.lock().unwrap_or_else(|err| err.into_inner());
+        values.remove(key);
+        Ok(())
+    }
+
+    fn keys(&self) -> Result<Vec<String>, StorageError> {
+        let values = self.values.lock().unwrap_or_else(|err| err.into_inner());
+        Ok(values.keys().cloned().collect())
+    }
+}
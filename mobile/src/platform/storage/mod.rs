@@ -1,13 +1,70 @@
-use dioxus::{
-    hooks::use_signal,
-    signals::{Readable, Signal, Writable},
-};
-use serde::{de::DeserializeOwned, Serialize};
+//! Storage for values which are kept between launches.
+//!
+//! [SecureStore] keeps secrets, like the token, in a [SecretBackend] for the platform: encrypted
+//! shared preferences on Android, the Keychain on iOS, and the system keyring on desktops,
+//! falling back to an encrypted file where there is none. [MemoryBackend] keeps them for tests.
+//!
+//! Everything else goes in the faster [Preferences], which isn't encrypted.
+//!
+//! Every account has its own values in the namespaces which are
+//! [scoped](Namespace::is_account_scoped) to it, read and written for the account made active
+//! with [set_active_account].
+
+use thiserror::Error;
 
 #[cfg(target_os = "android")]
 mod android;
-#[cfg(target_os = "ios")]
-mod ios;
+#[cfg(not(any(target_os = "android", target_os = "ios")))]
+mod desktop;
+mod key;
+#[cfg(not(target_os = "android"))]
+mod keyring;
+mod memory;
+mod preferences;
 mod store;
 
+pub use key::{set_active_account, Namespace, StoreKey};
+pub use memory::MemoryBackend;
+pub use preferences::Preferences;
 pub use store::SecureStore;
+
+#[derive(Error, Debug)]
+pub enum StorageError {
+    /// The secure storage of the platform failed
+    #[error("Secure storage failed: {0}")]
+    Platform(String),
+    #[error("Failed to access secrets file: {0}")]
+    Io(#[from] std::io::Error),
+    /// A value couldn't be decrypted, e.g. because the key it was encrypted with is gone
+    #[error("Failed to decrypt value")]
+    Decryption,
+    /// The stored value doesn't match the type it's read as
+    #[error("Invalid value for {key}: {message}")]
+    InvalidValue { key: String, message: String },
+    #[error("No migration for {key} from version {version}")]
+    Migration { key: String, version: u32 },
+}
+
+/// Where [SecureStore] keeps values
+pub trait SecretBackend: Send + Sync {
+    fn store(&self, key: &str, value: &str) -> Result<(), StorageError>;
+
+    fn retrieve(&self, key: &str) -> Result<Option<String>, StorageError>;
+
+    /// Removes the value, if there is one
+    fn remove(&self, key: &str) -> Result<(), StorageError>;
+
+    fn keys(&self) -> Result<Vec<String>, StorageError>;
+}
+
+/// Backend for the platform the app runs on
+fn platform_backend() -> Box<dyn SecretBackend> {
+    #[cfg(target_os = "android")]
+    let backend: Box<dyn SecretBackend> = Box::new(android::AndroidBackend);
+    #[cfg(target_os = "ios")]
+    let backend: Box<dyn SecretBackend> = Box::new(keyring::KeyringBackend::default());
+    #[cfg(not(any(target_os = "android", target_os = "ios")))]
+    let backend = desktop::backend();
+
+    backend
+}
@@ -0,0 +1,129 @@
+//! Android preferences using plain SharedPreferences, apart from the encrypted ones of the
+//! secure store.
+
+use std::{collections::HashMap, sync::mpsc};
+
+use dioxus::mobile::wry::prelude::dispatch;
+use jni::{
+    objects::{JObject, JObjectArray, JString, JValue},
+    JNIEnv,
+};
+
+use super::PreferencesBackend;
+use crate::platform::storage::StorageError;
+
+const PREFS_NAME: &str = "bind_preferences";
+
+pub struct SharedPreferencesBackend;
+
+impl PreferencesBackend for SharedPreferencesBackend {
+    fn load(&self) -> Result<HashMap<String, String>, StorageError> {
+        run(|env, prefs| {
+            let all = env
+                .call_method(prefs, "getAll", "()Ljava/util/Map;", &[])?
+                .l()?;
+            let key_set = env
+                .call_method(&all, "keySet", "()Ljava/util/Set;", &[])?
+                .l()?;
+            let keys = JObjectArray::from(
+                env.call_method(&key_set, "toArray", "()[Ljava/lang/Object;", &[])?
+                    .l()?,
+            );
+
+            let mut values = HashMap::new();
+            for i in 0..env.get_array_length(&keys)? {
+                let key = JString::from(env.get_object_array_element(&keys, i)?);
+                let value = env
+                    .call_method(
+                        prefs,
+                        "getString",
+                        "(Ljava/lang/String;Ljava/lang/String;)Ljava/lang/String;",
+                        &[JValue::Object(&key), JValue::Object(&JObject::null())],
+                    )?
+                    .l()?;
+                if !value.is_null() {
+                    let key: String = env.get_string(&key)?.into();
+                    let value: String = env.get_string(&JString::from(value))?.into();
+                    values.insert(key, value);
+                }
+            }
+            Ok(values)
+        })
+    }
+
+    /// Applies the changes in one edit
+    fn save(
+        &self,
+        _values: &HashMap<String, String>,
+        changes: &HashMap<String, Option<String>>,
+    ) -> Result<(), StorageError> {
+        let changes = changes.clone();
+        run(move |env, prefs| {
+            let editor = env
+                .call_method(
+                    prefs,
+                    "edit",
+                    "()Landroid/content/SharedPreferences$Editor;",
+                    &[],
+                )?
+                .l()?;
+
+            for (key, value) in &changes {
+                let key = env.new_string(key)?;
+                match value {
+                    Some(value) => {
+                        let value = env.new_string(value)?;
+                        env.call_method(
+                            &editor,
+                            "putString",
+                            "(Ljava/lang/String;Ljava/lang/String;)Landroid/content/SharedPreferences$Editor;",
+                            &[JValue::Object(&key), JValue::Object(&value)],
+                        )?;
+                    }
+                    None => {
+                        env.call_method(
+                            &editor,
+                            "remove",
+                            "(Ljava/lang/String;)Landroid/content/SharedPreferences$Editor;",
+                            &[JValue::Object(&key)],
+                        )?;
+                    }
+                }
+            }
+
+            env.call_method(&editor, "apply", "()V", &[])?;
+            Ok(())
+        })
+    }
+}
+
+/// Runs the function with the app's SharedPreferences on the main thread, waiting for it
+fn run<T: Send + 'static>(
+    f: impl FnOnce(&mut JNIEnv, &JObject) -> Result<T, jni::errors::Error> + Send + 'static,
+) -> Result<T, StorageError> {
+    let (tx, rx) = mpsc::channel();
+
+    dispatch(move |env: &mut JNIEnv, activity: &JObject, _webview| {
+        let result = (|| {
+            let prefs_name = env.new_string(PREFS_NAME)?;
+            let prefs = env
+                .call_method(
+                    activity,
+                    "getSharedPreferences",
+                    "(Ljava/lang/String;I)Landroid/content/SharedPreferences;",
+                    &[
+                        JValue::Object(&prefs_name),
+                        JValue::Int(0), // MODE_PRIVATE
+                    ],
+                )?
+                .l()?;
+            f(env, &prefs)
+        })();
+
+        let _ = tx.send(result);
+    });
+
+    rx.recv()
+        .map_err(|err| StorageError::Platform(err.to_string()))?
+        .map_err(|err| StorageError::Platform(err.to_string()))
+}
@@ -0,0 +1,48 @@
+use std::{collections::HashMap, fs, path::PathBuf};
+
+use super::PreferencesBackend;
+use crate::platform::{data_dir, storage::StorageError};
+
+const PREFERENCES_FILE: &str = "preferences.json";
+
+/// Values in a JSON file in the data directory
+pub struct FileBackend {
+    path: PathBuf,
+}
+
+impl FileBackend {
+    pub fn new() -> Self {
+        Self {
+            path: data_dir().join(PREFERENCES_FILE),
+        }
+    }
+}
+
+impl PreferencesBackend for FileBackend {
+    fn load(&self) -> Result<HashMap<String, String>, StorageError> {
+        match fs::read(&self.path) {
+            Ok(contents) => Ok(serde_json::from_slice(&contents).unwrap_or_else(|err| {
+                tracing::error!("Preferences file is corrupted, starting over: {err}");
+                HashMap::new()
+            })),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(HashMap::new()),
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    /// Writes to a temporary file first, so a crash can't leave the file half written
+    fn save(
+        &self,
+        values: &HashMap<String, String>,
+        _changes: &HashMap<String, Option<String>>,
+    ) -> Result<(), StorageError> {
+        if let Some(dir) = self.path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let contents = serde_json::to_vec(values).expect("A map of strings always serializes");
+        let temporary = self.path.with_extension("json.tmp");
+        fs::write(&temporary, contents)?;
+        fs::rename(&temporary, &self.path)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,252 @@
+//! Fast storage for values which aren't secret, like settings and caches, kept apart from the
+//! [SecureStore] so they skip its encryption.
+//!
+//! Values are read from memory once loaded, and changes are written in batches [WRITE_DELAY]
+//! after the first one. Every change is broadcast to [subscribers](Preferences::subscribe).
+
+use std::{
+    collections::HashMap,
+    sync::{LazyLock, Mutex, MutexGuard, OnceLock},
+    time::Duration,
+};
+
+use serde::{de::DeserializeOwned, Serialize};
+use tokio::sync::broadcast;
+
+use super::{Namespace, SecureStore, StorageError, StoreKey};
+
+#[cfg(target_os = "android")]
+mod android;
+#[cfg(not(target_os = "android"))]
+mod file;
+
+/// How long changes are collected before they're written
+const WRITE_DELAY: Duration = Duration::from_millis(500);
+
+/// Set once the values which used to be kept in the [SecureStore] have been moved here
+const MIGRATED: StoreKey<bool> = StoreKey::new(Namespace::Preferences, "migrated_secure_store");
+
+/// Where [Preferences] keeps values
+pub trait PreferencesBackend: Send + Sync {
+    fn load(&self) -> Result<HashMap<String, String>, StorageError>;
+
+    /// Writes the changed values, `None` for removed ones. `values` holds every value after
+    /// the changes, for backends which write them all at once.
+    fn save(
+        &self,
+        values: &HashMap<String, String>,
+        changes: &HashMap<String, Option<String>>,
+    ) -> Result<(), StorageError>;
+}
+
+#[derive(Default)]
+struct State {
+    values: HashMap<String, String>,
+    /// Changes which haven't been written yet
+    pending: HashMap<String, Option<String>>,
+    write_scheduled: bool,
+}
+
+static BACKEND: OnceLock<Box<dyn PreferencesBackend>> = OnceLock::new();
+
+static STATE: LazyLock<Mutex<State>> = LazyLock::new(|| {
+    let mut values = backend().load().unwrap_or_else(|err| {
+        tracing::error!("Failed to load preferences: {err}");
+        HashMap::new()
+    });
+    if !values.contains_key(&MIGRATED.storage_key()) {
+        migrate_from_secure_store(&mut values);
+    }
+    Mutex::new(State {
+        values,
+        ..Default::default()
+    })
+});
+
+/// Held while changes are written, so batches can't be written out of order
+static WRITE_LOCK: Mutex<()> = Mutex::new(());
+
+/// Storage keys of changed values
+static CHANGES: LazyLock<broadcast::Sender<String>> = LazyLock::new(|| broadcast::channel(64).0);
+
+/// Moves the values which used to be kept in the [SecureStore], along with the secrets, into the
+/// preferences. They're only removed from the secure store once they're written here.
+fn migrate_from_secure_store(values: &mut HashMap<String, String>) {
+    let moved = match SecureStore::non_secrets() {
+        Ok(moved) => moved,
+        Err(err) => {
+            tracing::error!("Failed to read values to move from the secure store: {err}");
+            return;
+        }
+    };
+    let marker = match MIGRATED.encode(&true) {
+        Ok(marker) => marker,
+        Err(err) => {
+            tracing::error!("Failed to mark preferences as migrated: {err}");
+            return;
+        }
+    };
+
+    let mut changes = HashMap::new();
+    for (key, value) in moved.iter().cloned() {
+        // Values which are already here were set after they moved
+        if !values.contains_key(&key) {
+            values.insert(key.clone(), value.clone());
+            changes.insert(key, Some(value));
+        }
+    }
+    values.insert(MIGRATED.storage_key(), marker.clone());
+    changes.insert(MIGRATED.storage_key(), Some(marker));
+    if let Err(err) = backend().save(values, &changes) {
+        tracing::error!("Failed to save values moved from the secure store: {err}");
+        return;
+    }
+
+    for (key, _) in moved {
+        if let Err(err) = SecureStore::remove_raw(&key) {
+            tracing::error!("Failed to remove {key} from the secure store: {err}");
+        }
+    }
+}
+
+fn backend() -> &'static dyn PreferencesBackend {
+    BACKEND.get_or_init(platform_backend).as_ref()
+}
+
+fn platform_backend() -> Box<dyn PreferencesBackend> {
+    #[cfg(target_os = "android")]
+    let backend: Box<dyn PreferencesBackend> = Box::new(android::SharedPreferencesBackend);
+    #[cfg(not(target_os = "android"))]
+    let backend: Box<dyn PreferencesBackend> = Box::new(file::FileBackend::new());
+
+    backend
+}
+
+fn state() -> MutexGuard<'static, State> {
+    STATE.lock().unwrap_or_else(|err| err.into_inner())
+}
+
+pub struct Preferences;
+
+impl Preferences {
+    /// Uses another backend than the platform's. Must be called before preferences are first used.
+    pub fn init_backend(backend: impl PreferencesBackend + 'static) {
+        if BACKEND.set(Box::new(backend)).is_err() {
+            panic!("Preferences backend already initialized");
+        }
+    }
+
+    pub fn set<T: Serialize + DeserializeOwned>(
+        key: &StoreKey<T>,
+        value: &T,
+    ) -> Result<(), StorageError> {
+        debug_assert_ne!(
+            key.namespace,
+            Namespace::Secrets,
+            "Secrets belong in SecureStore"
+        );
+        Self::write(key.storage_key(), Some(key.encode(value)?));
+        Ok(())
+    }
+
+    /// Reads a value, migrating it if it was stored with an older version of its schema
+    pub fn get<T: Serialize + DeserializeOwned>(
+        key: &StoreKey<T>,
+    ) -> Result<Option<T>, StorageError> {
+        debug_assert_ne!(
+            key.namespace,
+            Namespace::Secrets,
+            "Secrets belong in SecureStore"
+        );
+        let stored = state().values.get(&key.storage_key()).cloned();
+        let Some(stored) = stored else {
+            return Ok(None);
+        };
+
+        let (value, migrated) = key.decode(&stored)?;
+        if migrated {
+            Self::set(key, &value)?;
+        }
+        Ok(Some(value))
+    }
+
+    pub fn remove<T>(key: &StoreKey<T>) -> Result<(), StorageError> {
+        Self::write(key.storage_key(), None);
+        Ok(())
+    }
+
+    /// Removes every value in the namespace
+    pub fn clear(namespace: Namespace) {
+        Self::remove_prefixed(&namespace.prefix());
+    }
+
+    /// Removes the values of an account which is logging out, keeping the settings of the device
+    /// and the values of the other accounts
+    pub fn clear_account(account: &str) {
+        for namespace in Namespace::ALL {
+            if namespace.is_account_scoped() {
+                Self::remove_prefixed(&namespace.prefix_for(Some(account)));
+            }
+        }
+    }
+
+    /// Receives the storage key of every value which changes from now on
+    pub fn subscribe() -> broadcast::Receiver<String> {
+        CHANGES.subscribe()
+    }
+
+    /// Writes the pending changes now instead of waiting for the batch
+    pub fn flush() -> Result<(), StorageError> {
+        let _lock = WRITE_LOCK.lock().unwrap_or_else(|err| err.into_inner());
+        let (values, changes) = {
+            let mut state = state();
+            if state.pending.is_empty() {
+                return Ok(());
+            }
+            (state.values.clone(), std::mem::take(&mut state.pending))
+        };
+        backend().save(&values, &changes)
+    }
+
+    fn remove_prefixed(prefix: &str) {
+        let keys: Vec<String> = state()
+            .values
+            .keys()
+            .filter(|key| key.starts_with(prefix))
+            .cloned()
+            .collect();
+        for key in keys {
+            Self::write(key, None);
+        }
+    }
+
+    fn write(storage_key: String, value: Option<String>) {
+        let schedule = {
+            let mut state = state();
+            let changed = match &value {
+                Some(value) => {
+                    state.values.insert(storage_key.clone(), value.clone()) != Some(value.clone())
+                }
+                None => state.values.remove(&storage_key).is_some(),
+            };
+            if !changed {
+                return;
+            }
+            state.pending.insert(storage_key.clone(), value);
+            !std::mem::replace(&mut state.write_scheduled, true)
+        };
+
+        let _ = CHANGES.send(storage_key);
+
+        if schedule {
+            // A thread rather than a task, since writing blocks on the platform
+            std::thread::spawn(|| {
+                std::thread::sleep(WRITE_DELAY);
+                state().write_scheduled = false;
+                if let Err(err) = Self::flush() {
+                    tracing::error!("Failed to save preferences: {err}");
+                }
+            });
+        }
+    }
+}
@@ -1,58 +1,327 @@
-use dioxus::prelude::spawn;
 use serde::{de::DeserializeOwned, Serialize};
+use serde_json::Value;
 
-#[cfg(target_os = "android")]
-use super::android::*;
-#[cfg(target_os = "ios")]
-use super::ios::*;
+use super::{platform_backend, Namespace, SecretBackend, StorageError, StoreKey};
 
 use std::{
-    cell::LazyCell,
     collections::HashMap,
-    sync::{LazyLock, Mutex},
-    time::Instant,
+    sync::{LazyLock, Mutex, MutexGuard, OnceLock},
 };
 
-// Cache for secret values to avoid frequent JNI calls
+// Backend for values, picked for the platform on first use
+static BACKEND: OnceLock<Box<dyn SecretBackend>> = OnceLock::new();
+
+// Cache for stored values to avoid frequent calls to the backend
 static SECRET_CACHE: LazyLock<Mutex<HashMap<String, String>>> =
     LazyLock::new(|| Mutex::new(HashMap::new()));
 
+fn backend() -> &'static dyn SecretBackend {
+    BACKEND.get_or_init(platform_backend).as_ref()
+}
+
+fn cache() -> MutexGuard<'static, HashMap<String, String>> {
+    SECRET_CACHE.lock().unwrap_or_else(|err| err.into_inner())
+}
+
 pub struct SecureStore;
 
 impl SecureStore {
-    // Store a secret value
-    pub fn set<T: Serialize>(key: &str, value: T) {
-        let key = key.to_string();
-        let serialized = serde_json::to_string(&value).unwrap();
-        // TODO: check if the value is already set to the same value
-        secure_store(&key, serialized.as_str());
+    // Use another backend than the platform's, e.g. a `MemoryBackend` in tests.
+    // Must be called before the store is first used.
+    pub fn init_backend(backend: impl SecretBackend + 'static) {
+        if BACKEND.set(Box::new(backend)).is_err() {
+            panic!("Secure store backend already initialized");
+        }
+    }
+
+    // Store a secret value. Other values belong in `Preferences`, which skips the encryption.
+    pub fn set<T: Serialize + DeserializeOwned>(
+        key: &StoreKey<T>,
+        value: &T,
+    ) -> Result<(), StorageError> {
+        debug_assert_eq!(key.namespace, Namespace::Secrets, "Not a secret");
+        Self::write(key, value)
+    }
 
-        // Update cache
-        SECRET_CACHE.lock().unwrap().insert(key, serialized);
+    // Retrieve a secret value, migrating it if it was stored with an older version of its schema
+    pub fn get<T: Serialize + DeserializeOwned>(
+        key: &StoreKey<T>,
+    ) -> Result<Option<T>, StorageError> {
+        debug_assert_eq!(key.namespace, Namespace::Secrets, "Not a secret");
+        Self::read(key)
     }
 
-    // Retrieve a secret value
-    pub fn get<T: DeserializeOwned>(key: &str) -> Option<T> {
+    /// Values stored outside of the secrets, by storage key, from before they were kept in the
+    /// [Preferences](super::Preferences)
+    pub(super) fn non_secrets() -> Result<Vec<(String, String)>, StorageError> {
+        let mut values = Vec::new();
+        for key in backend().keys()? {
+            let namespaced = key.split_once('.').is_some_and(|(namespace, _)| {
+                matches!(namespace, "preferences" | "account" | "cache")
+            });
+            if !namespaced {
+                continue;
+            }
+            if let Some(value) = backend().retrieve(&key)? {
+                values.push((key, value));
+            }
+        }
+        Ok(values)
+    }
+
+    pub fn remove<T>(key: &StoreKey<T>) -> Result<(), StorageError> {
+        Self::remove_raw(&key.storage_key())
+    }
+
+    /// Names of the keys with values in the namespace
+    pub fn keys(namespace: Namespace) -> Result<Vec<String>, StorageError> {
+        let prefix = namespace.prefix();
+        Ok(backend()
+            .keys()?
+            .into_iter()
+            .filter_map(|key| key.strip_prefix(&prefix).map(str::to_string))
+            .collect())
+    }
+
+    /// Removes every value in the namespace
+    pub fn clear(namespace: Namespace) -> Result<(), StorageError> {
+        let prefix = namespace.prefix();
+        for name in Self::keys(namespace)? {
+            Self::remove_raw(&format!("{prefix}{name}"))?;
+        }
+        Ok(())
+    }
+
+    fn write<T: Serialize + DeserializeOwned>(
+        key: &StoreKey<T>,
+        value: &T,
+    ) -> Result<(), StorageError> {
+        let storage_key = key.storage_key();
+        let serialized = key.encode(value)?;
+
+        if cache().get(&storage_key) == Some(&serialized) {
+            return Ok(());
+        }
+        backend().store(&storage_key, &serialized)?;
+        cache().insert(storage_key, serialized);
+        Ok(())
+    }
+
+    fn read<T: Serialize + DeserializeOwned>(key: &StoreKey<T>) -> Result<Option<T>, StorageError> {
+        if let Some(stored) = Self::retrieve(&key.storage_key())? {
+            let (value, migrated) = key.decode(&stored)?;
+            if migrated {
+                Self::write(key, &value)?;
+            }
+            return Ok(Some(value));
+        }
+
+        // The value from before values were versioned is only removed once it is stored under
+        // the versioned key, so it isn't lost if the migration fails
+        let Some(stored) = backend().retrieve(key.name)? else {
+            return Ok(None);
+        };
+        let value = match Self::parse_unversioned(key, &stored)? {
+            Some(value) => {
+                let (value, _) = key.decode_value(0, value)?;
+                Self::write(key, &value)?;
+                Some(value)
+            }
+            None => None,
+        };
+        backend().remove(key.name)?;
+        Ok(value)
+    }
+
+    fn retrieve(storage_key: &str) -> Result<Option<String>, StorageError> {
         // Check cache first
-        if let Some(value) = SECRET_CACHE.lock().unwrap().get(key) {
-            let deserialized: T = serde_json::from_str(value).unwrap();
-            return Some(deserialized);
+        if let Some(value) = cache().get(storage_key) {
+            return Ok(Some(value.clone()));
         }
 
-        // If not in cache, fetch from secure storage
-        let result = secure_retrieve(key).unwrap_or(None);
+        // If not in cache, fetch from the backend
+        let result = match backend().retrieve(storage_key) {
+            Ok(result) => result,
+            Err(StorageError::Decryption) => {
+                // Only this value is lost, the others may still be readable
+                tracing::warn!("Removing {storage_key}, which can't be decrypted anymore");
+                Self::remove_raw(storage_key)?;
+                return Err(StorageError::Decryption);
+            }
+            Err(err) => return Err(err),
+        };
 
         // Update cache if value exists
-        if let Some(ref value) = result {
-            SECRET_CACHE
-                .lock()
-                .unwrap()
-                .insert(key.to_string(), value.to_string());
+        if let Some(value) = &result {
+            cache().insert(storage_key.to_string(), value.clone());
+        }
+        Ok(result)
+    }
+
+    pub(super) fn remove_raw(storage_key: &str) -> Result<(), StorageError> {
+        cache().remove(storage_key);
+        backend().remove(storage_key)
+    }
+
+    /// Parses a value stored under the bare name of the key, before values were namespaced
+    /// and versioned. Unversioned values were often options, so `null` means there is none.
+    fn parse_unversioned<T>(
+        key: &StoreKey<T>,
+        stored: &str,
+    ) -> Result<Option<Value>, StorageError> {
+        match serde_json::from_str(stored) {
+            Ok(Value::Null) => Ok(None),
+            Ok(value) => Ok(Some(value)),
+            Err(err) => Err(StorageError::InvalidValue {
+                key: key.name.to_string(),
+                message: err.to_string(),
+            }),
         }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::Deserialize;
+
+    use super::*;
+    use crate::platform::storage::MemoryBackend;
+
+    /// Every test shares the backend, so each uses keys of its own
+    fn init() {
+        BACKEND.get_or_init(|| Box::new(MemoryBackend::default()));
+    }
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Credentials {
+        token: String,
+    }
+
+    const TOKEN: StoreKey<String> = StoreKey::new(Namespace::Secrets, "test_token");
+    const CREDENTIALS: StoreKey<Credentials> =
+        StoreKey::new(Namespace::Secrets, "test_credentials").with_version(2, |version, value| {
+            (version == 1).then(|| serde_json::json!({ "token": value }))
+        });
+
+    #[test]
+    fn stores_reads_and_removes_secrets() {
+        init();
+        assert_eq!(SecureStore::get(&TOKEN).unwrap(), None);
+
+        SecureStore::set(&TOKEN, &"secret".to_string()).unwrap();
+        assert_eq!(
+            SecureStore::get(&TOKEN).unwrap(),
+            Some("secret".to_string())
+        );
+        assert_eq!(
+            backend().retrieve("secrets.test_token").unwrap().as_deref(),
+            Some(r#"{"version":1,"value":"secret"}"#)
+        );
+        assert!(SecureStore::keys(Namespace::Secrets)
+            .unwrap()
+            .contains(&"test_token".to_string()));
+
+        SecureStore::remove(&TOKEN).unwrap();
+        assert_eq!(SecureStore::get(&TOKEN).unwrap(), None);
+        assert_eq!(backend().retrieve("secrets.test_token").unwrap(), None);
+    }
+
+    #[test]
+    fn migrates_values_of_older_versions() {
+        init();
+        backend()
+            .store("secrets.test_credentials", r#"{"version":1,"value":"old"}"#)
+            .unwrap();
+
+        let credentials = SecureStore::get(&CREDENTIALS).unwrap();
+        assert_eq!(
+            credentials,
+            Some(Credentials {
+                token: "old".to_string()
+            })
+        );
+        // Stored again at the current version
+        assert_eq!(
+            backend()
+                .retrieve("secrets.test_credentials")
+                .unwrap()
+                .as_deref(),
+            Some(r#"{"version":2,"value":{"token":"old"}}"#)
+        );
+    }
+
+    #[test]
+    fn fails_for_values_of_unknown_versions() {
+        init();
+        const KEY: StoreKey<String> = StoreKey::new(Namespace::Secrets, "test_future");
+        backend()
+            .store("secrets.test_future", r#"{"version":3,"value":"new"}"#)
+            .unwrap();
+
+        assert!(matches!(
+            SecureStore::get(&KEY),
+            Err(StorageError::Migration { version: 3, .. })
+        ));
+    }
+
+    #[test]
+    fn moves_unversioned_values_under_their_key() {
+        init();
+        const KEY: StoreKey<String> = StoreKey::new(Namespace::Secrets, "test_legacy");
+        backend().store("test_legacy", r#""legacy""#).unwrap();
+
+        assert_eq!(SecureStore::get(&KEY).unwrap(), Some("legacy".to_string()));
+        assert_eq!(backend().retrieve("test_legacy").unwrap(), None);
+        assert_eq!(
+            backend()
+                .retrieve("secrets.test_legacy")
+                .unwrap()
+                .as_deref(),
+            Some(r#"{"version":1,"value":"legacy"}"#)
+        );
+    }
+
+    #[test]
+    fn removes_unversioned_values_which_are_none() {
+        init();
+        const KEY: StoreKey<String> = StoreKey::new(Namespace::Secrets, "test_legacy_none");
+        backend().store("test_legacy_none", "null").unwrap();
+
+        assert_eq!(SecureStore::get(&KEY).unwrap(), None);
+        assert_eq!(backend().retrieve("test_legacy_none").unwrap(), None);
+    }
+
+    #[test]
+    fn keeps_unversioned_values_which_fail_to_migrate() {
+        init();
+        const KEY: StoreKey<String> = StoreKey::new(Namespace::Secrets, "test_legacy_invalid");
+        backend().store("test_legacy_invalid", "not json").unwrap();
+
+        assert!(matches!(
+            SecureStore::get(&KEY),
+            Err(StorageError::InvalidValue { .. })
+        ));
+        assert_eq!(
+            backend()
+                .retrieve("test_legacy_invalid")
+                .unwrap()
+                .as_deref(),
+            Some("not json")
+        );
+    }
+
+    #[test]
+    fn finds_values_stored_outside_of_the_secrets() {
+        init();
+        backend()
+            .store("preferences.test_theme", r#"{"version":1,"value":"dark"}"#)
+            .unwrap();
 
-        result.map(|value| {
-            let deserialized: T = serde_json::from_str(&value).unwrap();
-            deserialized
-        })
+        let values = SecureStore::non_secrets().unwrap();
+        assert!(values.contains(&(
+            "preferences.test_theme".to_string(),
+            r#"{"version":1,"value":"dark"}"#.to_string()
+        )));
+        assert!(values.iter().all(|(key, _)| !key.starts_with("secrets.")));
     }
 }
@@ -0,0 +1,312 @@
+//! "Up Next" playback queue for podcasts and other audio and video enclosures.
+//!
+//! The queue itself is a plain state machine in [queue]. The [Playback] handle applies changes
+//! to it and forwards the resulting [PlayerCommand]s to a hidden audio element in the webview.
+
+use dioxus::prelude::*;
+use futures_util::StreamExt;
+use serde::Deserialize;
+
+use crate::{
+    api::{
+        types::user::{CreateHistoryRequest, UpdateHistoryRequest},
+        ApiClient,
+    },
+    hooks::use_api,
+    offline::{use_downloads, Downloads},
+    platform::{Namespace, Preferences, StoreKey},
+    read_state::{use_read_store, ReadStore},
+};
+
+mod queue;
+
+pub use queue::{PlaybackQueue, PlaybackStatus, PlayerCommand, QueueEntry};
+
+const QUEUE: StoreKey<PlaybackQueue> = StoreKey::new(Namespace::Cache, "playback_queue");
+
+/// Seconds of playback between saving the progress
+const SAVE_INTERVAL: f64 = 15.;
+
+const PLAYER_JS: &str = r#"
+    const audio = new Audio();
+    audio.preload = "auto";
+
+    let lastSent = 0;
+    audio.addEventListener("timeupdate", () => {
+        const now = Date.now();
+        if (now - lastSent < 1000) return;
+        lastSent = now;
+        dioxus.send({
+            type: "Time",
+            position: audio.currentTime,
+            duration: isFinite(audio.duration) ? audio.duration : null,
+        });
+    });
+    audio.addEventListener("ended", () => dioxus.send({ type: "Ended" }));
+    audio.addEventListener("error", () => dioxus.send({
+        type: "Error",
+        message: audio.error ? audio.error.message : "Unknown error",
+    }));
+
+    while (true) {
+        const command = await dioxus.recv();
+        switch (command.type) {
+            case "Load":
+                audio.src = command.url;
+                audio.currentTime = command.position;
+                audio.playbackRate = command.speed;
+                if (command.progress !== null) {
+                    const progress = command.progress;
+                    audio.addEventListener("loadedmetadata", () => {
+                        if (isFinite(audio.duration)) audio.currentTime = progress * audio.duration;
+                    }, { once: true });
+                }
+                break;
+            case "Play":
+                audio.play().catch((err) => dioxus.send({ type: "Error", message: err.message }));
+                break;
+            case "Pause":
+                audio.pause();
+                break;
+            case "Seek":
+                audio.currentTime = command.position;
+                break;
+            case "SetSpeed":
+                audio.playbackRate = command.speed;
+                break;
+            case "Stop":
+                audio.pause();
+                audio.removeAttribute("src");
+                audio.load();
+                break;
+        }
+    }
+"#;
+
+/// Events reported by the audio element
+#[derive(Debug, Deserialize)]
+#[serde(tag = "type")]
+enum PlayerEvent {
+    Time {
+        position: f64,
+        duration: Option<f64>,
+    },
+    Ended,
+    Error {
+        message: String,
+    },
+}
+
+#[derive(Clone, Copy)]
+pub struct Playback {
+    api: &'static ApiClient,
+    read_store: ReadStore,
+    queue: Signal<PlaybackQueue>,
+    player: Coroutine<PlayerCommand>,
+}
+
+impl Playback {
+    pub fn queue(&self) -> ReadOnlySignal<PlaybackQueue> {
+        self.queue.into()
+    }
+
+    pub fn play(&mut self, entry: QueueEntry) {
+        self.apply(|queue| queue.play(entry));
+    }
+
+    pub fn enqueue(&mut self, entry: QueueEntry) {
+        self.apply(|queue| queue.enqueue(entry));
+    }
+
+    pub fn enqueue_next(&mut self, entry: QueueEntry) {
+        self.apply(|queue| queue.enqueue_next(entry));
+    }
+
+    pub fn remove(&mut self, item_id: i64) {
+        self.apply(|queue| queue.remove(item_id));
+    }
+
+    pub fn reorder(&mut self, from: usize, to: usize) {
+        self.apply(|queue| {
+            queue.reorder(from, to);
+            vec![]
+        });
+    }
+
+    pub fn toggle(&mut self) {
+        self.apply(PlaybackQueue::toggle);
+    }
+
+    pub fn seek(&mut self, position: f64) {
+        self.apply(|queue| queue.seek(position));
+    }
+
+    pub fn skip_forward(&mut self) {
+        self.apply(PlaybackQueue::skip_forward);
+    }
+
+    pub fn skip_backward(&mut self) {
+        self.apply(PlaybackQueue::skip_backward);
+    }
+
+    pub fn cycle_speed(&mut self) {
+        self.apply(PlaybackQueue::cycle_speed);
+    }
+
+    fn apply(&mut self, update: impl FnOnce(&mut PlaybackQueue) -> Vec<PlayerCommand>) {
+        let previous = self.queue.peek().current.clone();
+        let commands = update(&mut self.queue.write());
+
+        // Save where we left off when switching away from an entry or pausing
+        let switched = previous.as_ref().map(|entry| entry.item_id)
+            != self
+                .queue
+                .peek()
+                .current
+                .as_ref()
+                .map(|entry| entry.item_id);
+        if switched || commands.contains(&PlayerCommand::Pause) {
+            save_progress(self.api, self.read_store, self.queue, previous);
+        }
+
+        for command in commands {
+            self.player.send(command);
+        }
+        save_queue(&self.queue.peek());
+    }
+}
+
+/// Creates the playback queue and audio player. Must be called once, above every
+/// view which uses [use_playback], so that playback continues across navigation.
+pub fn use_playback_provider() -> Playback {
+    let api = use_api();
+    let read_store = use_read_store();
+    let downloads = use_downloads();
+    let mut queue = use_signal(|| {
+        Preferences::get(&QUEUE)
+            .unwrap_or_else(|err| {
+                tracing::error!("Failed to read playback queue: {err}");
+                None
+            })
+            .unwrap_or_default()
+    });
+
+    let player = use_coroutine(move |mut rx: UnboundedReceiver<PlayerCommand>| async move {
+        let mut eval = document::eval(PLAYER_JS);
+        let mut last_saved_at = queue.peek().position();
+
+        for command in queue.write().restore() {
+            let _ = eval.send(prefer_download(downloads, command));
+        }
+
+        loop {
+            tokio::select! {
+                Some(command) = rx.next() => {
+                    let _ = eval.send(prefer_download(downloads, command));
+                }
+                event = eval.recv::<PlayerEvent>() => match event {
+                    Ok(PlayerEvent::Time { position, duration }) => {
+                        queue.write().update_position(position, duration);
+                        if (position - last_saved_at).abs() >= SAVE_INTERVAL {
+                            last_saved_at = position;
+                            save_progress(api, read_store, queue, queue.peek().current.clone());
+                            save_queue(&queue.peek());
+                        }
+                    }
+                    Ok(PlayerEvent::Ended) => {
+                        let (finished, commands) = queue.write().finish();
+                        save_progress(api, read_store, queue, finished);
+                        for command in commands {
+                            let _ = eval.send(prefer_download(downloads, command));
+                        }
+                        save_queue(&queue.peek());
+                    }
+                    Ok(PlayerEvent::Error { message }) => {
+                        tracing::error!("Playback failed: {message}");
+                        queue.write().pause();
+                    }
+                    Err(err) => {
+                        tracing::error!("Audio player stopped: {err:?}");
+                        break;
+                    }
+                }
+            }
+        }
+    });
+
+    use_context_provider(|| Playback {
+        api,
+        read_store,
+        queue,
+        player,
+    })
+}
+
+pub fn use_playback() -> Playback {
+    use_context::<Playback>()
+}
+
+/// Loads downloaded enclosures from their files instead of streaming them
+fn prefer_download(downloads: Downloads, mut command: PlayerCommand) -> PlayerCommand {
+    if let PlayerCommand::Load { item_id, url, .. } = &mut command {
+        if let Some(local) = downloads.enclosure_url(*item_id) {
+            *url = local;
+        }
+    }
+    command
+}
+
+/// Saves the queue, so it is restored on the next launch
+fn save_queue(queue: &PlaybackQueue) {
+    if let Err(err) = Preferences::set(&QUEUE, queue) {
+        tracing::error!("Failed to save playback queue: {err}");
+    }
+}
+
+/// Saves the entry's progress to the user's history, creating the history item if needed
+fn save_progress(
+    api: &'static ApiClient,
+    mut read_store: ReadStore,
+    mut queue: Signal<PlaybackQueue>,
+    entry: Option<QueueEntry>,
+) {
+    let Some(entry) = entry else {
+        return;
+    };
+    let progress = entry.progress();
+
+    // The item may have a history item from before it was queued, e.g. from being opened
+    let history_id = entry.history_id.or_else(|| {
+        read_store
+            .mark(entry.item_id)
+            .and_then(|mark| mark.history_id)
+    });
+
+    spawn(async move {
+        let result = match history_id {
+            Some(id) => {
+                api.update_history(id, &UpdateHistoryRequest { progress })
+                    .await
+            }
+            None => {
+                api.create_history(&CreateHistoryRequest {
+                    item: entry.item_id,
+                    progress,
+                })
+                .await
+            }
+        };
+
+        match result {
+            Ok(history) => {
+                read_store.record(&history);
+                let queue = &mut *queue.write();
+                let entries = queue.current.iter_mut().chain(queue.up_next.iter_mut());
+                for queued in entries.filter(|queued| queued.item_id == entry.item_id) {
+                    queued.history_id = Some(history.id);
+                }
+            }
+            Err(err) => tracing::error!("Failed to save playback progress: {}", err.message()),
+        }
+    });
+}
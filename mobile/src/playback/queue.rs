@@ -0,0 +1,589 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    api::types::feed::{EnclosureKind, FeedItem},
+    read_state::ReadMark,
+};
+
+pub const PLAYBACK_SPEEDS: [f32; 6] = [0.75, 1., 1.25, 1.5, 1.75, 2.];
+
+/// A single enclosure in the playback queue
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct QueueEntry {
+    /// ID of the feed item the enclosure belongs to
+    pub item_id: i64,
+    pub title: String,
+    pub url: String,
+    pub mime_type: String,
+    /// Last known position in seconds
+    pub position: f64,
+    /// Duration in seconds, known once the media has been loaded
+    pub duration: Option<f64>,
+    /// History item used to save the progress on the server
+    pub history_id: Option<i64>,
+    /// Progress saved in the history, 0 - 1, which playback resumes from until the duration is
+    /// known
+    #[serde(default)]
+    pub saved_progress: Option<f64>,
+}
+
+impl QueueEntry {
+    /// Creates an entry for the item's enclosure, if it has a playable one. Playback resumes
+    /// from the progress in the item's history, if it has been played before.
+    ///
+    /// Only the sound of videos is played, which suits video podcasts.
+    pub fn from_item(item: &FeedItem, history: Option<ReadMark>) -> Option<Self> {
+        let enclosure = item.enclosure.as_ref()?;
+        if !matches!(
+            enclosure.kind(),
+            EnclosureKind::Audio | EnclosureKind::Video
+        ) {
+            return None;
+        }
+
+        Some(Self {
+            item_id: item.id,
+            title: item.title.clone(),
+            url: enclosure.url.clone(),
+            mime_type: enclosure.mime_type.clone(),
+            position: 0.,
+            duration: None,
+            history_id: history.and_then(|history| history.history_id),
+            saved_progress: history.map(|history| history.progress),
+        })
+    }
+
+    /// Progress through the entry, 0 - 1
+    pub fn progress(&self) -> f64 {
+        match self.duration {
+            Some(duration) if duration > 0. => (self.position / duration).clamp(0., 1.),
+            _ => self.saved_progress.unwrap_or(0.),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub enum PlaybackStatus {
+    #[default]
+    Stopped,
+    Playing,
+    Paused,
+}
+
+/// Commands for the audio output, produced by the [PlaybackQueue]
+#[derive(Clone, Debug, PartialEq, Serialize)]
+#[serde(tag = "type")]
+pub enum PlayerCommand {
+    Load {
+        item_id: i64,
+        url: String,
+        position: f64,
+        /// Progress to seek to once the duration is known, for entries resumed from the history
+        progress: Option<f64>,
+        speed: f32,
+    },
+    Play,
+    Pause,
+    Seek {
+        position: f64,
+    },
+    SetSpeed {
+        speed: f32,
+    },
+    Stop,
+}
+
+/// "Up Next" queue and playback state.
+///
+/// All changes go through methods which return the [PlayerCommand]s required to bring
+/// the audio output in line with the new state, so the queue can be driven without any audio.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct PlaybackQueue {
+    /// Entry currently loaded in the player
+    pub current: Option<QueueEntry>,
+    /// Entries to play after the current one, in order
+    pub up_next: Vec<QueueEntry>,
+    pub status: PlaybackStatus,
+    pub speed: f32,
+    /// Seconds to skip back by
+    pub skip_backward: f64,
+    /// Seconds to skip forward by
+    pub skip_forward: f64,
+}
+
+impl Default for PlaybackQueue {
+    fn default() -> Self {
+        Self {
+            current: None,
+            up_next: Vec::new(),
+            status: PlaybackStatus::Stopped,
+            speed: 1.,
+            skip_backward: 15.,
+            skip_forward: 30.,
+        }
+    }
+}
+
+impl PlaybackQueue {
+    pub fn contains(&self, item_id: i64) -> bool {
+        self.current
+            .as_ref()
+            .is_some_and(|entry| entry.item_id == item_id)
+            || self.up_next.iter().any(|entry| entry.item_id == item_id)
+    }
+
+    /// Commands required to restore the player after the queue was loaded from storage.
+    /// Playback is left paused.
+    pub fn restore(&mut self) -> Vec<PlayerCommand> {
+        match &self.current {
+            Some(current) => {
+                self.status = PlaybackStatus::Paused;
+                vec![self.load_command(current)]
+            }
+            None => {
+                self.status = PlaybackStatus::Stopped;
+                vec![]
+            }
+        }
+    }
+
+    /// Starts playing the entry immediately. The previous entry is kept at the front of the queue.
+    pub fn play(&mut self, entry: QueueEntry) -> Vec<PlayerCommand> {
+        if self
+            .current
+            .as_ref()
+            .is_some_and(|current| current.item_id == entry.item_id)
+        {
+            return self.resume();
+        }
+
+        let entry = self.take(entry.item_id).unwrap_or(entry);
+        if let Some(previous) = self.current.take() {
+            self.up_next.insert(0, previous);
+        }
+        self.start(entry)
+    }
+
+    /// Adds the entry to the end of the queue, starting playback if nothing is loaded
+    pub fn enqueue(&mut self, entry: QueueEntry) -> Vec<PlayerCommand> {
+        if self.contains(entry.item_id) {
+            return vec![];
+        }
+        if self.current.is_none() {
+            return self.start(entry);
+        }
+
+        self.up_next.push(entry);
+        vec![]
+    }
+
+    /// Adds the entry to the front of the queue, moving it if it was already queued
+    pub fn enqueue_next(&mut self, entry: QueueEntry) -> Vec<PlayerCommand> {
+        if self
+            .current
+            .as_ref()
+            .is_some_and(|current| current.item_id == entry.item_id)
+        {
+            return vec![];
+        }
+        if self.current.is_none() {
+            return self.start(entry);
+        }
+
+        let entry = self.take(entry.item_id).unwrap_or(entry);
+        self.up_next.insert(0, entry);
+        vec![]
+    }
+
+    /// Removes the entry from the queue, skipping to the next one if it was playing
+    pub fn remove(&mut self, item_id: i64) -> Vec<PlayerCommand> {
+        if self
+            .current
+            .as_ref()
+            .is_some_and(|current| current.item_id == item_id)
+        {
+            self.current = None;
+            return self.next();
+        }
+
+        self.take(item_id);
+        vec![]
+    }
+
+    /// Moves an entry in the "Up Next" list to a new index
+    pub fn reorder(&mut self, from: usize, to: usize) {
+        if from >= self.up_next.len() {
+            return;
+        }
+        let entry = self.up_next.remove(from);
+        self.up_next.insert(to.min(self.up_next.len()), entry);
+    }
+
+    pub fn resume(&mut self) -> Vec<PlayerCommand> {
+        match self.status {
+            PlaybackStatus::Paused => {
+                self.status = PlaybackStatus::Playing;
+                vec![PlayerCommand::Play]
+            }
+            PlaybackStatus::Stopped => self.next(),
+            PlaybackStatus::Playing => vec![],
+        }
+    }
+
+    pub fn pause(&mut self) -> Vec<PlayerCommand> {
+        if self.status != PlaybackStatus::Playing {
+            return vec![];
+        }
+        self.status = PlaybackStatus::Paused;
+        vec![PlayerCommand::Pause]
+    }
+
+    pub fn toggle(&mut self) -> Vec<PlayerCommand> {
+        match self.status {
+            PlaybackStatus::Playing => self.pause(),
+            _ => self.resume(),
+        }
+    }
+
+    /// Seeks the current entry to the position in seconds
+    pub fn seek(&mut self, position: f64) -> Vec<PlayerCommand> {
+        let Some(current) = &mut self.current else {
+            return vec![];
+        };
+
+        let max = current.duration.unwrap_or(f64::MAX);
+        current.position = position.clamp(0., max);
+        vec![PlayerCommand::Seek {
+            position: current.position,
+        }]
+    }
+
+    pub fn skip_forward(&mut self) -> Vec<PlayerCommand> {
+        let position = self.position() + self.skip_forward;
+        self.seek(position)
+    }
+
+    pub fn skip_backward(&mut self) -> Vec<PlayerCommand> {
+        let position = self.position() - self.skip_backward;
+        self.seek(position)
+    }
+
+    pub fn set_speed(&mut self, speed: f32) -> Vec<PlayerCommand> {
+        self.speed = speed.clamp(0.5, 3.);
+        vec![PlayerCommand::SetSpeed { speed: self.speed }]
+    }
+
+    /// Cycles through [PLAYBACK_SPEEDS]
+    pub fn cycle_speed(&mut self) -> Vec<PlayerCommand> {
+        let next = PLAYBACK_SPEEDS
+            .iter()
+            .copied()
+            .find(|speed| *speed > self.speed)
+            .unwrap_or(PLAYBACK_SPEEDS[0]);
+        self.set_speed(next)
+    }
+
+    /// Records the position reported by the player
+    pub fn update_position(&mut self, position: f64, duration: Option<f64>) {
+        if let Some(current) = &mut self.current {
+            current.position = position;
+            current.duration = duration.or(current.duration);
+        }
+    }
+
+    /// Called when the current entry finished playing. Returns the finished entry.
+    pub fn finish(&mut self) -> (Option<QueueEntry>, Vec<PlayerCommand>) {
+        let finished = self.current.take().map(|mut entry| {
+            entry.position = entry.duration.unwrap_or(entry.position);
+            entry
+        });
+        (finished, self.next())
+    }
+
+    pub fn position(&self) -> f64 {
+        self.current
+            .as_ref()
+            .map(|entry| entry.position)
+            .unwrap_or(0.)
+    }
+
+    fn next(&mut self) -> Vec<PlayerCommand> {
+        if self.up_next.is_empty() {
+            self.current = None;
+            self.status = PlaybackStatus::Stopped;
+            return vec![PlayerCommand::Stop];
+        }
+
+        let entry = self.up_next.remove(0);
+        self.start(entry)
+    }
+
+    fn start(&mut self, entry: QueueEntry) -> Vec<PlayerCommand> {
+        let load = self.load_command(&entry);
+        self.current = Some(entry);
+        self.status = PlaybackStatus::Playing;
+        vec![load, PlayerCommand::Play]
+    }
+
+    fn take(&mut self, item_id: i64) -> Option<QueueEntry> {
+        let index = self
+            .up_next
+            .iter()
+            .position(|entry| entry.item_id == item_id)?;
+        Some(self.up_next.remove(index))
+    }
+
+    fn load_command(&self, entry: &QueueEntry) -> PlayerCommand {
+        // Start from the beginning if the entry was previously finished
+        let finished = entry.progress() >= 0.99;
+        let position = if finished { 0. } else { entry.position };
+        let progress = entry
+            .saved_progress
+            .filter(|_| !finished && entry.duration.is_none() && entry.position == 0.);
+        PlayerCommand::Load {
+            item_id: entry.item_id,
+            url: entry.url.clone(),
+            position,
+            progress,
+            speed: self.speed,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+
+    use super::*;
+    use crate::api::types::feed::FeedItemEnclosure;
+
+    fn entry(item_id: i64) -> QueueEntry {
+        QueueEntry {
+            item_id,
+            title: format!("Episode {item_id}"),
+            url: format!("https://example.com/{item_id}.mp3"),
+            mime_type: "audio/mpeg".to_string(),
+            position: 0.,
+            duration: None,
+            history_id: None,
+            saved_progress: None,
+        }
+    }
+
+    fn item(mime_type: &str) -> FeedItem {
+        FeedItem {
+            id: 1,
+            guid: "1".to_string(),
+            feed_id: 1,
+            index_in_feed: 0,
+            title: "Episode".to_string(),
+            link: None,
+            description: None,
+            enclosure: Some(FeedItemEnclosure {
+                url: "https://example.com/1".to_string(),
+                length: 0,
+                mime_type: mime_type.to_string(),
+            }),
+            categories: vec![],
+            comments_link: None,
+            published_at: None,
+            content: None,
+            content_type: None,
+            base_link: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    fn ids(entries: &[QueueEntry]) -> Vec<i64> {
+        entries.iter().map(|entry| entry.item_id).collect()
+    }
+
+    fn load(item_id: i64, position: f64, progress: Option<f64>) -> PlayerCommand {
+        PlayerCommand::Load {
+            item_id,
+            url: format!("https://example.com/{item_id}.mp3"),
+            position,
+            progress,
+            speed: 1.,
+        }
+    }
+
+    #[test]
+    fn queues_audio_and_video() {
+        assert!(QueueEntry::from_item(&item("audio/mpeg"), None).is_some());
+        assert!(QueueEntry::from_item(&item("video/mp4"), None).is_some());
+        assert!(QueueEntry::from_item(&item("image/png"), None).is_none());
+    }
+
+    #[test]
+    fn resumes_from_history() {
+        let history = ReadMark {
+            history_id: Some(7),
+            progress: 0.5,
+        };
+        let mut entry = QueueEntry::from_item(&item("audio/mpeg"), Some(history)).unwrap();
+        assert_eq!(entry.history_id, Some(7));
+        assert_eq!(entry.progress(), 0.5);
+
+        entry.url = "https://example.com/1.mp3".to_string();
+        let mut queue = PlaybackQueue::default();
+        assert_eq!(
+            queue.play(entry),
+            [load(1, 0., Some(0.5)), PlayerCommand::Play]
+        );
+
+        // The position takes over once the duration is known
+        queue.update_position(30., Some(100.));
+        assert_eq!(queue.current.as_ref().unwrap().progress(), 0.3);
+    }
+
+    #[test]
+    fn restarts_finished_entries() {
+        let mut finished = entry(1);
+        finished.saved_progress = Some(1.);
+        let mut queue = PlaybackQueue::default();
+        assert_eq!(
+            queue.play(finished),
+            [load(1, 0., None), PlayerCommand::Play]
+        );
+    }
+
+    #[test]
+    fn enqueue_starts_playback_when_empty() {
+        let mut queue = PlaybackQueue::default();
+        assert_eq!(
+            queue.enqueue(entry(1)),
+            [load(1, 0., None), PlayerCommand::Play]
+        );
+        assert_eq!(queue.status, PlaybackStatus::Playing);
+
+        assert!(queue.enqueue(entry(2)).is_empty());
+        assert!(
+            queue.enqueue(entry(2)).is_empty(),
+            "Entries are only queued once"
+        );
+        assert_eq!(ids(&queue.up_next), [2]);
+    }
+
+    #[test]
+    fn play_keeps_previous_entry_next() {
+        let mut queue = PlaybackQueue::default();
+        queue.play(entry(1));
+        queue.enqueue(entry(2));
+        queue.enqueue(entry(3));
+
+        assert_eq!(
+            queue.play(entry(3)),
+            [load(3, 0., None), PlayerCommand::Play]
+        );
+        assert_eq!(queue.current.as_ref().map(|entry| entry.item_id), Some(3));
+        assert_eq!(ids(&queue.up_next), [1, 2]);
+    }
+
+    #[test]
+    fn enqueue_next_moves_entry_to_front() {
+        let mut queue = PlaybackQueue::default();
+        queue.play(entry(1));
+        queue.enqueue(entry(2));
+        queue.enqueue(entry(3));
+
+        assert!(queue.enqueue_next(entry(3)).is_empty());
+        assert_eq!(ids(&queue.up_next), [3, 2]);
+    }
+
+    #[test]
+    fn removing_current_entry_skips_to_next() {
+        let mut queue = PlaybackQueue::default();
+        queue.play(entry(1));
+        queue.enqueue(entry(2));
+
+        assert_eq!(queue.remove(1), [load(2, 0., None), PlayerCommand::Play]);
+        assert_eq!(queue.remove(2), [PlayerCommand::Stop]);
+        assert_eq!(queue.status, PlaybackStatus::Stopped);
+        assert!(queue.current.is_none());
+    }
+
+    #[test]
+    fn finish_returns_finished_entry() {
+        let mut queue = PlaybackQueue::default();
+        queue.play(entry(1));
+        queue.enqueue(entry(2));
+        queue.update_position(90., Some(100.));
+
+        let (finished, commands) = queue.finish();
+        let finished = finished.unwrap();
+        assert_eq!(finished.item_id, 1);
+        assert_eq!(finished.progress(), 1.);
+        assert_eq!(commands, [load(2, 0., None), PlayerCommand::Play]);
+    }
+
+    #[test]
+    fn toggle_pauses_and_resumes() {
+        let mut queue = PlaybackQueue::default();
+        assert_eq!(queue.toggle(), [PlayerCommand::Stop], "Nothing to play");
+
+        queue.play(entry(1));
+        assert_eq!(queue.toggle(), [PlayerCommand::Pause]);
+        assert_eq!(queue.status, PlaybackStatus::Paused);
+        assert_eq!(queue.toggle(), [PlayerCommand::Play]);
+        assert_eq!(queue.status, PlaybackStatus::Playing);
+    }
+
+    #[test]
+    fn restore_leaves_playback_paused() {
+        let mut queue = PlaybackQueue::default();
+        queue.play(entry(1));
+        queue.update_position(12., Some(100.));
+
+        assert_eq!(queue.restore(), [load(1, 12., None)]);
+        assert_eq!(queue.status, PlaybackStatus::Paused);
+    }
+
+    #[test]
+    fn seek_stays_within_entry() {
+        let mut queue = PlaybackQueue::default();
+        assert!(queue.seek(10.).is_empty());
+
+        queue.play(entry(1));
+        queue.update_position(10., Some(60.));
+        assert_eq!(
+            queue.skip_backward(),
+            [PlayerCommand::Seek { position: 0. }]
+        );
+        assert_eq!(queue.seek(90.), [PlayerCommand::Seek { position: 60. }]);
+        assert_eq!(
+            queue.skip_forward(),
+            [PlayerCommand::Seek { position: 60. }]
+        );
+    }
+
+    #[test]
+    fn reorder_moves_entry() {
+        let mut queue = PlaybackQueue::default();
+        queue.play(entry(1));
+        for item_id in 2..=4 {
+            queue.enqueue(entry(item_id));
+        }
+
+        queue.reorder(2, 0);
+        assert_eq!(ids(&queue.up_next), [4, 2, 3]);
+        queue.reorder(0, 10);
+        assert_eq!(ids(&queue.up_next), [2, 3, 4]);
+    }
+
+    #[test]
+    fn cycles_through_speeds() {
+        let mut queue = PlaybackQueue::default();
+        assert_eq!(
+            queue.cycle_speed(),
+            [PlayerCommand::SetSpeed { speed: 1.25 }]
+        );
+
+        queue.set_speed(2.);
+        assert_eq!(
+            queue.cycle_speed(),
+            [PlayerCommand::SetSpeed { speed: 0.75 }]
+        );
+    }
+}
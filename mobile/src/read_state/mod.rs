@@ -0,0 +1,309 @@
+//! Read state of feed items, shared by every screen so unread counts stay in sync.
+//!
+//! [ReadState] holds the state, the [ReadStore] handle applies changes to it optimistically
+//! and syncs them with the user's history on the server.
+
+use chrono::{DateTime, Utc};
+use dioxus::prelude::*;
+use futures_util::{stream, StreamExt};
+
+use crate::{
+    api::{
+        types::{
+            feed::FeedItem,
+            tag::UserTag,
+            user::{CreateHistoryRequest, HistoryItem},
+        },
+        ApiClient,
+    },
+    hooks::{use_api, use_persistent},
+    platform::{Namespace, StoreKey},
+    store::{use_store, QueryKey, Store},
+};
+
+mod state;
+
+pub use state::{ItemRef, ReadMark, ReadState};
+
+const MARK_ON_SCROLL: StoreKey<bool> = StoreKey::new(Namespace::Preferences, "mark_read_on_scroll");
+
+/// Page size of `GET /user/history`
+const HISTORY_PAGE_SIZE: usize = 20;
+
+/// History items created or deleted at once when marking many items. The API has no batch
+/// endpoint, so this keeps bulk marks from taking a round trip per item.
+const HISTORY_REQUESTS: usize = 8;
+
+/// Reports list items which were scrolled past, i.e. left the top of the viewport
+const SCROLL_OBSERVER_JS: &str = r#"
+    const reported = new Set();
+    const observer = new IntersectionObserver((entries) => {
+        for (const entry of entries) {
+            const top = entry.rootBounds ? entry.rootBounds.top : 0;
+            if (entry.isIntersecting || entry.boundingClientRect.bottom > top) continue;
+
+            const id = entry.target.dataset.itemId;
+            observer.unobserve(entry.target);
+            if (reported.has(id)) continue;
+            reported.add(id);
+            dioxus.send(Number(id));
+        }
+    });
+
+    const observe = () => {
+        document.querySelectorAll("[data-item-id]").forEach((element) => observer.observe(element));
+    };
+    observe();
+    new MutationObserver(observe).observe(document.body, { childList: true, subtree: true });
+    await new Promise(() => {});
+"#;
+
+#[derive(Clone, Copy)]
+pub struct ReadStore {
+    api: &'static ApiClient,
+    store: Store,
+    state: Signal<ReadState>,
+    mark_on_scroll: Signal<bool>,
+}
+
+impl ReadStore {
+    pub fn is_read(&self, item_id: i64) -> bool {
+        self.state.read().is_read(item_id)
+    }
+
+    /// The user's history item for the feed item, if it has been read
+    pub fn mark(&self, item_id: i64) -> Option<ReadMark> {
+        self.state.peek().mark(item_id)
+    }
+
+    /// Number of unread items in the feed, `None` if its items haven't been loaded
+    pub fn feed_unread(&self, feed_id: i32) -> Option<usize> {
+        self.state.read().feed_unread(feed_id)
+    }
+
+    /// Number of unread items across the index's feeds, `None` if they haven't been found
+    pub fn index_unread(&self, index_id: i32) -> Option<usize> {
+        self.state.read().index_unread(index_id)
+    }
+
+    pub fn tag_unread(&self, tag: &UserTag) -> usize {
+        self.state.read().tag_unread(tag)
+    }
+
+    /// Whether items are marked as read when they are scrolled past in a list
+    pub fn mark_on_scroll(&self) -> bool {
+        *self.mark_on_scroll.read()
+    }
+
+    pub fn set_mark_on_scroll(&mut self, enabled: bool) {
+        self.mark_on_scroll.set(enabled);
+    }
+
+    /// Adds items of the feed loaded by a view, so new ones are counted after refreshing
+    pub fn add_feed_items(&mut self, feed_id: i32, items: &[FeedItem]) {
+        self.state
+            .write()
+            .add_feed_items(feed_id, items.iter().map(ItemRef::from));
+    }
+
+    /// Loads every item of the feeds which haven't been loaded yet
+    pub async fn load_feeds(&mut self, feed_ids: impl IntoIterator<Item = i32>) {
+        for feed_id in feed_ids {
+            if self.state.peek().is_feed_loaded(feed_id) {
+                continue;
+            }
+            match self.store.feed_items_since(feed_id, None).await {
+                Ok(items) => self
+                    .state
+                    .write()
+                    .set_feed_items(feed_id, items.iter().map(ItemRef::from)),
+                Err(err) => tracing::error!("Failed to load items of feed {feed_id}: {err}"),
+            }
+        }
+    }
+
+    /// Finds the feeds of the indexes which haven't been found yet, then loads their items
+    pub async fn load_indexes(&mut self, index_ids: impl IntoIterator<Item = i32>) {
+        let mut feed_ids = Vec::new();
+        for index_id in index_ids {
+            if self.state.peek().is_index_loaded(index_id) {
+                continue;
+            }
+            match self.store.index_feeds(index_id).await {
+                Ok(feeds) => {
+                    let ids: Vec<i32> = feeds.iter().map(|feed| feed.id).collect();
+                    self.state.write().set_index_feeds(index_id, ids.clone());
+                    feed_ids.extend(ids);
+                }
+                Err(err) => tracing::error!("Failed to load feeds of index {index_id}: {err}"),
+            }
+        }
+        self.load_feeds(feed_ids).await;
+    }
+
+    /// Loads the feeds and indexes in the tags, for their unread counts
+    pub async fn load_tags(&mut self, tags: &[UserTag]) {
+        self.load_feeds(tags.iter().flat_map(ReadState::tag_feeds))
+            .await;
+        self.load_indexes(tags.iter().flat_map(ReadState::tag_indexes))
+            .await;
+    }
+
+    /// Records a history item created elsewhere, e.g. by playback
+    pub fn record(&mut self, history: &HistoryItem) {
+        self.state.write().record(history);
+    }
+
+    /// Marks the items as read. Returns the items which weren't read before, for undoing.
+    pub fn mark_read(&mut self, item_ids: impl IntoIterator<Item = i64>) -> Vec<i64> {
+        let marked: Vec<i64> = {
+            let mut state = self.state.write();
+            item_ids
+                .into_iter()
+                .filter(|item_id| state.mark_pending(*item_id))
+                .collect()
+        };
+        if marked.is_empty() {
+            return marked;
+        }
+
+        let api = self.api;
+        let mut state = self.state;
+        let pending = marked.clone();
+        spawn(async move {
+            stream::iter(pending)
+                .for_each_concurrent(HISTORY_REQUESTS, |item_id| async move {
+                    let request = CreateHistoryRequest {
+                        item: item_id,
+                        progress: 1.,
+                    };
+                    match api.create_history(&request).await {
+                        // Marked as unread while the history item was being created
+                        Ok(history) if !state.peek().is_read(item_id) => {
+                            if let Err(err) = api.delete_history(history.id).await {
+                                tracing::error!("Failed to mark item as unread: {}", err.message());
+                            }
+                        }
+                        Ok(history) => state.write().record(&history),
+                        Err(err) => {
+                            tracing::error!("Failed to mark item as read: {}", err.message());
+                            state.write().forget(item_id);
+                        }
+                    }
+                })
+                .await;
+        });
+
+        marked
+    }
+
+    pub fn mark_unread(&mut self, item_ids: impl IntoIterator<Item = i64>) {
+        let history_ids: Vec<i64> = {
+            let mut state = self.state.write();
+            item_ids
+                .into_iter()
+                .filter_map(|item_id| state.forget(item_id))
+                .filter_map(|mark| mark.history_id)
+                .collect()
+        };
+        if history_ids.is_empty() {
+            return;
+        }
+
+        let api = self.api;
+        spawn(async move {
+            stream::iter(history_ids)
+                .for_each_concurrent(HISTORY_REQUESTS, |history_id| async move {
+                    if let Err(err) = api.delete_history(history_id).await {
+                        tracing::error!("Failed to mark item as unread: {}", err.message());
+                    }
+                })
+                .await;
+        });
+    }
+
+    /// Marks the feed's items as read, optionally only those published before `before`.
+    /// Returns the items which were marked.
+    pub fn mark_feed_read(&mut self, feed_id: i32, before: Option<DateTime<Utc>>) -> Vec<i64> {
+        let items = self.state.peek().unread_items(feed_id, before);
+        self.mark_read(items)
+    }
+
+    /// Marks the items of every feed in the tag, and matching its indexes, as read. Returns the
+    /// items which were marked.
+    pub fn mark_tag_read(&mut self, tag: &UserTag, before: Option<DateTime<Utc>>) -> Vec<i64> {
+        let items: Vec<i64> = {
+            let state = self.state.peek();
+            state
+                .all_tag_feeds(tag)
+                .into_iter()
+                .flat_map(|feed_id| state.unread_items(feed_id, before))
+                .collect()
+        };
+        self.mark_read(items)
+    }
+}
+
+/// Creates the read state store, loading the user's history whenever they log in.
+/// Must be called once, above every view which uses [use_read_store].
+pub fn use_read_store_provider() -> ReadStore {
+    let api = use_api();
+    let store = use_store();
+    let token = store.token();
+    let mut state = use_signal(ReadState::default);
+    let mark_on_scroll = use_persistent(MARK_ON_SCROLL, || false);
+
+    let read_store = use_context_provider(|| ReadStore {
+        api,
+        store,
+        state,
+        mark_on_scroll,
+    });
+
+    use_effect(move || {
+        state.set(ReadState::default());
+        if token.read().is_none() {
+            return;
+        }
+
+        spawn(async move {
+            let mut page = 0;
+            loop {
+                match store.load(QueryKey::History { page }).await {
+                    Ok(ids) => {
+                        let entities = store.entities();
+                        let entities = entities.peek();
+                        let mut state = state.write();
+                        for item in ids.iter().filter_map(|id| entities.history.get(id)) {
+                            state.record(item);
+                        }
+                        if ids.len() < HISTORY_PAGE_SIZE {
+                            break;
+                        }
+                        page += 1;
+                    }
+                    Err(err) => {
+                        tracing::error!("Failed to load history: {err}");
+                        break;
+                    }
+                }
+            }
+        });
+    });
+
+    use_future(move || async move {
+        let mut eval = document::eval(SCROLL_OBSERVER_JS);
+        let mut read_store = read_store;
+        while let Ok(item_id) = eval.recv::<i64>().await {
+            if read_store.mark_on_scroll() {
+                read_store.mark_read([item_id]);
+            }
+        }
+    });
+
+    read_store
+}
+
+pub fn use_read_store() -> ReadStore {
+    use_context::<ReadStore>()
+}
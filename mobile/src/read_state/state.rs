@@ -0,0 +1,282 @@
+use std::collections::{BTreeSet, HashMap, HashSet};
+
+use chrono::{DateTime, Utc};
+
+use crate::api::types::{
+    feed::FeedItem,
+    tag::{TagChildType, UserTag},
+    user::HistoryItem,
+};
+
+/// History item of a read feed item
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ReadMark {
+    /// ID of the history item, `None` while it is being created
+    pub history_id: Option<i64>,
+    pub progress: f64,
+}
+
+/// Feed item as needed for counting unread items
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ItemRef {
+    pub id: i64,
+    pub published_at: Option<DateTime<Utc>>,
+}
+
+impl From<&FeedItem> for ItemRef {
+    fn from(item: &FeedItem) -> Self {
+        Self {
+            id: item.id,
+            published_at: item.published_at,
+        }
+    }
+}
+
+/// Which feed items have been read.
+///
+/// An item is read once the user has a history item for it, either from opening it, playing
+/// its enclosure or marking it as read. Marking it as unread deletes the history item.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ReadState {
+    marks: HashMap<i64, ReadMark>,
+    /// Items of each feed, as far as they've been loaded
+    feed_items: HashMap<i32, Vec<ItemRef>>,
+    /// Feeds whose items have all been loaded, so their unread counts are complete
+    loaded_feeds: HashSet<i32>,
+    /// Feeds matching each index's search
+    index_feeds: HashMap<i32, Vec<i32>>,
+}
+
+impl ReadState {
+    pub fn is_read(&self, item_id: i64) -> bool {
+        self.marks.contains_key(&item_id)
+    }
+
+    pub fn mark(&self, item_id: i64) -> Option<ReadMark> {
+        self.marks.get(&item_id).copied()
+    }
+
+    pub fn record(&mut self, history: &HistoryItem) {
+        self.marks.insert(
+            history.item,
+            ReadMark {
+                history_id: Some(history.id),
+                progress: history.progress,
+            },
+        );
+    }
+
+    /// Marks the item as read until its history item is created.
+    /// Returns false if it was already read.
+    pub fn mark_pending(&mut self, item_id: i64) -> bool {
+        if self.is_read(item_id) {
+            return false;
+        }
+
+        self.marks.insert(
+            item_id,
+            ReadMark {
+                history_id: None,
+                progress: 1.,
+            },
+        );
+        true
+    }
+
+    pub fn forget(&mut self, item_id: i64) -> Option<ReadMark> {
+        self.marks.remove(&item_id)
+    }
+
+    pub fn is_feed_loaded(&self, feed_id: i32) -> bool {
+        self.loaded_feeds.contains(&feed_id)
+    }
+
+    /// Sets every item of the feed
+    pub fn set_feed_items(&mut self, feed_id: i32, items: impl IntoIterator<Item = ItemRef>) {
+        self.feed_items.insert(feed_id, items.into_iter().collect());
+        self.loaded_feeds.insert(feed_id);
+    }
+
+    /// Adds items of the feed loaded elsewhere, e.g. a page fetched after refreshing
+    pub fn add_feed_items(&mut self, feed_id: i32, items: impl IntoIterator<Item = ItemRef>) {
+        let known = self.feed_items.entry(feed_id).or_default();
+        for item in items {
+            match known.iter_mut().find(|known| known.id == item.id) {
+                Some(known) => *known = item,
+                None => known.push(item),
+            }
+        }
+    }
+
+    pub fn is_index_loaded(&self, index_id: i32) -> bool {
+        self.index_feeds.contains_key(&index_id)
+    }
+
+    pub fn set_index_feeds(&mut self, index_id: i32, feed_ids: impl IntoIterator<Item = i32>) {
+        self.index_feeds
+            .insert(index_id, feed_ids.into_iter().collect());
+    }
+
+    /// Unread items of the feed, optionally only those published before `before`
+    pub fn unread_items(&self, feed_id: i32, before: Option<DateTime<Utc>>) -> Vec<i64> {
+        self.feed_items
+            .get(&feed_id)
+            .into_iter()
+            .flatten()
+            .filter(|item| !self.is_read(item.id))
+            .filter(|item| match (before, item.published_at) {
+                (Some(before), Some(published_at)) => published_at < before,
+                (Some(_), None) => false,
+                (None, _) => true,
+            })
+            .map(|item| item.id)
+            .collect()
+    }
+
+    /// Number of unread items in the feed, `None` if its items haven't been loaded
+    pub fn feed_unread(&self, feed_id: i32) -> Option<usize> {
+        self.is_feed_loaded(feed_id)
+            .then(|| self.unread_items(feed_id, None).len())
+    }
+
+    /// Number of unread items across the index's feeds which have been loaded, `None` if
+    /// its feeds haven't been found yet
+    pub fn index_unread(&self, index_id: i32) -> Option<usize> {
+        let feed_ids = self.index_feeds.get(&index_id)?;
+        Some(
+            feed_ids
+                .iter()
+                .filter_map(|feed_id| self.feed_unread(*feed_id))
+                .sum(),
+        )
+    }
+
+    /// Feeds in the tag. Indexes are skipped since their items depend on a search, see
+    /// [Self::tag_indexes].
+    pub fn tag_feeds(tag: &UserTag) -> impl Iterator<Item = i32> + '_ {
+        tag.children
+            .iter()
+            .filter(|child| child.type_ == TagChildType::Feed)
+            .map(|child| child.id)
+    }
+
+    pub fn tag_indexes(tag: &UserTag) -> impl Iterator<Item = i32> + '_ {
+        tag.children
+            .iter()
+            .filter(|child| child.type_ == TagChildType::Index)
+            .map(|child| child.id)
+    }
+
+    /// Feeds in the tag, including those matching its indexes which have been loaded. A feed
+    /// in several of them is only counted once.
+    pub fn all_tag_feeds(&self, tag: &UserTag) -> BTreeSet<i32> {
+        let index_feeds = Self::tag_indexes(tag)
+            .filter_map(|index_id| self.index_feeds.get(&index_id))
+            .flatten()
+            .copied();
+        Self::tag_feeds(tag).chain(index_feeds).collect()
+    }
+
+    /// Number of unread items across the tag's feeds and indexes which have been loaded
+    pub fn tag_unread(&self, tag: &UserTag) -> usize {
+        self.all_tag_feeds(tag)
+            .into_iter()
+            .filter_map(|feed_id| self.feed_unread(feed_id))
+            .sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+    use crate::api::types::tag::TagChild;
+
+    fn now() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2025, 6, 1, 12, 0, 0).unwrap()
+    }
+
+    fn items(ids: std::ops::Range<i64>) -> impl Iterator<Item = ItemRef> {
+        ids.map(|id| ItemRef {
+            id,
+            published_at: Some(now() - chrono::Duration::days(id)),
+        })
+    }
+
+    fn history(id: i64, item: i64) -> HistoryItem {
+        HistoryItem {
+            id,
+            owner: 1,
+            item,
+            progress: 1.,
+            created_at: now(),
+            updated_at: now(),
+        }
+    }
+
+    fn tag(children: &[(TagChildType, i32)]) -> UserTag {
+        UserTag {
+            id: 1,
+            owner: 1,
+            title: "Tag".to_string(),
+            children: children
+                .iter()
+                .map(|(type_, id)| TagChild {
+                    type_: type_.clone(),
+                    id: *id,
+                })
+                .collect(),
+            created_at: now(),
+            updated_at: now(),
+        }
+    }
+
+    #[test]
+    fn counts_every_item_of_the_feed() {
+        let mut state = ReadState::default();
+        state.set_feed_items(1, items(0..45));
+        state.record(&history(1, 0));
+        state.record(&history(2, 44));
+
+        assert_eq!(state.feed_unread(1), Some(43));
+        assert_eq!(
+            state
+                .unread_items(1, Some(now() - chrono::Duration::days(30)))
+                .len(),
+            13
+        );
+    }
+
+    #[test]
+    fn counts_feeds_only_once_loaded() {
+        let mut state = ReadState::default();
+        state.add_feed_items(1, items(0..20));
+        assert_eq!(state.feed_unread(1), None);
+
+        state.set_feed_items(1, items(0..30));
+        state.add_feed_items(1, items(25..32));
+        assert_eq!(state.feed_unread(1), Some(32));
+    }
+
+    #[test]
+    fn counts_items_matching_indexes() {
+        let mut state = ReadState::default();
+        state.set_feed_items(1, items(0..3));
+        state.set_feed_items(2, items(10..15));
+        state.set_feed_items(3, items(20..27));
+        state.set_index_feeds(5, [2, 3]);
+
+        assert_eq!(state.index_unread(5), Some(12));
+        assert_eq!(state.index_unread(6), None);
+
+        // Feed 2 is in the tag directly and through the index
+        let tag = tag(&[
+            (TagChildType::Feed, 1),
+            (TagChildType::Feed, 2),
+            (TagChildType::Index, 5),
+        ]);
+        assert_eq!(state.all_tag_feeds(&tag), BTreeSet::from([1, 2, 3]));
+        assert_eq!(state.tag_unread(&tag), 15);
+    }
+}
@@ -0,0 +1,194 @@
+//! Settings the user chooses on the settings screen.
+//!
+//! Appearance belongs to the device, so every account looks the same on it. Reader defaults,
+//! notifications and sync belong to the active account.
+
+use dioxus::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    hooks::use_persistent,
+    i18n::{t, use_language, Language},
+    platform::{Namespace, StoreKey},
+    views::Route,
+};
+
+const THEME: StoreKey<Theme> = StoreKey::new(Namespace::Preferences, "theme");
+const TEXT_SIZE: StoreKey<TextSize> = StoreKey::new(Namespace::Preferences, "text_size");
+const OPEN_ITEMS_IN: StoreKey<ReaderMode> = StoreKey::new(Namespace::Account, "open_items_in");
+const SWIPE_LEFT: StoreKey<ItemAction> = StoreKey::new(Namespace::Preferences, "swipe_left");
+const SWIPE_RIGHT: StoreKey<ItemAction> = StoreKey::new(Namespace::Preferences, "swipe_right");
+const NOTIFICATIONS: StoreKey<bool> = StoreKey::new(Namespace::Account, "notifications");
+const AUTO_SYNC: StoreKey<bool> = StoreKey::new(Namespace::Account, "auto_sync");
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub enum Theme {
+    /// Follows the theme of the system
+    #[default]
+    System,
+    Light,
+    Dark,
+}
+
+impl Theme {
+    pub const ALL: [Theme; 3] = [Theme::System, Theme::Light, Theme::Dark];
+
+    pub fn label(self) -> String {
+        match self {
+            Theme::System => t!("theme-system"),
+            Theme::Light => t!("theme-light"),
+            Theme::Dark => t!("theme-dark"),
+        }
+    }
+
+    /// Value of the `data-theme` attribute the theme's colors are selected with in `theme.css`
+    fn attribute(self) -> &'static str {
+        match self {
+            Theme::System => "system",
+            Theme::Light => "light",
+            Theme::Dark => "dark",
+        }
+    }
+}
+
+/// Size of the text of articles in the reader
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub enum TextSize {
+    Small,
+    #[default]
+    Medium,
+    Large,
+}
+
+impl TextSize {
+    pub const ALL: [TextSize; 3] = [TextSize::Small, TextSize::Medium, TextSize::Large];
+
+    pub fn label(self) -> String {
+        match self {
+            TextSize::Small => t!("text-size-small"),
+            TextSize::Medium => t!("text-size-medium"),
+            TextSize::Large => t!("text-size-large"),
+        }
+    }
+
+    pub fn css(self) -> &'static str {
+        match self {
+            TextSize::Small => "14px",
+            TextSize::Medium => "16px",
+            TextSize::Large => "19px",
+        }
+    }
+}
+
+/// How items are shown when they're opened
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub enum ReaderMode {
+    /// The content of the item from its feed
+    #[default]
+    Feed,
+    /// The page the item links to
+    Page,
+}
+
+impl ReaderMode {
+    pub const ALL: [ReaderMode; 2] = [ReaderMode::Feed, ReaderMode::Page];
+
+    pub fn label(self) -> String {
+        match self {
+            ReaderMode::Feed => t!("reader-mode-feed"),
+            ReaderMode::Page => t!("reader-mode-page"),
+        }
+    }
+
+    /// Route of the reader showing the item this way
+    pub fn route(self, id: i64) -> Route {
+        match self {
+            ReaderMode::Feed => Route::FeedReader { id },
+            ReaderMode::Page => Route::ParsedReader { id },
+        }
+    }
+}
+
+/// What swiping an item in a list does
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub enum ItemAction {
+    /// Marks the item as read, or as unread when it was read
+    ToggleRead,
+    SaveToList,
+    Share,
+    /// Removes the item from the list it's shown in. Not offered outside of lists.
+    Archive,
+    Nothing,
+}
+
+impl ItemAction {
+    pub const ALL: [ItemAction; 5] = [
+        ItemAction::ToggleRead,
+        ItemAction::SaveToList,
+        ItemAction::Share,
+        ItemAction::Archive,
+        ItemAction::Nothing,
+    ];
+
+    pub fn label(self) -> String {
+        match self {
+            ItemAction::ToggleRead => t!("item-action-toggle-read"),
+            ItemAction::SaveToList => t!("item-save-to-list"),
+            ItemAction::Share => t!("item-share"),
+            ItemAction::Archive => t!("item-archive"),
+            ItemAction::Nothing => t!("item-action-nothing"),
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+pub struct Settings {
+    pub theme: Signal<Theme>,
+    /// Language of the app, which belongs to the device like the appearance
+    pub language: Signal<Language>,
+    pub text_size: Signal<TextSize>,
+    pub open_items_in: Signal<ReaderMode>,
+    /// What swiping an item to the left does
+    pub swipe_left: Signal<ItemAction>,
+    /// What swiping an item to the right does
+    pub swipe_right: Signal<ItemAction>,
+    /// Notify about new items in the user's feeds
+    pub notifications: Signal<bool>,
+    /// Keep downloads of lists with auto-download rules up to date in the background
+    pub auto_sync: Signal<bool>,
+}
+
+/// Loads the settings and applies the appearance to the document. Must be called once, below
+/// where the active account is made active and above every view which uses [use_settings].
+pub fn use_settings_provider() -> Settings {
+    let theme = use_persistent(THEME, Theme::default);
+    let language = use_language();
+    let text_size = use_persistent(TEXT_SIZE, TextSize::default);
+    let open_items_in = use_persistent(OPEN_ITEMS_IN, ReaderMode::default);
+    let swipe_left = use_persistent(SWIPE_LEFT, || ItemAction::Archive);
+    let swipe_right = use_persistent(SWIPE_RIGHT, || ItemAction::ToggleRead);
+    let notifications = use_persistent(NOTIFICATIONS, || true);
+    let auto_sync = use_persistent(AUTO_SYNC, || true);
+
+    use_effect(move || {
+        let theme = theme().attribute();
+        document::eval(&format!(
+            "document.documentElement.dataset.theme = '{theme}';"
+        ));
+    });
+
+    use_context_provider(|| Settings {
+        theme,
+        language,
+        text_size,
+        open_items_in,
+        swipe_left,
+        swipe_right,
+        notifications,
+        auto_sync,
+    })
+}
+
+pub fn use_settings() -> Settings {
+    use_context::<Settings>()
+}
@@ -0,0 +1,187 @@
+//! Accounts the user is logged in to, which can be on different servers.
+//!
+//! The accounts are kept in the [Preferences], and their tokens in the [SecureStore]. Every
+//! account has its own partition of the storage, made active along with it, and its own directory
+//! for files like downloads.
+
+use std::{collections::HashMap, path::PathBuf};
+
+use reqwest::Url;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+use super::auth::decode_token;
+use crate::platform::{
+    data_dir, set_active_account, Namespace, Preferences, SecureStore, StoreKey,
+};
+
+/// Server of the accounts which weren't added with another one
+pub const DEFAULT_SERVER: &str = "https://api.bind.sh";
+
+const ACCOUNTS: StoreKey<Vec<Account>> = StoreKey::new(Namespace::Preferences, "accounts");
+const ACTIVE_ACCOUNT: StoreKey<String> = StoreKey::new(Namespace::Preferences, "active_account");
+/// Tokens of the accounts, by id
+const TOKENS: StoreKey<HashMap<String, String>> = StoreKey::new(Namespace::Secrets, "tokens");
+/// Token of the only account, from before there could be several
+const LEGACY_TOKEN: StoreKey<String> = StoreKey::new(Namespace::Secrets, "token");
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Account {
+    /// Identifies the account among the others, from the user's id and the server
+    pub id: String,
+    /// Base URL of the API the account is on
+    pub server: String,
+    pub email: String,
+    pub username: String,
+}
+
+impl Account {
+    /// The account the token was issued for by the server
+    pub fn from_token(server: &str, token: &str) -> Option<Self> {
+        let user = decode_token(token)?;
+        let url = Url::parse(server).ok()?;
+        let host = match url.port() {
+            Some(port) => format!("{}-{port}", url.host_str()?),
+            None => url.host_str()?.to_string(),
+        };
+
+        Some(Self {
+            id: format!("{}@{host}", user.id),
+            server: server.trim_end_matches('/').to_string(),
+            email: user.email,
+            username: user.username,
+        })
+    }
+
+    /// Whether the account is on a server other than Bind's, e.g. a self-hosted one
+    pub fn is_self_hosted(&self) -> bool {
+        self.server != DEFAULT_SERVER
+    }
+}
+
+/// Directory for the files of the account, or for the files used while logged out
+pub fn files_dir(account: Option<&Account>) -> PathBuf {
+    data_dir()
+        .join("accounts")
+        .join(account.map_or("-", |account| account.id.as_str()))
+}
+
+/// Accounts the user is logged in to, in the order they were added
+pub fn load() -> Vec<Account> {
+    read(Preferences::get(&ACCOUNTS)).unwrap_or_default()
+}
+
+/// Makes the account which was active when the app was closed active again, returning it along
+/// with its token
+pub fn restore() -> Option<(Account, String)> {
+    migrate_legacy_token();
+
+    let id = read(Preferences::get(&ACTIVE_ACCOUNT))?;
+    let account = load().into_iter().find(|account| account.id == id)?;
+    let token = token(&id)?;
+    set_active_account(Some(&account.id));
+    Some((account, token))
+}
+
+pub fn token(id: &str) -> Option<String> {
+    tokens().remove(id)
+}
+
+/// Saves the account along with its token, replacing it if it was added before
+pub fn save(account: &Account, token: &str) {
+    let mut tokens = tokens();
+    tokens.insert(account.id.clone(), token.to_string());
+    if let Err(err) = SecureStore::set(&TOKENS, &tokens) {
+        tracing::error!("Failed to save token: {err}");
+    }
+
+    let mut accounts = load();
+    match accounts.iter_mut().find(|other| other.id == account.id) {
+        Some(existing) => *existing = account.clone(),
+        None => accounts.push(account.clone()),
+    }
+    write(&ACCOUNTS, &accounts);
+}
+
+/// Makes the account active, or none for `None`, which is the one the storage is read for
+pub fn activate(account: Option<&Account>) {
+    set_active_account(account.map(|account| account.id.as_str()));
+    let saved = match account {
+        Some(account) => Preferences::set(&ACTIVE_ACCOUNT, &account.id),
+        None => Preferences::remove(&ACTIVE_ACCOUNT),
+    };
+    if let Err(err) = saved {
+        tracing::error!("Failed to save the active account: {err}");
+    }
+}
+
+/// Forgets the token of the account, which the server no longer accepts, keeping the account and
+/// its data for when the user logs in to it again
+pub fn remove_token(account: &Account) {
+    let mut tokens = tokens();
+    tokens.remove(&account.id);
+    if let Err(err) = SecureStore::set(&TOKENS, &tokens) {
+        tracing::error!("Failed to remove token: {err}");
+    }
+}
+
+/// Forgets the account, removing its token, settings, caches and files
+pub fn remove(account: &Account) {
+    remove_token(account);
+
+    let mut accounts = load();
+    accounts.retain(|other| other.id != account.id);
+    write(&ACCOUNTS, &accounts);
+
+    Preferences::clear_account(&account.id);
+    let dir = files_dir(Some(account));
+    if dir.exists() {
+        if let Err(err) = std::fs::remove_dir_all(&dir) {
+            tracing::error!("Failed to remove the files of {}: {err}", account.id);
+        }
+    }
+}
+
+fn tokens() -> HashMap<String, String> {
+    read(SecureStore::get(&TOKENS)).unwrap_or_default()
+}
+
+/// Turns the token from before there could be several accounts into an account on the default
+/// server, moving its downloads into the account's directory
+fn migrate_legacy_token() {
+    let Some(token) = read(SecureStore::get(&LEGACY_TOKEN)) else {
+        return;
+    };
+    if let Err(err) = SecureStore::remove(&LEGACY_TOKEN) {
+        tracing::error!("Failed to remove legacy token: {err}");
+    }
+    let Some(account) = Account::from_token(DEFAULT_SERVER, &token) else {
+        tracing::warn!("Dropping legacy token, which has no account in it");
+        return;
+    };
+
+    save(&account, &token);
+    activate(Some(&account));
+
+    let downloads = data_dir().join("downloads");
+    if downloads.exists() {
+        let dir = files_dir(Some(&account));
+        let moved = std::fs::create_dir_all(&dir)
+            .and_then(|()| std::fs::rename(&downloads, dir.join("downloads")));
+        if let Err(err) = moved {
+            tracing::error!("Failed to move downloads to {}: {err}", account.id);
+        }
+    }
+}
+
+fn write<T: Serialize + DeserializeOwned>(key: &StoreKey<T>, value: &T) {
+    if let Err(err) = Preferences::set(key, value) {
+        tracing::error!("Failed to save {}: {err}", key.name);
+    }
+}
+
+fn read<T, E: std::fmt::Display>(result: Result<Option<T>, E>) -> Option<T> {
+    result.unwrap_or_else(|err| {
+        tracing::error!("Failed to read accounts: {err}");
+        None
+    })
+}
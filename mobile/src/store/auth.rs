@@ -0,0 +1,13 @@
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+
+use crate::api::types::user::AuthUser;
+
+/// Reads the user from the claims of the token.
+///
+/// The signature isn't verified, the claims are only used for display and the server
+/// validates the token on every request anyway.
+pub fn decode_token(token: &str) -> Option<AuthUser> {
+    let claims = token.split('.').nth(1)?;
+    let claims = URL_SAFE_NO_PAD.decode(claims.trim_end_matches('=')).ok()?;
+    serde_json::from_slice(&claims).ok()
+}
@@ -0,0 +1,111 @@
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+
+use crate::api::{
+    types::{
+        feed::{Feed, FeedItem},
+        index::UserIndex,
+        list::UserList,
+        tag::UserTag,
+        user::HistoryItem,
+    },
+    ApiClientError,
+};
+
+/// Every entity fetched from the API, keyed by id
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Entities {
+    pub feeds: HashMap<i32, Feed>,
+    pub items: HashMap<i64, FeedItem>,
+    pub lists: HashMap<i32, UserList>,
+    pub tags: HashMap<i32, UserTag>,
+    pub indexes: HashMap<i32, UserIndex>,
+    pub history: HashMap<i64, HistoryItem>,
+}
+
+impl Entities {
+    pub fn insert(&mut self, entity: Entity) {
+        match entity {
+            Entity::Feed(feed) => {
+                self.feeds.insert(feed.id, feed);
+            }
+            Entity::Item(item) => {
+                self.items.insert(item.id, item);
+            }
+            Entity::List(list) => {
+                self.lists.insert(list.id, list);
+            }
+            Entity::Tag(tag) => {
+                self.tags.insert(tag.id, tag);
+            }
+            Entity::Index(index) => {
+                self.indexes.insert(index.id, index);
+            }
+            Entity::History(history) => {
+                self.history.insert(history.id, history);
+            }
+        }
+    }
+}
+
+pub enum Entity {
+    Feed(Feed),
+    Item(FeedItem),
+    List(UserList),
+    Tag(UserTag),
+    Index(UserIndex),
+    History(HistoryItem),
+}
+
+/// A request to the API whose result is kept in the store.
+///
+/// Single entities resolve to their own id, pages resolve to the ids of the entities on the page,
+/// in order. Ids are widened to `i64` so every query can share a result type.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum QueryKey {
+    Feed(i32),
+    FeedItems {
+        feed_id: i32,
+        page: usize,
+    },
+    Item(i64),
+    Index(i32),
+    List(i32),
+    Lists {
+        page: usize,
+    },
+    /// Resolves to the ids of the feed items in the list
+    ListItems {
+        list_id: i32,
+        page: usize,
+    },
+    Tags {
+        page: usize,
+    },
+    Indexes {
+        page: usize,
+    },
+    History {
+        page: usize,
+    },
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct QueryState {
+    /// Result of the last completed request
+    pub result: Option<Result<Vec<i64>, ApiClientError>>,
+    /// A request is in flight, so no other request is made for the same key
+    pub loading: bool,
+    /// The result is outdated after a mutation and will be requested again
+    pub stale: bool,
+    /// When the last request completed
+    pub fetched_at: Option<DateTime<Utc>>,
+}
+
+impl QueryState {
+    /// Whether a request should be made for this query
+    pub fn needs_fetch(&self) -> bool {
+        !self.loading && (self.result.is_none() || self.stale)
+    }
+}
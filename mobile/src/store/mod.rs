@@ -0,0 +1,695 @@
+//! Normalized client-side store for data from the API.
+//!
+//! Entities are kept once, keyed by id, in [Entities]. Each [QueryKey] records which entities a
+//! request returned, so views select them through the hooks in [selectors] and stay in sync when
+//! another screen changes them. Requests for the same query are deduplicated, and mutations update
+//! the entities optimistically before invalidating the queries they affect.
+
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+use dioxus::prelude::*;
+use futures_util::future::{try_join_all, FutureExt, LocalBoxFuture, Shared};
+
+use crate::{
+    api::{
+        types::{
+            feed::{Feed, FeedItem},
+            index::UserIndex,
+            list::{CreateListItemRequest, UserListItem},
+            search::SearchRequest,
+            tag::{UpdateTagRequest, UserTag},
+            user::AuthUser,
+        },
+        ApiClient, ApiClientError,
+    },
+    hooks::use_api,
+    i18n::t,
+};
+
+mod accounts;
+mod auth;
+mod entities;
+mod selectors;
+
+pub use accounts::{files_dir, Account, DEFAULT_SERVER};
+pub use entities::{Entities, Entity, QueryKey, QueryState};
+pub use selectors::*;
+
+/// Page size of `GET /feed/{id}/item`
+const FEED_ITEMS_PAGE_SIZE: usize = 20;
+
+/// Page size of `POST /search`
+const SEARCH_PAGE_SIZE: usize = 20;
+
+/// A request for a query, shared by everyone waiting for it
+type Request = Shared<LocalBoxFuture<'static, Result<Vec<i64>, ApiClientError>>>;
+
+#[derive(Clone, Copy)]
+pub struct Store {
+    api: &'static ApiClient,
+    token: Signal<Option<String>>,
+    accounts: Signal<Vec<Account>>,
+    active_account: Signal<Option<Account>>,
+    expired_account: Signal<Option<Account>>,
+    entities: Signal<Entities>,
+    queries: Signal<HashMap<QueryKey, QueryState>>,
+    requests: CopyValue<HashMap<QueryKey, Request>>,
+    /// Changes whenever the store is reset for another account, so responses to requests made
+    /// for the previous one are dropped
+    generation: CopyValue<u64>,
+}
+
+impl Store {
+    pub fn api(&self) -> &'static ApiClient {
+        self.api
+    }
+
+    /// Token of the active account. Setting it logs in to the account it was issued for on the
+    /// API's current server, or out of the active account while keeping it for later.
+    pub fn token(&self) -> Signal<Option<String>> {
+        self.token
+    }
+
+    /// Accounts the user is logged in to, including the active one
+    pub fn accounts(&self) -> ReadOnlySignal<Vec<Account>> {
+        self.accounts.into()
+    }
+
+    pub fn active_account(&self) -> ReadOnlySignal<Option<Account>> {
+        self.active_account.into()
+    }
+
+    /// Account whose token the server stopped accepting, which the user has to log in to again
+    pub fn expired_account(&self) -> ReadOnlySignal<Option<Account>> {
+        self.expired_account.into()
+    }
+
+    /// Makes another account active. Everything is loaded again for it.
+    pub fn switch_account(&self, id: &str) {
+        let account = self
+            .accounts
+            .peek()
+            .iter()
+            .find(|account| account.id == id)
+            .cloned();
+        match account {
+            Some(account) => self.switch_to(&account),
+            None => tracing::error!("No account {id} to switch to"),
+        }
+    }
+
+    /// Leaves the active account without logging out of it, so another can be added
+    pub fn add_account(&self) {
+        let mut expired = self.expired_account;
+        expired.set(None);
+        let mut token = self.token;
+        token.set(None);
+    }
+
+    /// Logs out of the active account, removing its data, and switches to the next account if
+    /// the user is logged in to another
+    pub fn logout(&self) {
+        let mut expired = self.expired_account;
+        expired.set(None);
+        if let Some(account) = self.active_account.peek().as_ref() {
+            accounts::remove(account);
+        }
+
+        match accounts::load().first() {
+            Some(next) => self.switch_to(next),
+            None => {
+                let mut token = self.token;
+                token.set(None);
+            }
+        }
+    }
+
+    /// Leaves the active account after the server rejected its token, so the user logs in to it
+    /// again. Unlike [Store::logout], its settings and files are kept.
+    pub fn expire_session(&self) {
+        let Some(account) = self.active_account.peek().clone() else {
+            return;
+        };
+        accounts::remove_token(&account);
+        self.expire(account);
+    }
+
+    fn switch_to(&self, account: &Account) {
+        self.api.set_base_url(&account.server);
+        match accounts::token(&account.id) {
+            Some(token) => {
+                let mut signal = self.token;
+                signal.set(Some(token));
+            }
+            None => self.expire(account.clone()),
+        }
+    }
+
+    fn expire(&self, account: Account) {
+        let mut expired = self.expired_account;
+        expired.set(Some(account));
+        let mut token = self.token;
+        token.set(None);
+    }
+
+    /// The logged in user, read from the token
+    pub fn user(&self) -> Option<AuthUser> {
+        self.token.read().as_deref().and_then(auth::decode_token)
+    }
+
+    pub fn entities(&self) -> ReadOnlySignal<Entities> {
+        self.entities.into()
+    }
+
+    pub fn query(&self, key: QueryKey) -> QueryState {
+        self.queries.read().get(&key).cloned().unwrap_or_default()
+    }
+
+    /// Requests the query in the background, unless it is fresh or already being requested
+    pub fn fetch(&self, key: QueryKey) {
+        self.request(key);
+    }
+
+    /// Returns the ids the query resolves to, requesting it only if it isn't fresh.
+    /// Waits for the request in flight, if there is one, instead of making another.
+    pub async fn load(&self, key: QueryKey) -> Result<Vec<i64>, ApiClientError> {
+        loop {
+            let generation = *self.generation.peek();
+            let result = match self.request(key) {
+                Some(request) => request.await,
+                None => match self
+                    .queries
+                    .peek()
+                    .get(&key)
+                    .and_then(|state| state.result.clone())
+                {
+                    Some(result) => result,
+                    None => continue,
+                },
+            };
+            // The store was reset for another account while waiting, so request it for that one
+            if *self.generation.peek() == generation {
+                return result;
+            }
+        }
+    }
+
+    /// The request in flight for the query, starting one if the query isn't fresh
+    fn request(&self, key: QueryKey) -> Option<Request> {
+        if let Some(request) = self.requests.peek().get(&key) {
+            return Some(request.clone());
+        }
+        let fresh = self
+            .queries
+            .peek()
+            .get(&key)
+            .is_some_and(|state| state.result.is_some() && !state.needs_fetch());
+        if fresh {
+            return None;
+        }
+
+        let mut queries = self.queries;
+        queries.write().entry(key).or_default().loading = true;
+        let store = *self;
+        let generation = *self.generation.peek();
+        let request = async move { store.run(key, generation).await }
+            .boxed_local()
+            .shared();
+        let mut requests = self.requests;
+        requests.write().insert(key, request.clone());
+
+        // Outlives the component which asked for it, so the query can't be left loading forever
+        spawn_forever({
+            let request = request.clone();
+            async move {
+                let _ = request.await;
+            }
+        });
+        Some(request)
+    }
+
+    /// Forgets every entity and cached response, so everything is requested again when used
+    pub fn clear_cache(&self) {
+        self.api.clear_cache();
+        self.reset();
+    }
+
+    /// Forgets every entity and query, dropping the responses to requests in flight
+    fn reset(&self) {
+        let mut generation = self.generation;
+        *generation.write() += 1;
+        let mut requests = self.requests;
+        requests.write().clear();
+        let mut entities = self.entities;
+        entities.set(Entities::default());
+        let mut queries = self.queries;
+        queries.set(HashMap::new());
+    }
+
+    /// Requests every query which failed again, for when the user retries after an error
+    pub fn retry_failed(&self) {
+        let failed: Vec<QueryKey> = self
+            .queries
+            .peek()
+            .iter()
+            .filter(|(_, state)| matches!(state.result, Some(Err(_))))
+            .map(|(key, _)| *key)
+            .collect();
+        self.invalidate(|key| failed.contains(key));
+        for key in failed {
+            self.fetch(key);
+        }
+    }
+
+    /// Marks every query matching the predicate as stale, so it is requested again when used
+    pub fn invalidate(&self, predicate: impl Fn(&QueryKey) -> bool) {
+        let mut queries = self.queries;
+        for (key, state) in queries.write().iter_mut() {
+            if predicate(key) {
+                state.stale = true;
+            }
+        }
+    }
+
+    /// Requests the queries matching the predicate again, revalidating cached responses with the
+    /// server, and waits for them
+    pub async fn refresh(
+        &self,
+        predicate: impl Fn(&QueryKey) -> bool,
+    ) -> Result<(), ApiClientError> {
+        self.api.expire_cache();
+        self.invalidate(&predicate);
+        let keys: Vec<QueryKey> = self
+            .queries
+            .peek()
+            .keys()
+            .filter(|key| predicate(key))
+            .copied()
+            .collect();
+        for key in keys {
+            self.load(key).await?;
+        }
+        Ok(())
+    }
+
+    /// When the least recently fetched query matching the predicate was fetched
+    pub fn updated_at(&self, predicate: impl Fn(&QueryKey) -> bool) -> Option<DateTime<Utc>> {
+        self.queries
+            .read()
+            .iter()
+            .filter(|(key, _)| predicate(key))
+            .filter_map(|(_, state)| state.fetched_at)
+            .min()
+    }
+
+    async fn run(&self, key: QueryKey, generation: u64) -> Result<Vec<i64>, ApiClientError> {
+        let result = request(self.api, key).await;
+        // Made for an account which isn't active anymore
+        if *self.generation.peek() != generation {
+            return result.map(|(_, ids)| ids);
+        }
+
+        let result = result.map(|(fetched, ids)| {
+            let mut entities = self.entities;
+            let mut entities = entities.write();
+            for entity in fetched {
+                entities.insert(entity);
+            }
+            ids
+        });
+
+        let mut requests = self.requests;
+        requests.write().remove(&key);
+        let mut queries = self.queries;
+        queries.write().insert(
+            key,
+            QueryState {
+                result: Some(result.clone()),
+                loading: false,
+                stale: false,
+                fetched_at: Some(Utc::now()),
+            },
+        );
+        result
+    }
+
+    // ---------------
+    // Selectors for async code
+    // ---------------
+
+    pub async fn feed(&self, id: i32) -> Result<Feed, ApiClientError> {
+        self.load(QueryKey::Feed(id)).await?;
+        let feed = self.entities.peek().feeds.get(&id).cloned();
+        feed.ok_or_else(|| ApiClientError::NotFound(t!("error-feed-not-found", id = id)))
+    }
+
+    pub async fn feed_items(
+        &self,
+        feed_id: i32,
+        page: usize,
+    ) -> Result<Vec<FeedItem>, ApiClientError> {
+        let ids = self.load(QueryKey::FeedItems { feed_id, page }).await?;
+        let entities = self.entities.peek();
+        Ok(ids
+            .iter()
+            .filter_map(|id| entities.items.get(id))
+            .cloned()
+            .collect())
+    }
+
+    /// Items of the feed, newest first, requesting pages until every item published since
+    /// `since` is loaded, or every item for `None`
+    pub async fn feed_items_since(
+        &self,
+        feed_id: i32,
+        since: Option<DateTime<Utc>>,
+    ) -> Result<Vec<FeedItem>, ApiClientError> {
+        let mut items = Vec::new();
+        for page in 0.. {
+            let page_items = self.feed_items(feed_id, page).await?;
+            let last_page = page_items.len() < FEED_ITEMS_PAGE_SIZE;
+            let covered = since.is_some_and(|since| {
+                page_items
+                    .last()
+                    .and_then(|item| item.published_at)
+                    .is_some_and(|published_at| published_at < since)
+            });
+            items.extend(page_items);
+            if last_page || covered {
+                break;
+            }
+        }
+        Ok(items)
+    }
+
+    pub async fn item(&self, id: i64) -> Result<FeedItem, ApiClientError> {
+        self.load(QueryKey::Item(id)).await?;
+        let item = self.entities.peek().items.get(&id).cloned();
+        item.ok_or_else(|| ApiClientError::NotFound(t!("error-item-not-found", id = id)))
+    }
+
+    pub async fn index(&self, id: i32) -> Result<UserIndex, ApiClientError> {
+        self.load(QueryKey::Index(id)).await?;
+        let index = self.entities.peek().indexes.get(&id).cloned();
+        index.ok_or_else(|| ApiClientError::NotFound(t!("error-index-not-found", id = id)))
+    }
+
+    /// Every feed matching the index's search, requesting pages until a short one
+    pub async fn index_feeds(&self, id: i32) -> Result<Vec<Feed>, ApiClientError> {
+        let index = self.index(id).await?;
+        let request = SearchRequest {
+            query: index.query,
+            sort: index.sort.into(),
+        };
+        let mut feeds = Vec::new();
+        for page in 0.. {
+            let page_feeds = self.api.search(&request, page).await?;
+            let last_page = page_feeds.len() < SEARCH_PAGE_SIZE;
+            feeds.extend(page_feeds);
+            if last_page {
+                break;
+            }
+        }
+        Ok(feeds)
+    }
+
+    pub async fn tags(&self, page: usize) -> Result<Vec<UserTag>, ApiClientError> {
+        let ids = self.load(QueryKey::Tags { page }).await?;
+        let entities = self.entities.peek();
+        Ok(ids
+            .iter()
+            .filter_map(|id| entities.tags.get(&(*id as i32)))
+            .cloned()
+            .collect())
+    }
+
+    /// Finds the item linking to the page. Items which aren't in the store yet are found in
+    /// the feed of the page's site, which the user is subscribed to for that.
+    pub async fn item_for_link(&self, link: &str) -> Result<FeedItem, ApiClientError> {
+        let known = self
+            .entities
+            .peek()
+            .items
+            .values()
+            .find(|item| {
+                item.link
+                    .as_deref()
+                    .is_some_and(|other| same_link(other, link))
+            })
+            .cloned();
+        if let Some(item) = known {
+            return Ok(item);
+        }
+
+        let discovered = self.api.discover_feeds(link).await?;
+        let information = discovered
+            .first()
+            .ok_or_else(|| ApiClientError::NotFound(t!("error-no-feed-for-site")))?;
+        let feed = self.create_feed(&information.url).await?;
+        self.feed_items(feed.id, 0)
+            .await?
+            .into_iter()
+            .find(|item| {
+                item.link
+                    .as_deref()
+                    .is_some_and(|other| same_link(other, link))
+            })
+            .ok_or_else(|| ApiClientError::NotFound(t!("error-page-not-in-feed")))
+    }
+
+    // ---------------
+    // Mutations
+    // ---------------
+
+    /// `PUT /feed`: Subscribes to a feed
+    pub async fn create_feed(&self, link: &str) -> Result<Feed, ApiClientError> {
+        let feed = self.api.create_feed(link).await?;
+        let mut entities = self.entities;
+        entities.write().insert(Entity::Feed(feed.clone()));
+        Ok(feed)
+    }
+
+    /// `DELETE /feed/{id}`: Unsubscribes from a feed, removing it immediately
+    pub async fn delete_feed(&self, id: i32) -> Result<(), ApiClientError> {
+        let mut entities = self.entities;
+        let removed = entities.write().feeds.remove(&id);
+
+        match self.api.delete_feed(id).await {
+            Ok(()) => {
+                self.invalidate(|key| matches!(key, QueryKey::Tags { .. }));
+                Ok(())
+            }
+            Err(err) => {
+                if let Some(feed) = removed {
+                    entities.write().insert(Entity::Feed(feed));
+                }
+                Err(err)
+            }
+        }
+    }
+
+    /// `PATCH /tag/{id}`: Updates a tag, applying the change immediately
+    pub async fn update_tag(
+        &self,
+        id: i32,
+        request: &UpdateTagRequest,
+    ) -> Result<UserTag, ApiClientError> {
+        let mut entities = self.entities;
+        let previous = entities.peek().tags.get(&id).cloned();
+        if let Some(tag) = entities.write().tags.get_mut(&id) {
+            if let Some(title) = &request.title {
+                tag.title = title.clone();
+            }
+            for child in request.children_to_remove.iter().flatten() {
+                tag.children.retain(|existing| existing != child);
+            }
+            for child in request.children_to_add.iter().flatten() {
+                if !tag.children.contains(child) {
+                    tag.children.push(child.clone());
+                }
+            }
+        }
+
+        match self.api.update_tag(id, request).await {
+            Ok(tag) => {
+                entities.write().insert(Entity::Tag(tag.clone()));
+                Ok(tag)
+            }
+            Err(err) => {
+                if let Some(tag) = previous {
+                    entities.write().insert(Entity::Tag(tag));
+                }
+                Err(err)
+            }
+        }
+    }
+
+    /// `PUT /list/{list_id}/item`: Adds an item to a list
+    pub async fn create_list_item(
+        &self,
+        list_id: i32,
+        request: &CreateListItemRequest,
+    ) -> Result<UserListItem, ApiClientError> {
+        let result = self.api.create_list_item(list_id, request).await;
+        self.invalidate(
+            |key| matches!(key, QueryKey::ListItems { list_id: id, .. } if *id == list_id),
+        );
+        result
+    }
+
+    /// `DELETE /list/{list_id}/item/{item_id}`: Removes an item from a list immediately
+    pub async fn delete_list_item(&self, list_id: i32, item_id: i64) -> Result<(), ApiClientError> {
+        let mut queries = self.queries;
+        for (key, state) in queries.write().iter_mut() {
+            if matches!(key, QueryKey::ListItems { list_id: id, .. } if *id == list_id) {
+                if let Some(Ok(ids)) = &mut state.result {
+                    ids.retain(|id| *id != item_id);
+                }
+            }
+        }
+
+        let result = self.api.delete_list_item(list_id, item_id).await;
+        self.invalidate(
+            |key| matches!(key, QueryKey::ListItems { list_id: id, .. } if *id == list_id),
+        );
+        result
+    }
+}
+
+/// Whether two links point to the same page, ignoring a trailing slash
+fn same_link(a: &str, b: &str) -> bool {
+    a.trim_end_matches('/') == b.trim_end_matches('/')
+}
+
+/// Makes the request for the query, returning the fetched entities and the ids it resolves to
+async fn request(
+    api: &ApiClient,
+    key: QueryKey,
+) -> Result<(Vec<Entity>, Vec<i64>), ApiClientError> {
+    Ok(match key {
+        QueryKey::Feed(id) => {
+            let feed = api.get_feed(id).await?;
+            (vec![Entity::Feed(feed)], vec![id as i64])
+        }
+        QueryKey::FeedItems { feed_id, page } => {
+            let items = api.list_feed_items(feed_id, page).await?;
+            let ids = items.iter().map(|item| item.id).collect();
+            (items.into_iter().map(Entity::Item).collect(), ids)
+        }
+        QueryKey::Item(id) => {
+            let item = api.get_item(id).await?;
+            (vec![Entity::Item(item)], vec![id])
+        }
+        QueryKey::Index(id) => {
+            let index = api.get_index(id).await?;
+            (vec![Entity::Index(index)], vec![id as i64])
+        }
+        QueryKey::List(id) => {
+            let list = api.get_list(id).await?;
+            (vec![Entity::List(list)], vec![id as i64])
+        }
+        QueryKey::Lists { page } => {
+            let lists = api.list_lists(page).await?;
+            let ids = lists.iter().map(|list| list.id as i64).collect();
+            (lists.into_iter().map(Entity::List).collect(), ids)
+        }
+        QueryKey::ListItems { list_id, page } => {
+            let list_items = api.list_list_items(list_id, page).await?;
+            // There's no endpoint for several items at once, so they are requested together
+            let items = try_join_all(
+                list_items
+                    .iter()
+                    .map(|list_item| api.get_item(list_item.item)),
+            )
+            .await?;
+            let ids = list_items.iter().map(|list_item| list_item.item).collect();
+            (items.into_iter().map(Entity::Item).collect(), ids)
+        }
+        QueryKey::Tags { page } => {
+            let tags = api.list_tags(page).await?;
+            let ids = tags.iter().map(|tag| tag.id as i64).collect();
+            (tags.into_iter().map(Entity::Tag).collect(), ids)
+        }
+        QueryKey::Indexes { page } => {
+            let indexes = api.list_indexes(page).await?;
+            let ids = indexes.iter().map(|index| index.id as i64).collect();
+            (indexes.into_iter().map(Entity::Index).collect(), ids)
+        }
+        QueryKey::History { page } => {
+            let history = api.list_history(page).await?;
+            let ids = history.iter().map(|history| history.id).collect();
+            (history.into_iter().map(Entity::History).collect(), ids)
+        }
+    })
+}
+
+/// Creates the store and restores the account which was active. Must be called once, at the
+/// root of the app.
+pub fn use_store_provider() -> Store {
+    let api = use_api();
+    let restored = use_hook(|| {
+        let restored = accounts::restore();
+        if let Some((account, token)) = &restored {
+            api.set_base_url(&account.server);
+            api.set_token(Some(token.clone()));
+        }
+        restored
+    });
+    let token = use_signal(|| restored.as_ref().map(|(_, token)| token.clone()));
+    let mut active_account = use_signal(|| restored.map(|(account, _)| account));
+    let mut expired_account = use_signal(|| None::<Account>);
+    let mut accounts = use_signal(accounts::load);
+    let entities = use_signal(Entities::default);
+    let queries = use_signal(HashMap::new);
+    let store = use_context_provider(|| Store {
+        api,
+        token,
+        accounts,
+        active_account,
+        expired_account,
+        entities,
+        queries,
+        requests: CopyValue::new(HashMap::new()),
+        generation: CopyValue::new(0),
+    });
+
+    // Everything in the store belongs to the account it was fetched for
+    use_effect(move || {
+        let token = token();
+        if api.get_token() == token {
+            return;
+        }
+
+        let account = token.as_deref().and_then(|token| {
+            let account = Account::from_token(&api.base_url(), token);
+            if account.is_none() {
+                tracing::error!("Failed to read the account from the token");
+            }
+            account
+        });
+        if let (Some(account), Some(token)) = (&account, &token) {
+            accounts::save(account, token);
+        }
+        accounts::activate(account.as_ref());
+        match &account {
+            // Logging in again to an account which expired goes to the server it is on
+            None => match expired_account.peek().as_ref() {
+                Some(expired) => api.set_base_url(&expired.server),
+                None => api.set_base_url(DEFAULT_SERVER),
+            },
+            Some(_) => expired_account.set(None),
+        }
+
+        api.set_token(token);
+        store.reset();
+        accounts.set(accounts::load());
+        active_account.set(account);
+    });
+
+    store
+}
+
+pub fn use_store() -> Store {
+    use_context::<Store>()
+}
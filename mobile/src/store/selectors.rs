@@ -0,0 +1,167 @@
+use dioxus::prelude::*;
+
+use super::{use_store, Entities, QueryKey, QueryState};
+use crate::api::{
+    types::{
+        feed::{Feed, FeedItem},
+        index::UserIndex,
+        list::UserList,
+        tag::UserTag,
+        user::{AuthUser, HistoryItem},
+    },
+    ApiClientError,
+};
+
+/// Data selected from the store for a query
+#[derive(Clone, Debug, PartialEq)]
+pub struct Query<T> {
+    pub data: Option<T>,
+    pub error: Option<ApiClientError>,
+    /// A request is in flight. `data` may still hold the previous result while refreshing.
+    pub loading: bool,
+}
+
+/// Requests the query when it is missing or stale, and selects its data from the entities
+fn use_query<T: Clone + PartialEq + 'static>(
+    key: QueryKey,
+    select: fn(&Entities, &[i64]) -> Option<T>,
+) -> Memo<Query<T>> {
+    let store = use_store();
+
+    // Give queries which failed another chance whenever they are shown again
+    use_hook(|| {
+        if matches!(store.query(key).result, Some(Err(_))) {
+            store.invalidate(|other| *other == key);
+        }
+    });
+
+    use_effect(use_reactive!(|key| {
+        if store.query(key).needs_fetch() {
+            store.fetch(key);
+        }
+    }));
+
+    use_memo(use_reactive!(|key| {
+        let QueryState {
+            result, loading, ..
+        } = store.query(key);
+        let entities = store.entities();
+        let entities = entities.read();
+
+        match result {
+            Some(Ok(ids)) => Query {
+                data: select(&entities, &ids),
+                error: None,
+                loading,
+            },
+            Some(Err(error)) => Query {
+                data: None,
+                error: Some(error),
+                loading,
+            },
+            None => Query {
+                data: None,
+                error: None,
+                loading: true,
+            },
+        }
+    }))
+}
+
+pub fn use_feed(id: i32) -> Memo<Query<Feed>> {
+    use_query(QueryKey::Feed(id), |entities, ids| {
+        ids.first()
+            .and_then(|id| entities.feeds.get(&(*id as i32)))
+            .cloned()
+    })
+}
+
+pub fn use_feed_items(feed_id: i32, page: usize) -> Memo<Query<Vec<FeedItem>>> {
+    use_query(QueryKey::FeedItems { feed_id, page }, select_items)
+}
+
+pub fn use_item(id: i64) -> Memo<Query<FeedItem>> {
+    use_query(QueryKey::Item(id), |entities, ids| {
+        ids.first().and_then(|id| entities.items.get(id)).cloned()
+    })
+}
+
+pub fn use_index(id: i32) -> Memo<Query<UserIndex>> {
+    use_query(QueryKey::Index(id), |entities, ids| {
+        ids.first()
+            .and_then(|id| entities.indexes.get(&(*id as i32)))
+            .cloned()
+    })
+}
+
+pub fn use_list(id: i32) -> Memo<Query<UserList>> {
+    use_query(QueryKey::List(id), |entities, ids| {
+        ids.first()
+            .and_then(|id| entities.lists.get(&(*id as i32)))
+            .cloned()
+    })
+}
+
+pub fn use_lists(page: usize) -> Memo<Query<Vec<UserList>>> {
+    use_query(QueryKey::Lists { page }, |entities, ids| {
+        Some(
+            ids.iter()
+                .filter_map(|id| entities.lists.get(&(*id as i32)))
+                .cloned()
+                .collect(),
+        )
+    })
+}
+
+/// Feed items in the list
+pub fn use_list_items(list_id: i32, page: usize) -> Memo<Query<Vec<FeedItem>>> {
+    use_query(QueryKey::ListItems { list_id, page }, select_items)
+}
+
+pub fn use_tags(page: usize) -> Memo<Query<Vec<UserTag>>> {
+    use_query(QueryKey::Tags { page }, |entities, ids| {
+        Some(
+            ids.iter()
+                .filter_map(|id| entities.tags.get(&(*id as i32)))
+                .cloned()
+                .collect(),
+        )
+    })
+}
+
+pub fn use_indexes(page: usize) -> Memo<Query<Vec<UserIndex>>> {
+    use_query(QueryKey::Indexes { page }, |entities, ids| {
+        Some(
+            ids.iter()
+                .filter_map(|id| entities.indexes.get(&(*id as i32)))
+                .cloned()
+                .collect(),
+        )
+    })
+}
+
+pub fn use_history(page: usize) -> Memo<Query<Vec<HistoryItem>>> {
+    use_query(QueryKey::History { page }, |entities, ids| {
+        Some(
+            ids.iter()
+                .filter_map(|id| entities.history.get(id))
+                .cloned()
+                .collect(),
+        )
+    })
+}
+
+/// The logged in user, read from the token
+pub fn use_auth_user() -> Memo<Option<AuthUser>> {
+    let store = use_store();
+    use_memo(move || store.user())
+}
+
+fn select_items(entities: &Entities, ids: &[i64]) -> Option<Vec<FeedItem>> {
+    Some(
+        ids.iter()
+            .filter_map(|id| entities.items.get(id))
+            .cloned()
+            .collect(),
+    )
+}
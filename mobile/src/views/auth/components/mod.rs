@@ -2,12 +2,14 @@ use dioxus::prelude::*;
 
 mod container;
 mod header;
+mod resend;
 
 pub use container::AuthContainer;
 pub use header::Header;
+pub use resend::{use_resend_cooldown, ResendButton, CODE_LIFETIME};
 
 #[component]
-pub fn Error(error: Signal<Option<String>>) -> Element {
+pub fn Error(error: ReadOnlySignal<Option<String>>) -> Element {
     if let Some(error) = error() {
         return rsx! {
             span { align_self: "center", color: "var(--text-error)", text_align: "center",
@@ -0,0 +1,97 @@
+use std::time::{Duration, Instant};
+
+use dioxus::prelude::*;
+use ui::forms::button::TransparentButton;
+
+use crate::api::ApiClientError;
+use crate::i18n::t;
+
+/// How long to wait before sending another code, unless the server asks for longer
+const RESEND_COOLDOWN: Duration = Duration::from_secs(60);
+
+/// How long codes sent by email can be used for
+pub const CODE_LIFETIME: Duration = Duration::from_secs(15 * 60);
+
+/// When a code was last sent, to wait before sending another and to tell when it expired
+#[derive(Clone, Copy, PartialEq)]
+pub struct ResendCooldown {
+    sent_at: Signal<Option<Instant>>,
+    available_at: Signal<Option<Instant>>,
+    /// Updated every second, so the countdown is rendered again
+    now: Signal<Instant>,
+}
+
+impl ResendCooldown {
+    /// Seconds until another code can be sent
+    pub fn remaining(&self) -> u64 {
+        let now = (self.now)();
+        (self.available_at)()
+            .filter(|available_at| *available_at > now)
+            .map_or(0, |available_at| {
+                (available_at - now).as_secs_f64().ceil() as u64
+            })
+    }
+
+    /// Whether the code which was sent can't be used anymore
+    pub fn expired(&self) -> bool {
+        (self.sent_at)().is_some_and(|sent_at| (self.now)() - sent_at > CODE_LIFETIME)
+    }
+
+    /// Starts waiting after a request for a code. When the server rate limited the request,
+    /// waits as long as it asks for.
+    pub fn handle<T>(&mut self, result: &Result<T, ApiClientError>) {
+        let now = Instant::now();
+        self.now.set(now);
+        match result {
+            Ok(_) => {
+                self.sent_at.set(Some(now));
+                self.available_at.set(Some(now + RESEND_COOLDOWN));
+            }
+            Err(ApiClientError::RateLimited { retry_after, .. }) => {
+                let wait = retry_after.unwrap_or(RESEND_COOLDOWN);
+                self.available_at.set(Some(now + wait));
+            }
+            Err(_) => {}
+        }
+    }
+}
+
+/// Tracks the code sent to the user. `sent` is whether one was sent just before the screen
+/// was opened.
+pub fn use_resend_cooldown(sent: bool) -> ResendCooldown {
+    let sent_at = use_signal(|| sent.then(Instant::now));
+    let available_at = use_signal(|| sent.then(|| Instant::now() + RESEND_COOLDOWN));
+    let mut now = use_signal(Instant::now);
+
+    use_future(move || async move {
+        loop {
+            tokio::time::sleep(Duration::from_secs(1)).await;
+            now.set(Instant::now());
+        }
+    });
+
+    ResendCooldown {
+        sent_at,
+        available_at,
+        now,
+    }
+}
+
+/// Sends another code, counting down until it can
+#[component]
+pub fn ResendButton(cooldown: ResendCooldown, onclick: EventHandler) -> Element {
+    let remaining = cooldown.remaining();
+    let countdown = format!("{}:{:02}", remaining / 60, remaining % 60);
+
+    rsx! {
+        TransparentButton {
+            disabled: remaining > 0,
+            onclick: move |_| onclick.call(()),
+            if remaining > 0 {
+                {t!("resend-in", countdown = countdown)}
+            } else {
+                {t!("resend")}
+            }
+        }
+    }
+}
@@ -1,12 +1,17 @@
 use dioxus::prelude::*;
+use reqwest::Url;
 
 use crate::{
-    api::ApiClient,
+    api::{types::user::OAuthProvider, ApiClient},
     hooks::{use_api, use_keyboard_open, use_token},
+    i18n::t,
+    store::{use_store, DEFAULT_SERVER},
     views::{
         auth::{
             components::{AuthContainer, Error},
-            use_auth_form, AuthFormContext,
+            use_auth_form, use_oauth_login,
+            validation::required,
+            AuthFormContext,
         },
         Route,
     },
@@ -15,11 +20,12 @@ use crate::{
 use super::components::Header;
 use ui::{
     forms::{
-        button::{SolidButton, TransparentButton},
-        input::Input,
+        button::TransparentButton,
+        form::{use_field, use_form, FieldOptions, SubmitButton},
+        input::{Input, PasswordInput},
     },
-    icons::{AppleIcon, GoogleIcon, LockIcon, UserIcon},
-    layout::Column,
+    icons::{AppleIcon, GoogleIcon, UserIcon},
+    layout::{Column, Row},
 };
 
 #[component]
@@ -27,54 +33,83 @@ pub fn Login() -> Element {
     let api = use_api();
 
     let mut auth_form = use_auth_form();
-    let mut error = use_signal(|| None::<String>);
     let mut token = use_token();
+    let store = use_store();
+    let accounts = store.accounts();
+
+    let mut form = use_form();
+    let name = use_signal(|| auth_form.email_or_username());
+    let mut name = use_field(form, "email", name, || {
+        FieldOptions::new().validate(|name: &String| required(name, t!("login-enter-name")))
+    });
+    let mut password = use_field(form, "password", auth_form.password, || {
+        FieldOptions::new()
+            .validate(|password: &String| required(password, t!("login-enter-password")))
+    });
+
+    // Accounts can be on a self-hosted server instead of Bind's
+    let server = use_signal(|| api.base_url());
+    let mut choose_server = use_signal(|| server() != DEFAULT_SERVER);
+    let mut server = use_field(form, "server", server, || {
+        FieldOptions::new().validate(|server: &String| {
+            parse_server(server)
+                .map(|_| ())
+                .ok_or_else(|| t!("login-enter-server"))
+        })
+    });
+    let use_server = move || {
+        if let Some(server) = parse_server(&server.value()) {
+            api.set_base_url(&server);
+        }
+    };
+
+    // Logging in again to an account whose session expired, which keeps its data
+    let expired_account = store.expired_account();
+    use_effect(move || {
+        if let Some(account) = expired_account() {
+            auth_form.set_email_or_username(account.email.clone());
+            name.set(account.email.clone());
+            server.set(account.server.clone());
+            choose_server.set(account.is_self_hosted());
+        }
+    });
 
     let mut keyboard_open = use_keyboard_open();
 
-    let login = use_callback(move |_| {
-        let email = auth_form.email();
-        let username = auth_form.username();
-        let password = auth_form.password();
+    let login = move |_| {
+        use_server();
+        form.submit(move || async move {
+            let response = api.login_user(&name.value(), &password.value()).await?;
+            token.set(Some(response.token));
+            Ok(())
+        });
+    };
 
-        // Validate
-        if (email.is_empty() && username.is_empty()) || password.is_empty() {
-            error.set(Some(
-                "Email/username and password cannot be empty".to_string(),
-            ));
+    let oauth_login = use_oauth_login(form);
+    let login_with = use_callback(move |provider: OAuthProvider| {
+        if !server.is_valid() {
+            server.touch();
             return;
         }
-
-        // Login
-        spawn(async move {
-            match api
-                .login_user(&auth_form.email_or_username(), &password)
-                .await
-            {
-                Ok(response) => {
-                    token.set(Some(response.token));
-                    error.set(None);
-                }
-                Err(err) => error.set(Some(err.message())),
-            };
-        });
+        use_server();
+        oauth_login.call(provider);
     });
 
     rsx! {
         AuthContainer {
-            Header { subtitle: "Welcome back to", title: "Bind" }
+            Header { subtitle: t!("login-welcome-back"), title: "Bind" }
 
             // OAuth
             if !keyboard_open() {
                 Column { gap: "24px",
 
-                    button { class: "solid", onclick: move |_| {},
+                    button { class: "solid", onclick: move |_| login_with.call(OAuthProvider::Google),
                         GoogleIcon {}
-                        "Login with Google"
+                        {t!("login-with-google")}
                     }
-                    button { class: "solid", onclick: move |_| {},
+                    button { class: "solid", onclick: move |_| login_with.call(OAuthProvider::Apple),
                         AppleIcon {}
-                        "Login with Apple"
+                        {t!("login-with-apple")}
                     }
                 }
             }
@@ -84,50 +119,101 @@ pub fn Login() -> Element {
             // Form
             Column { gap: "24px",
 
+                if expired_account.read().is_some() {
+                    span { color: "var(--text-secondary)", {t!("error-logged-out-message")} }
+                }
+
                 Input {
-                    title: "Email or Username",
-                    placeholder: "Email or Username",
+                    title: t!("login-email-or-username"),
+                    placeholder: t!("login-email-or-username"),
                     icon: rsx! {
                         UserIcon {}
                     },
                     input_type: "email",
-                    value: auth_form.email_or_username(),
-                    onchange: move |value| {
-                        auth_form.set_email_or_username(value);
+                    value: name.value(),
+                    error: name.error(),
+                    onchange: move |value: String| {
+                        auth_form.set_email_or_username(value.clone());
+                        name.set(value);
                     },
+                    onblur: move |_| name.touch(),
                 }
-                Input {
-                    title: "Password",
-                    placeholder: "Password",
-                    icon: rsx! {
-                        LockIcon {}
-                    },
-                    input_type: "password",
-                    value: auth_form.password(),
-                    onchange: move |value| {
-                        auth_form.set_password(value);
-                    },
+                PasswordInput {
+                    title: t!("auth-password"),
+                    placeholder: t!("auth-password"),
+                    value: password.value(),
+                    error: password.error(),
+                    onchange: move |value| password.set(value),
+                    onblur: move |_| password.touch(),
+                }
+                if choose_server() {
+                    Input {
+                        title: t!("login-server"),
+                        placeholder: DEFAULT_SERVER,
+                        input_type: "url",
+                        value: server.value(),
+                        error: server.error(),
+                        onchange: move |value| server.set(value),
+                        onblur: move |_| server.touch(),
+                    }
                 }
 
                 // Actions
                 Column { gap: "12px", align: "stretch",
 
-                    SolidButton { onclick: move |_| { login.call(()); }, "Login" }
+                    SubmitButton { form, onclick: login, {t!("auth-login")} }
                     TransparentButton {
                         onclick: move |_| {
                             navigator().push(Route::ResetPassword {});
                         },
-                        "Reset Password"
+                        {t!("auth-reset-password")}
                     }
                     TransparentButton {
                         onclick: move |_| {
                             navigator().push(Route::SignUp {});
                         },
-                        "Sign up"
+                        {t!("auth-sign-up")}
+                    }
+                    if !choose_server() {
+                        TransparentButton {
+                            onclick: move |_| choose_server.set(true),
+                            {t!("login-another-server")}
+                        }
+                    }
+                    Error { error: form.error() }
+                }
+
+                // Accounts the user is still logged in to, after leaving one to add another
+                if !accounts.read().is_empty() {
+                    Column { gap: "8px", align: "stretch",
+                        span { color: "var(--text-secondary)", {t!("login-continue-as")} }
+                        for account in accounts() {
+                            Row { key: "{account.id}", align: "space-between", cross_align: "center",
+                                span { "{account.username}" }
+                                TransparentButton {
+                                    onclick: {
+                                        let id = account.id.clone();
+                                        move |_| store.switch_account(&id)
+                                    },
+                                    {t!("login-continue")}
+                                }
+                            }
+                        }
                     }
-                    Error { error }
                 }
             }
         }
     }
 }
+
+/// The base URL of a server entered by the user, who may leave out the scheme
+fn parse_server(server: &str) -> Option<String> {
+    let server = server.trim().trim_end_matches('/');
+    let server = if server.contains("://") {
+        server.to_string()
+    } else {
+        format!("https://{server}")
+    };
+    let url = Url::parse(&server).ok()?;
+    (matches!(url.scheme(), "http" | "https") && url.host_str().is_some()).then_some(server)
+}
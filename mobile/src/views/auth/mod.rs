@@ -2,17 +2,26 @@ use std::ops::Deref;
 
 use dioxus::prelude::*;
 
-use crate::{hooks::use_token, views::Route};
+use crate::{
+    api::types::user::OAuthProvider,
+    deep_link::use_deep_links,
+    hooks::{use_api, use_token},
+    oauth,
+    views::Route,
+};
+use ui::forms::form::Form;
 
 mod components;
 mod login;
+mod oauth_callback;
 mod reset_password;
 mod reset_password_confirm;
 mod sign_up;
-mod validation;
+pub mod validation;
 mod verify_email;
 
 pub use login::Login;
+pub use oauth_callback::OAuthCallback;
 pub use reset_password::ResetPassword;
 pub use reset_password_confirm::ResetPasswordConfirm;
 pub use sign_up::SignUp;
@@ -67,14 +76,32 @@ pub fn use_auth_form() -> AuthFormContext {
     use_context::<AuthFormContext>()
 }
 
+/// Starts logging in with a provider from the login or sign up screen, showing why it couldn't
+/// start on the form. It's completed in [OAuthCallback] once the browser redirects back to the
+/// app.
+fn use_oauth_login(form: Form) -> Callback<OAuthProvider> {
+    let api = use_api();
+    use_callback(move |provider: OAuthProvider| {
+        let mut form = form;
+        spawn(async move {
+            if let Err(err) = oauth::start(api, provider).await {
+                form.set_error(Some(err.message()));
+            }
+        });
+    })
+}
+
 #[component]
 pub fn AuthLayout() -> Element {
     use_context_provider(AuthFormContext::default);
 
     let mut token = use_token();
+    let mut deep_links = use_deep_links();
     use_effect(move || {
         if token().is_some() {
-            navigator().push(Route::Feed {});
+            // Open the link the user had to log in for, if there was one
+            let route = deep_links.take_pending().unwrap_or(Route::Feed {});
+            navigator().push(route);
         }
     });
 
@@ -0,0 +1,55 @@
+use dioxus::prelude::*;
+
+use crate::{
+    hooks::{use_api, use_token},
+    i18n::t,
+    oauth::{self, Callback},
+    views::{
+        auth::components::{AuthContainer, Error},
+        Route,
+    },
+};
+
+use super::components::Header;
+use ui::{forms::button::TransparentButton, layout::Column};
+
+/// Opened by the `bind://oauth/callback` link the provider redirects back to
+#[component]
+pub fn OAuthCallback(code: String, state: String, token: String, error: String) -> Element {
+    let api = use_api();
+    let mut auth_token = use_token();
+    let mut error_message = use_signal(|| None::<String>);
+
+    use_hook(move || {
+        let callback = match Callback::from_params(code, state, token, error) {
+            Ok(callback) => callback,
+            Err(err) => {
+                error_message.set(Some(err.message()));
+                return;
+            }
+        };
+        spawn(async move {
+            match oauth::complete(api, callback).await {
+                // Logged in, the auth layout takes it from here
+                Ok(token) => auth_token.set(Some(token)),
+                Err(err) => error_message.set(Some(err.message())),
+            }
+        });
+    });
+
+    rsx! {
+        AuthContainer {
+            Header { subtitle: t!("oauth-logging-in"), title: "Bind" }
+
+            Column { gap: "8px",
+                if error_message().is_none() {
+                    span { align_self: "center", color: "var(--text-secondary)", {t!("oauth-just-a-moment")} }
+                }
+                Error { error: error_message }
+                TransparentButton { onclick: move |_| { navigator().replace(Route::Login {}); },
+                    {t!("oauth-back-to-login")}
+                }
+            }
+        }
+    }
+}
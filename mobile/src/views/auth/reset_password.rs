@@ -4,8 +4,9 @@ use crate::views::auth::use_auth_form;
 use crate::views::Route;
 use crate::{
     hooks::use_api,
+    i18n::t,
     views::auth::{
-        components::{AuthContainer, Error},
+        components::{use_resend_cooldown, AuthContainer, Error},
         validation::validate_email,
     },
 };
@@ -14,6 +15,7 @@ use super::components::Header;
 use ui::{
     forms::{
         button::{SolidButton, TransparentButton},
+        form::{use_field, use_form, FieldOptions},
         input::Input,
     },
     icons::{EnvelopeIcon, LockIcon},
@@ -23,57 +25,61 @@ use ui::{
 #[component]
 pub fn ResetPassword() -> Element {
     let api = use_api();
-    let mut auth_form = use_auth_form();
-    let mut error = use_signal(|| None::<String>);
+    let auth_form = use_auth_form();
+    let mut cooldown = use_resend_cooldown(false);
 
-    let send_password_reset_code = use_callback(move |_| {
-        let email = auth_form.email();
-        if let Err(err) = validate_email(&email) {
-            error.set(Some(err));
-        }
+    let form = use_form();
+    let mut email = use_field(form, "email", auth_form.email, || {
+        FieldOptions::new().validate(|email: &String| validate_email(email))
+    });
 
-        spawn(async move {
-            match api.send_password_reset_code(&email).await {
-                Ok(_) => {
-                    navigator().push(Route::ResetPasswordConfirm { email });
-                }
-                Err(err) => {
-                    error.set(Some(err.message()));
-                }
-            }
+    let send_password_reset_code = move |_| {
+        form.submit(move || async move {
+            let result = api.send_password_reset_code(&email.value()).await;
+            cooldown.handle(&result);
+            result?;
+            navigator().push(Route::ResetPasswordConfirm {
+                email: email.value(),
+                code: String::new(),
+            });
+            Ok(())
         });
-    });
+    };
 
     rsx! {
         AuthContainer {
             Header {
-                title: "Password",
-                subtitle: "Reset your",
+                title: t!("reset-password-title"),
+                subtitle: t!("reset-password-subtitle"),
                 icon: rsx! {
                     LockIcon { size: 82 }
                 },
             }
 
             Input {
-                title: "Email",
-                placeholder: "Email",
+                title: t!("auth-email"),
+                placeholder: t!("auth-email"),
                 icon: rsx! {
                     EnvelopeIcon {}
                 },
                 input_type: "email",
-                value: auth_form.email(),
-                onchange: move |value| auth_form.set_email(value),
+                value: email.value(),
+                error: email.error(),
+                onchange: move |value| email.set(value),
+                onblur: move |_| email.touch(),
             }
 
             Column { gap: "8px",
+                // Disabled while the server is rate limiting requests for codes
                 SolidButton {
+                    disabled: form.is_pending() || cooldown.remaining() > 0,
                     onclick: send_password_reset_code,
-                    "Reset Password"
+                    {t!("auth-reset-password")}
                 }
                 TransparentButton { onclick: move |_| { navigator().push(Route::Login {}); },
-                    "Login"
+                    {t!("auth-login")}
                 }
-                Error { error }
+                Error { error: form.error() }
             }
         }
     }
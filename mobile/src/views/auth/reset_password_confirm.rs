@@ -1,81 +1,136 @@
 use dioxus::prelude::*;
 
-use super::components::{AuthContainer, Header};
-use crate::{hooks::use_api, views::Route};
+use super::components::{
+    use_resend_cooldown, AuthContainer, Error, Header, ResendButton, CODE_LIFETIME,
+};
+use crate::{
+    components::password_strength::{new_password_options, PasswordStrengthMeter},
+    hooks::{use_api, use_token},
+    i18n::t,
+    views::{auth::validation::required, Route},
+};
 use ui::{
     forms::{
-        button::{SolidButton, TransparentButton},
-        code_input::CodeInput,
-        input::Input,
+        button::TransparentButton,
+        code_input::{is_complete, CodeInput},
+        form::{use_field, use_form, FieldOptions, SubmitButton, SubmitError},
+        input::PasswordInput,
     },
     icons::LockIcon,
     layout::Column,
 };
 
+/// `code` is prefilled when opened from the link in the reset email
 #[component]
-pub fn ResetPasswordConfirm(email: String) -> Element {
+pub fn ResetPasswordConfirm(email: String, code: String) -> Element {
     let api = use_api();
+    let mut token = use_token();
+
+    let initial_code = code;
+    // The code was just sent, unless the screen was opened from the email
+    let mut cooldown = use_resend_cooldown(initial_code.is_empty());
 
-    let mut password = use_signal(String::new);
-    let mut code = use_signal(String::new);
+    let mut form = use_form();
+    let code = use_signal(|| initial_code.clone());
+    let mut code = use_field(form, "code", code, || {
+        FieldOptions::new().validate(|code: &String| {
+            required(code, t!("reset-password-enter-code"))?;
+            is_complete(code, 5)
+                .then_some(())
+                .ok_or_else(|| t!("reset-password-enter-code"))
+        })
+    });
+    let password = use_signal(String::new);
+    let strength = use_signal(|| None);
+    let mut password = use_field(form, "new_password", password, {
+        let email = email.clone();
+        move || new_password_options(strength, move || vec![email.clone()])
+    });
 
-    let mut error = use_signal(|| None::<String>);
+    let reset_password = {
+        let email = email.clone();
+        move |_| {
+            let email = email.clone();
+            form.submit(move || async move {
+                if cooldown.expired() {
+                    return Err(SubmitError::new(t!("reset-password-code-expired")));
+                }
+                api.reset_password(&email, &code.value(), &password.value())
+                    .await?;
 
-    let reset_password = use_callback(move |_| {
-        if password().is_empty() || code().is_empty() {
-            error.set(Some("Password and code cannot be empty".to_string()));
-            return;
+                // Log in with the new password, which takes the user to where they were going
+                match api.login_user(&email, &password.value()).await {
+                    Ok(response) => token.set(Some(response.token)),
+                    Err(err) => {
+                        tracing::error!("Failed to log in after resetting password: {err}");
+                        navigator().push(Route::Login {});
+                    }
+                }
+                Ok(())
+            });
         }
+    };
 
+    let resend = use_callback(move |_| {
         let email = email.clone();
         spawn(async move {
-            match api.reset_password(&email, &code(), &password()).await {
-                Ok(_) => {
-                    navigator().push(Route::Login {});
-                }
-                Err(err) => {
-                    error.set(Some(err.message()));
-                }
-            }
+            let result = api.send_password_reset_code(&email).await;
+            cooldown.handle(&result);
+            form.set_error(result.err().map(|err| err.message()));
         });
     });
 
+    let lifetime = CODE_LIFETIME.as_secs() / 60;
+
     rsx! {
         AuthContainer {
             Header {
-                title: "Password",
-                subtitle: "Reset your",
+                title: t!("reset-password-title"),
+                subtitle: t!("reset-password-subtitle"),
                 icon: rsx! {
                     LockIcon { size: 82 }
                 },
             }
 
-            CodeInput { length: 5, onchange: move |value| code.set(value) }
+            span { align_self: "center", color: "var(--text-secondary)", text_align: "center",
+                {t!("reset-password-instructions", minutes = lifetime)}
+            }
 
-            Input {
-                title: "Password",
-                placeholder: "Password",
-                icon: rsx! {
-                    LockIcon {}
-                },
-                input_type: "password",
-                value: password(),
+            CodeInput {
+                length: 5,
+                initial_value: initial_code,
+                error: code.error().is_some(),
+                onchange: move |value| code.set(value),
+            }
+
+            PasswordInput {
+                title: t!("reset-password-new-password"),
+                placeholder: t!("reset-password-new-password"),
+                autocomplete: "new-password",
+                value: password.value(),
+                error: password.error(),
                 onchange: move |value| password.set(value),
+                oninput: move |value| password.set(value),
+                onblur: move |_| password.touch(),
+            }
+            PasswordStrengthMeter {
+                password: password.value(),
+                strength,
+                estimating: password.is_validating(),
             }
 
             Column { gap: "8px",
-                SolidButton {
-                    onclick: reset_password,
-                    "Reset Password"
-                }
+                SubmitButton { form, onclick: reset_password, {t!("auth-reset-password")} }
+                ResendButton { cooldown, onclick: resend }
 
                 TransparentButton { onclick: move |_| { navigator().push(Route::ResetPassword {}); },
-                    "Go Back"
+                    {t!("auth-go-back")}
                 }
 
                 TransparentButton { onclick: move |_| { navigator().push(Route::Login {}); },
-                    "Login"
+                    {t!("auth-login")}
                 }
+                Error { error: form.error().or(code.error()) }
             }
         }
     }
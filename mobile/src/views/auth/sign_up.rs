@@ -1,80 +1,105 @@
+use std::time::Duration;
+
 use dioxus::{mobile::window, prelude::*};
 use regex::Regex;
 
 use super::components::Header;
+use crate::components::password_strength::{new_password_options, PasswordStrengthMeter};
 use crate::hooks::{use_api, use_keyboard_open, use_token};
+use crate::i18n::t;
 use crate::views::auth::components::{AuthContainer, Error};
-use crate::views::auth::use_auth_form;
-use crate::views::auth::validation::{validate_email, validate_password, validate_username};
-use crate::{api::ApiClient, views::Route};
+use crate::views::auth::validation::{validate_email, validate_username};
+use crate::views::auth::{use_auth_form, use_oauth_login};
+use crate::{
+    api::{types::user::OAuthProvider, ApiClient},
+    views::Route,
+};
 
 use ui::{
     forms::{
-        button::{SolidButton, TransparentButton, UnstyledButton},
+        button::{TransparentButton, UnstyledButton},
         code_input::CodeInput,
-        input::Input,
+        form::{use_field, use_form, FieldOptions, SubmitButton},
+        input::{Input, PasswordInput},
     },
-    icons::{AppleIcon, EnvelopeIcon, GoogleIcon, LockIcon, UserIcon},
+    icons::{AppleIcon, EnvelopeIcon, GoogleIcon, UserIcon},
     layout::Column,
 };
 
+/// How long to wait after the username changes before checking if it's taken
+const USERNAME_CHECK_DELAY: Duration = Duration::from_millis(400);
+
 #[component]
 pub fn SignUp() -> Element {
     let api = use_api();
     let nav = use_navigator();
-    let mut auth_form = use_auth_form();
-    let mut error = use_signal(|| None::<String>);
+    let auth_form = use_auth_form();
     let mut token = use_token();
 
     tracing::info!("Sign up screen");
 
     let keyboard_open = use_keyboard_open();
 
-    let send_email_verification = use_callback(move |_| {
-        if let Err(err) = validate_email(&auth_form.email()) {
-            error.set(Some(err));
-        } else if let Err(err) = validate_username(&auth_form.username()) {
-            error.set(Some(err));
-        } else if let Err(err) = validate_password(
-            &auth_form.email(),
-            &auth_form.username(),
-            &auth_form.password(),
-        ) {
-            error.set(Some(err));
-        } else {
-            error.set(None);
-            spawn(async move {
-                match api.send_email_verification(&auth_form.email()).await {
-                    Ok(_) => {
-                        nav.push(Route::VerifyEmail {
-                            email: auth_form.email(),
-                            username: auth_form.username(),
-                            password: auth_form.password(),
-                        });
-                    }
+    let form = use_form();
+    let mut email = use_field(form, "email", auth_form.email, || {
+        FieldOptions::new().validate(|email: &String| validate_email(email))
+    });
+    let mut username = use_field(form, "username", auth_form.username, || {
+        FieldOptions::new()
+            .validate(|username: &String| validate_username(username))
+            .validate_async(move |username: String| async move {
+                // Waits for the user to stop typing
+                tokio::time::sleep(USERNAME_CHECK_DELAY).await;
+                match api.username_available(&username).await {
+                    Ok(true) => Ok(()),
+                    Ok(false) => Err(t!("sign-up-username-taken")),
+                    // The server checks it again when signing up
                     Err(err) => {
-                        error.set(Some(err.message()));
+                        tracing::warn!("Failed to check if the username is available: {err}");
+                        Ok(())
                     }
                 }
-            });
-        }
+            })
+    });
+    let strength = use_signal(|| None);
+    let mut password = use_field(form, "password", auth_form.password, || {
+        new_password_options(strength, move || {
+            vec![
+                auth_form.email.peek().clone(),
+                auth_form.username.peek().clone(),
+            ]
+        })
     });
 
+    let login_with = use_oauth_login(form);
+
+    let send_email_verification = move |_| {
+        form.submit(move || async move {
+            api.send_email_verification(&email.value()).await?;
+            nav.push(Route::VerifyEmail {
+                email: email.value(),
+                username: username.value(),
+                password: password.value(),
+            });
+            Ok(())
+        });
+    };
+
     rsx! {
         AuthContainer {
-            Header { subtitle: "Welcome to", title: "Bind" }
+            Header { subtitle: t!("sign-up-welcome"), title: "Bind" }
 
             // OAuth
             if !keyboard_open() {
                 Column { gap: "24px",
 
-                    button { class: "solid", onclick: move |_| {},
+                    button { class: "solid", onclick: move |_| login_with.call(OAuthProvider::Google),
                         GoogleIcon {}
-                        "Sign up with Google"
+                        {t!("sign-up-with-google")}
                     }
-                    button { class: "solid", onclick: move |_| {},
+                    button { class: "solid", onclick: move |_| login_with.call(OAuthProvider::Apple),
                         AppleIcon {}
-                        "Sign up with Apple"
+                        {t!("sign-up-with-apple")}
                     }
                 }
 
@@ -85,53 +110,64 @@ pub fn SignUp() -> Element {
             Column { gap: "16px",
 
                 Input {
-                    title: "Email",
-                    placeholder: "Email",
+                    title: t!("auth-email"),
+                    placeholder: t!("auth-email"),
                     icon: rsx! {
                         EnvelopeIcon {}
                     },
                     input_type: "email",
-                    value: auth_form.email(),
-                    onchange: move |value| { auth_form.set_email(value) },
+                    value: email.value(),
+                    error: email.error(),
+                    autocomplete: "email",
+                    onchange: move |value| email.set(value),
+                    oninput: move |value| email.set(value),
+                    onblur: move |_| email.touch(),
                 }
                 Input {
-                    title: "Username",
-                    placeholder: "Username",
+                    title: t!("auth-username"),
+                    placeholder: t!("auth-username"),
                     icon: rsx! {
                         UserIcon {}
                     },
-                    value: auth_form.username(),
-                    onchange: move |value| {
-                        auth_form.set_username(value);
-                    },
+                    value: username.value(),
+                    error: username.error(),
+                    autocomplete: "username",
+                    onchange: move |value| username.set(value),
+                    oninput: move |value| username.set(value),
+                    onblur: move |_| username.touch(),
                 }
-                Input {
-                    title: "Password",
-                    placeholder: "Password",
-                    icon: rsx! {
-                        LockIcon {}
-                    },
-                    input_type: "password",
-                    value: auth_form.password(),
-                    onchange: move |value| {
-                        auth_form.set_password(value);
-                    },
+                if username.is_validating() {
+                    span { color: "var(--text-secondary)", font_size: "14px",
+                        {t!("sign-up-checking-username")}
+                    }
+                }
+                PasswordInput {
+                    title: t!("auth-password"),
+                    placeholder: t!("auth-password"),
+                    autocomplete: "new-password",
+                    value: password.value(),
+                    error: password.error(),
+                    onchange: move |value| password.set(value),
+                    oninput: move |value| password.set(value),
+                    onblur: move |_| password.touch(),
+                }
+                PasswordStrengthMeter {
+                    password: password.value(),
+                    strength,
+                    estimating: password.is_validating(),
                 }
 
                 // Actions
                 Column { gap: "8px", align: "stretch",
 
-                    SolidButton {
-                        onclick: move |_| { send_email_verification.call(()) },
-                        "Sign up"
-                    }
+                    SubmitButton { form, onclick: send_email_verification, {t!("auth-sign-up")} }
                     TransparentButton {
                         onclick: move |_| {
                             nav.push(Route::Login {});
                         },
-                        "Login"
+                        {t!("auth-login")}
                     }
-                    Error { error }
+                    Error { error: form.error() }
                 }
             }
         }
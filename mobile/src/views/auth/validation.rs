@@ -1,20 +1,30 @@
-use std::sync::LazyLock;
+use std::{sync::LazyLock, time::Duration};
 
 use zxcvbn::Score;
 
+use crate::i18n::t;
+
 static EMAIL_REGEX: LazyLock<regex::Regex> =
     LazyLock::new(|| regex::Regex::new(r"^\S+@\S+\.\S+$").unwrap());
 
 static USERNAME_REGEX: LazyLock<regex::Regex> =
     LazyLock::new(|| regex::Regex::new(r"^[a-zA-Z0-9_\.-]{2,48}$").unwrap());
 
+/// Checks that something was entered, returning the message otherwise
+pub fn required(value: &str, message: impl Into<String>) -> Result<(), String> {
+    if value.trim().is_empty() {
+        return Err(message.into());
+    }
+    Ok(())
+}
+
 pub fn validate_email(email: &str) -> Result<(), String> {
     if email.is_empty() {
-        return Err("Email cannot be empty".to_string());
+        return Err(t!("validation-email-empty"));
     }
 
     if !EMAIL_REGEX.is_match(email) {
-        return Err("Email is invalid".to_string());
+        return Err(t!("validation-email-invalid"));
     }
 
     Ok(())
@@ -22,53 +32,104 @@ pub fn validate_email(email: &str) -> Result<(), String> {
 
 pub fn validate_username(username: &str) -> Result<(), String> {
     if username.is_empty() {
-        return Err("Username cannot be empty".to_string());
+        return Err(t!("validation-username-empty"));
     }
     if username.len() < 2 {
-        return Err("Username must be at least 2 characters long".to_string());
+        return Err(t!("validation-username-too-short", min = 2));
     }
     if username.len() > 48 {
-        return Err("Username must be at most 48 characters long".to_string());
+        return Err(t!("validation-username-too-long", max = 48));
     }
 
     if !USERNAME_REGEX.is_match(username) {
-        return Err(
-            "Username must contain only letters, numbers, dots, dashes, and underscores"
-                .to_string(),
-        );
+        return Err(t!("validation-username-characters"));
     }
 
     Ok(())
 }
 
-pub fn validate_password(email: &str, username: &str, password: &str) -> Result<(), String> {
+pub fn validate_password(password: &str) -> Result<(), String> {
     if password.is_empty() {
-        return Err("Password cannot be empty".to_string());
+        return Err(t!("validation-password-empty"));
     }
     if password.len() < 8 || password.len() > 128 {
-        return Err("Password must be between 8 and 128 characters long".to_string());
+        return Err(t!("validation-password-length", min = 8, max = 128));
+    }
+
+    Ok(())
+}
+
+/// How hard a password is to guess, estimated by zxcvbn
+#[derive(Clone, Debug, PartialEq)]
+pub struct PasswordStrength {
+    /// From 0, guessed within a thousand tries, to 4
+    pub score: u8,
+    /// How long it would take to guess the password from a leaked hash
+    pub crack_time: Duration,
+    /// Why the password is weak, and how to make it stronger. These come from zxcvbn, which
+    /// only has them in English.
+    pub warning: Option<String>,
+    pub suggestions: Vec<String>,
+}
+
+impl PasswordStrength {
+    /// Estimates the strength of a password, which is less when it contains the user's email or
+    /// username. This takes a while for long passwords, see [Self::estimate_in_background].
+    pub fn estimate(password: &str, user_inputs: &[&str]) -> Self {
+        let estimate = zxcvbn::zxcvbn(password, user_inputs);
+        let feedback = estimate.feedback();
+        Self {
+            score: estimate.score().into(),
+            crack_time: estimate
+                .crack_times()
+                .offline_slow_hashing_1e4_per_second()
+                .into(),
+            warning: feedback.and_then(|feedback| feedback.warning().map(|x| x.to_string())),
+            suggestions: feedback
+                .map(|feedback| {
+                    feedback
+                        .suggestions()
+                        .iter()
+                        .map(|x| x.to_string())
+                        .collect()
+                })
+                .unwrap_or_default(),
+        }
+    }
+
+    /// Estimates the strength on a blocking thread, to keep the UI responsive while typing
+    pub async fn estimate_in_background(password: String, user_inputs: Vec<String>) -> Self {
+        tokio::task::spawn_blocking(move || {
+            let user_inputs = user_inputs.iter().map(String::as_str).collect::<Vec<_>>();
+            Self::estimate(&password, &user_inputs)
+        })
+        .await
+        .expect("estimating the password strength panicked")
     }
 
-    let password_estimate = zxcvbn::zxcvbn(&password, &[&username, &email]);
-    if password_estimate.score() <= Score::Two {
-        if let Some(feedback) = password_estimate.feedback() {
-            return Err(format!(
-                "{}. {}",
-                feedback
-                    .warning()
-                    .map(|x| x.to_string())
-                    .unwrap_or("Password is too weak".to_string()),
-                feedback
-                    .suggestions()
-                    .iter()
-                    .map(|x| x.to_string())
-                    .collect::<Vec<_>>()
-                    .join(". ")
-            ));
-        } else {
-            return Err("Password is too weak, try adding more letters, numbers and symbols. Avoid common words and phrases.".to_string());
+    pub fn is_strong_enough(&self) -> bool {
+        self.score > u8::from(Score::Two)
+    }
+
+    pub fn label(&self) -> String {
+        match self.score {
+            0 => t!("password-very-weak"),
+            1 => t!("password-weak"),
+            2 => t!("password-fair"),
+            3 => t!("password-strong"),
+            _ => t!("password-very-strong"),
         }
     }
 
-    Ok(())
+    /// Why the password is too weak
+    pub fn error(&self) -> String {
+        let warning = self
+            .warning
+            .clone()
+            .unwrap_or_else(|| t!("password-too-weak"));
+        if self.suggestions.is_empty() {
+            return t!("password-too-weak-hint", warning = warning);
+        }
+        format!("{warning} {}", self.suggestions.join(" "))
+    }
 }
@@ -3,16 +3,17 @@ use dioxus::prelude::*;
 use crate::{
     api::{types::user::UserRegisterRequest, ApiClient},
     hooks::{use_api, use_token},
+    i18n::t,
     platform,
     views::{
-        auth::components::{AuthContainer, Error, Header},
+        auth::components::{use_resend_cooldown, AuthContainer, Error, Header, ResendButton},
         Route,
     },
 };
 use ui::{
     forms::{
         button::{SolidButton, TransparentButton},
-        code_input::CodeInput,
+        code_input::{is_complete, CodeInput},
     },
     icons::EnvelopeIcon,
     layout::Column,
@@ -24,10 +25,31 @@ pub fn VerifyEmail(email: String, username: String, password: String) -> Element
     let mut token = use_token();
     let mut code = use_signal(String::new);
     let mut error = use_signal(|| None::<String>);
+    // Sign up sent the code before opening this screen
+    let mut cooldown = use_resend_cooldown(true);
+
+    let resend = use_callback({
+        let email = email.clone();
+        move |_| {
+            let email = email.clone();
+            spawn(async move {
+                let result = api.send_email_verification(&email).await;
+                cooldown.handle(&result);
+                match result {
+                    Ok(()) => error.set(None),
+                    Err(err) => error.set(Some(err.message())),
+                }
+            });
+        }
+    });
 
     let register = use_callback(move |_| {
-        if code().len() != 5 {
-            error.set(Some("Code must be 5 digits long".to_string()));
+        if !is_complete(&code(), 5) {
+            error.set(Some(t!("verify-email-code-length", length = 5)));
+            return;
+        }
+        if cooldown.expired() {
+            error.set(Some(t!("verify-email-code-expired")));
             return;
         }
         error.set(None);
@@ -62,8 +84,8 @@ pub fn VerifyEmail(email: String, username: String, password: String) -> Element
             gap: "48px",
 
             Header {
-                subtitle: "Verify your",
-                title: "Email",
+                subtitle: t!("verify-email-subtitle"),
+                title: t!("verify-email-title"),
                 icon: rsx! {
                     EnvelopeIcon { size: 82 }
                 },
@@ -71,23 +93,23 @@ pub fn VerifyEmail(email: String, username: String, password: String) -> Element
 
             CodeInput {
                 length: 5,
+                error: error().is_some(),
                 onchange: move |value| code.set(value),
+                oncomplete: move |_| register.call(()),
             }
 
             Column { gap: "8px",
                 Column { gap: "16px",
                     SolidButton { onclick: move |_| { register.call(()); },
-                        "Submit"
+                        {t!("verify-email-submit")}
                     }
                     SolidButton { onclick: move |_| { platform::open_email(); },
-                        "Open Email App"
+                        {t!("verify-email-open-app")}
                     }
                 }
-                TransparentButton { onclick: move |_| {},
-                    "Didn't receive the code? Resend"
-                }
+                ResendButton { cooldown, onclick: resend }
                 TransparentButton { onclick: move |_| { navigator().push(Route::SignUp {}); },
-                    "Go Back"
+                    {t!("auth-go-back")}
                 }
                 Error { error }
             }
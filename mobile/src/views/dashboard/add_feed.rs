@@ -4,6 +4,7 @@ use ui::{
     icons::SearchIcon,
 };
 
+use crate::i18n::t;
 use crate::views::dashboard::components::{FeedList, Header};
 
 #[component]
@@ -11,7 +12,7 @@ pub fn AddFeed() -> Element {
     let mut query = use_signal(String::new);
 
     rsx! {
-        Header { title: "Add New Feed" }
+        Header { title: t!("navbar-add-feed") }
 
         if query().is_empty() {
             FeedList { num: 16 }
@@ -23,7 +24,7 @@ pub fn AddFeed() -> Element {
             Input {
                 icon: rsx! { SearchIcon {} },
                 icon_position: IconPosition::Left,
-                placeholder: "Search for a name, topic, or URL",
+                placeholder: t!("add-feed-search"),
                 value: query(),
                 onchange: move |value| query.set(value),
             }
@@ -1,14 +1,41 @@
+use std::rc::Rc;
+
+use chrono::{DateTime, Utc};
 use dioxus::prelude::*;
-use ui::layout::*;
+use ui::{
+    gesture::{SwipeAction, SwipeActions},
+    layout::*,
+    list::VirtualList,
+    overlay::{use_overlays, MenuItem, Toast},
+};
+
+use crate::{
+    api::types::{
+        feed::{Feed, FeedItem, FeedItemEnclosure},
+        list::CreateListItemRequest,
+    },
+    components::{
+        enclosure::EnclosureBadge,
+        popup::{PopupList, PopupListItem},
+        time::{format_day, format_relative_time},
+    },
+    i18n::t,
+    platform::share_feed_item,
+    read_state::use_read_store,
+    settings::{use_settings, ItemAction},
+    store::{use_lists, use_store},
+};
 
 #[derive(Props, Clone, PartialEq)]
 pub struct FeedItemListItemProps {
-    pub image_url: String,
+    pub image_url: Option<String>,
     pub title: String,
     pub description: String,
     pub feed_name: String,
     pub created_at: String,
     pub unread: bool,
+    pub enclosure: Option<FeedItemEnclosure>,
+    pub onclick: Option<EventHandler>,
 }
 
 #[component]
@@ -20,52 +47,299 @@ pub fn FeedItemListItem(props: FeedItemListItemProps) -> Element {
         feed_name,
         created_at,
         unread,
+        enclosure,
+        onclick,
     } = props;
 
     rsx! {
-        Row {
-            padding: "10px 16px",
-            border_bottom: "1px solid var(--text-secondary)",
-            gap: "12px",
-            cross_align: "center",
-
-            img { src: image_url, width: "80px", height: "80px" }
-            Column { gap: "2px",
-                span {
-                    font_size: "16px",
-                    color: if unread { "var(--text)" } else { "var(--text-tertiary)" },
-                    "{title}"
+        div {
+            // Opened with the keyboard like a link, as it's one on screen readers
+            role: onclick.map(|_| "link"),
+            tabindex: onclick.map(|_| "0"),
+            onclick: move |_| {
+                if let Some(onclick) = onclick {
+                    onclick.call(());
                 }
-                span {
-                    font_size: "12px",
-                    color: if unread { "var(--text-secondary)" } else { "var(--text-tertiary)" },
-                    "({feed_name}) {created_at}"
+            },
+            onkeydown: move |ev| {
+                let Some(onclick) = onclick else {
+                    return;
+                };
+                if matches!(ev.key(), Key::Enter) || ev.key() == Key::Character(" ".into()) {
+                    ev.prevent_default();
+                    onclick.call(());
                 }
-                span {
-                    font_size: "12px",
-                    color: if unread { "var(--text-secondary)" } else { "var(--text-tertiary)" },
-                    "{description}"
+            },
+
+            Row {
+                padding: "10px 16px",
+                border_bottom: "1px solid var(--text-secondary)",
+                gap: "12px",
+                cross_align: "center",
+
+                if let Some(image_url) = image_url {
+                    img { src: image_url, alt: "", width: "80px", height: "80px" }
+                }
+                Column { gap: "2px",
+                    span {
+                        font_size: "16px",
+                        color: if unread { "var(--text)" } else { "var(--text-tertiary)" },
+                        "{title}"
+                    }
+                    span {
+                        font_size: "12px",
+                        color: if unread { "var(--text-secondary)" } else { "var(--text-tertiary)" },
+                        "({feed_name}) {created_at}"
+                    }
+                    span {
+                        font_size: "12px",
+                        color: if unread { "var(--text-secondary)" } else { "var(--text-tertiary)" },
+                        "{description}"
+                    }
+                    if let Some(enclosure) = enclosure {
+                        EnclosureBadge { enclosure }
+                    }
                 }
             }
         }
     }
 }
 
+/// Feed items with their read state, opening the reader when clicked. Only the items on screen
+/// are rendered, under a header for the day they were published.
+#[component]
+pub fn FeedItemList(
+    items: Vec<FeedItem>,
+    feeds: Vec<Feed>,
+    /// Shown before the items and scrolled with them
+    leading: Option<Element>,
+    /// Called once the last items are shown, to load more
+    onendreached: Option<Callback>,
+    /// List the items are shown from, which they can be archived from
+    list_id: Option<i32>,
+) -> Element {
+    let now = Utc::now();
+    let items: Rc<Vec<(FeedItem, Option<Feed>)>> = Rc::new(
+        items
+            .into_iter()
+            .map(|item| {
+                let feed = feeds.iter().find(|feed| feed.id == item.feed_id).cloned();
+                (item, feed)
+            })
+            .collect(),
+    );
+
+    rsx! {
+        VirtualList {
+            len: items.len(),
+            leading,
+            onendreached,
+            key_of: {
+                let items = items.clone();
+                move |index: usize| items[index].0.id.to_string()
+            },
+            section_of: {
+                let items = items.clone();
+                move |index: usize| {
+                    items[index]
+                        .0
+                        .published_at
+                        .map(|published_at| format_day(published_at, now))
+                        .unwrap_or_else(|| t!("day-undated"))
+                }
+            },
+            section_header: move |day: String| rsx! {
+                div {
+                    padding: "6px 16px",
+                    background: "var(--bg)",
+                    border_bottom: "1px solid var(--text-secondary)",
+                    font_size: "12px",
+                    color: "var(--text-secondary)",
+                    "{day}"
+                }
+            },
+            row: move |index: usize| {
+                let (item, feed) = items[index].clone();
+                rsx! {
+                    FeedItemRow { item, feed, list_id, now }
+                }
+            },
+        }
+    }
+}
+
+/// Row of an item, taking the actions chosen in the settings when it's swiped. Every action is
+/// in its context menu too.
 #[component]
-pub fn FeedItemList(num: usize) -> Element {
+fn FeedItemRow(
+    item: FeedItem,
+    feed: Option<Feed>,
+    list_id: Option<i32>,
+    now: DateTime<Utc>,
+) -> Element {
+    let store = use_store();
+    let mut read_store = use_read_store();
+    let mut overlays = use_overlays();
+    let settings = use_settings();
+    let item_id = item.id;
+    let read = read_store.is_read(item_id);
+
+    let toggle_read = use_callback(move |_| {
+        if read_store.is_read(item_id) {
+            read_store.mark_unread([item_id]);
+        } else {
+            read_store.mark_read([item_id]);
+        }
+    });
+    let save_to_list = use_callback(move |_| {
+        overlays.sheet(rsx! {
+            SaveToListPopup { item_id }
+        });
+    });
+    let share = use_callback({
+        let (link, title) = (item.link.clone(), item.title.clone());
+        move |_| {
+            if let Err(err) = share_feed_item(link.clone().unwrap_or_default(), title.clone()) {
+                overlays.toast(Toast::new(err));
+            }
+        }
+    });
+    let archive = use_callback(move |_| {
+        let (Some(list_id), Some(user)) = (list_id, store.user()) else {
+            return;
+        };
+        // Outlives the row, which is removed from the list right away
+        spawn_forever(async move {
+            match store.delete_list_item(list_id, item_id).await {
+                Ok(()) => overlays.toast(Toast::with_undo(t!("item-archived"), move |_| {
+                    let request = CreateListItemRequest {
+                        index: 0,
+                        owner: user.id,
+                        item: item_id,
+                    };
+                    spawn_forever(async move {
+                        if let Err(err) = store.create_list_item(list_id, &request).await {
+                            overlays.toast(Toast::new(err.message()));
+                        }
+                    });
+                })),
+                Err(err) => overlays.toast(Toast::new(err.message())),
+            }
+        });
+    });
+
+    // Label, color and callback of each action, if it can be taken on the item
+    let action = |action: ItemAction| match action {
+        ItemAction::ToggleRead => Some((
+            if read {
+                t!("item-mark-unread")
+            } else {
+                t!("item-mark-read")
+            },
+            "var(--text-secondary)",
+            toggle_read,
+        )),
+        ItemAction::SaveToList => {
+            Some((t!("item-save-to-list"), "var(--text-success)", save_to_list))
+        }
+        ItemAction::Share => Some((t!("item-share"), "var(--text)", share)),
+        ItemAction::Archive => list_id.map(|_| (t!("item-archive"), "var(--text-error)", archive)),
+        ItemAction::Nothing => None,
+    };
+    let swipe_action = |setting: ItemAction| {
+        action(setting).map(|(label, background, callback)| {
+            SwipeAction::new(label, background, move |_| callback.call(()))
+        })
+    };
+    let menu: Vec<MenuItem> = ItemAction::ALL
+        .into_iter()
+        .filter_map(|setting| {
+            let (label, _, callback) = action(setting)?;
+            let item = MenuItem::new(label, move |_| callback.call(()));
+            Some(if setting == ItemAction::Archive {
+                item.destructive()
+            } else {
+                item
+            })
+        })
+        .collect();
+
+    let open_items_in = settings.open_items_in;
+
     rsx! {
-        Column {
-            for i in 0..num {
+        SwipeActions {
+            swipe_left: swipe_action((settings.swipe_left)()),
+            swipe_right: swipe_action((settings.swipe_right)()),
+            menu,
+
+            div { "data-item-id": item.id,
                 FeedItemListItem {
-                    key: "{i}",
-                    image_url: "https://upload.wikimedia.org/wikipedia/en/d/d1/Plasticbeach452.jpg",
-                    title: "On Melancholy Hill",
-                    description: "On Melancholy Hill is the third single from British virtual band Gorillaz's third studio album, Plastic Beach.",
-                    feed_name: "Genius",
-                    created_at: "28 minutes ago",
-                    unread: i % 2 == 0,
+                    image_url: feed.as_ref().and_then(|feed| feed.icon.clone()),
+                    title: item.title.clone(),
+                    description: item.description.clone().unwrap_or_default(),
+                    feed_name: feed.map(|feed| feed.title).unwrap_or_default(),
+                    created_at: item
+                        .published_at
+                        .map(|published_at| format_relative_time(published_at, now))
+                        .unwrap_or_default(),
+                    unread: !read,
+                    enclosure: item.enclosure.clone(),
+                    onclick: move |_| {
+                        navigator().push(open_items_in().route(item_id));
+                    },
                 }
             }
         }
     }
 }
+
+/// Saves the item to one of the user's lists
+#[component]
+fn SaveToListPopup(item_id: i64) -> Element {
+    let store = use_store();
+    let mut overlays = use_overlays();
+    let lists = use_lists(0);
+    let lists = lists.read();
+
+    rsx! {
+        PopupList { label: t!("item-save-to-list"),
+            match (&lists.data, &lists.error) {
+                (Some(lists), _) if lists.is_empty() => rsx! {
+                    span { padding: "8px 24px", color: "var(--text-secondary)", {t!("lists-empty")} }
+                },
+                (Some(lists), _) => rsx! {
+                    for list in lists.clone() {
+                        PopupListItem {
+                            key: "{list.id}",
+                            title: list.title.clone(),
+                            onclick: move |_| {
+                                let Some(user) = store.user() else {
+                                    return;
+                                };
+                                let request = CreateListItemRequest {
+                                    index: 0,
+                                    owner: user.id,
+                                    item: item_id,
+                                };
+                                let title = list.title.clone();
+                                // Outlives the popup, which closes right away
+                                spawn_forever(async move {
+                                    match store.create_list_item(list.id, &request).await {
+                                        Ok(_) => overlays.toast(Toast::new(t!("item-saved-to-list", list = title))),
+                                        Err(err) => overlays.toast(Toast::new(err.message())),
+                                    }
+                                });
+                            },
+                        }
+                    }
+                },
+                (None, Some(err)) => rsx! {
+                    span { padding: "8px 24px", color: "var(--text-error)", "{err.message()}" }
+                },
+                (None, None) => rsx! {
+                    span { padding: "8px 24px", color: "var(--text-secondary)", {t!("lists-loading")} }
+                },
+            }
+        }
+    }
+}
@@ -1,8 +1,8 @@
 use dioxus::prelude::*;
 use reqwest::Url;
-use ui::layout::*;
+use ui::{layout::*, list::VirtualList};
 
-use crate::components::activity::Activity;
+use crate::{components::activity::FeedActivity, i18n::t, read_state::use_read_store};
 
 #[derive(Props, Clone, PartialEq)]
 pub struct FeedListItemProps {
@@ -10,6 +10,9 @@ pub struct FeedListItemProps {
     pub description: String,
     pub image_url: String,
     pub link: Url,
+    /// Feed the user is subscribed to, whose activity and unread items are shown
+    #[props(default)]
+    pub feed_id: Option<i32>,
 }
 
 #[component]
@@ -19,6 +22,7 @@ pub fn FeedListItem(props: FeedListItemProps) -> Element {
         description,
         image_url,
         link,
+        feed_id,
     } = props;
 
     let link_pretty = format!(
@@ -36,14 +40,24 @@ pub fn FeedListItem(props: FeedListItemProps) -> Element {
 
             img { src: image_url, max_width: "48px", max_height: "48px", width: "100%", align_self: "center" }
             Column {
-                span { font_size: "18px",
-                    "{title}"
+                Row { gap: "8px", cross_align: "center",
+                    span { font_size: "18px",
+                        "{title}"
+                    }
+                    if let Some(feed_id) = feed_id {
+                        UnreadBadge { feed_id }
+                    }
                 }
                 span { font_size: "14px", color: "var(--text-secondary)",
                     "{link_pretty}"
                 }
             }
-            Activity { points: vec![0.5, 0.8, 0., 0.5, 0.4, 1., 0.1], per_month: 24 }
+            // Wrapped so the description keeps its cell of the grid while there is no activity
+            div {
+                if let Some(feed_id) = feed_id {
+                    FeedActivity { feed_id }
+                }
+            }
             span { font_size: "14px", text_overflow: "ellipsis", overflow: "hidden", style: "display: -webkit-box; -webkit-box-orient: vertical; -webkit-line-clamp: 2;",
                 "{description}"
             }
@@ -51,19 +65,37 @@ pub fn FeedListItem(props: FeedListItemProps) -> Element {
     }
 }
 
+/// Number of unread items in the feed, loading its items if needed. Hidden while there are none.
+#[component]
+pub fn UnreadBadge(feed_id: i32) -> Element {
+    let mut read_store = use_read_store();
+    use_future(move || async move { read_store.load_feeds([feed_id]).await });
+
+    match read_store.feed_unread(feed_id) {
+        Some(unread) if unread > 0 => rsx! {
+            span { font_size: "12px", color: "var(--text-secondary)", white_space: "nowrap",
+                {t!("feed-unread-badge", count = unread)}
+            }
+        },
+        _ => rsx! {},
+    }
+}
+
 #[component]
 pub fn FeedList(num: usize) -> Element {
     rsx! {
-        Column {
-            for i in 0..num {
+        VirtualList {
+            len: num,
+            estimated_height: 120.0,
+            key_of: |index: usize| index.to_string(),
+            row: |_| rsx! {
                 FeedListItem {
-                    key: "{i}",
                     image_url: "https://www.nasa.gov/wp-content/themes/nasa/assets/images/nasa-logo@2x.png",
                     title: "NASA",
                     link: Url::parse("https://www.nasa.gov/rss/dyn/breaking_news.rss").unwrap(),
                     description: "Official National Aeronautics and Space Administration Website",
                 }
-            }
+            },
         }
     }
 }
@@ -1,8 +1,9 @@
 use dioxus::prelude::*;
-use ui::forms::button::UnstyledButton;
+use ui::forms::button::IconButton;
 use ui::icons::Cog6Tooth;
 use ui::layout::*;
 
+use crate::i18n::t;
 use crate::views::Route;
 
 #[component]
@@ -26,7 +27,7 @@ pub fn Header(
                 })}
             }
 
-            {onsettings.map(|onsettings| rsx! { UnstyledButton { onclick: move |_| { onsettings.call(()); }, padding: "14px 0px",
+            {onsettings.map(|onsettings| rsx! { IconButton { label: t!("settings-title"), onclick: move |_| { onsettings.call(()); }, padding: "14px 0px",
                 Cog6Tooth {}
             } })}
         }
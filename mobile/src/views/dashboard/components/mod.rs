@@ -3,7 +3,7 @@ mod feed_list;
 mod header;
 mod navbar;
 
-pub use feed_item_list::FeedItemList;
-pub use feed_list::FeedList;
+pub use feed_item_list::{FeedItemList, FeedItemListItem};
+pub use feed_list::{FeedList, UnreadBadge};
 pub use header::Header;
 pub use navbar::DashboardNavbar;
@@ -1,69 +1,83 @@
 use dioxus::prelude::*;
-use ui::icons::{Bars3Icon, BookmarkIcon, PlusIcon, QueueIcon, SearchIcon};
+use ui::{
+    icons::{ArrowDownTrayIcon, Bars3Icon, BookmarkIcon, PlusIcon, QueueIcon, SearchIcon},
+    overlay::use_overlays,
+};
 
 use crate::{
     components::{
         navbar::{Navbar, NavbarButton, NavbarButtonWithoutRoute},
-        popup::{use_popup_state, PopupList, PopupListItem, PopupState},
+        popup::{PopupList, PopupListItem},
     },
+    i18n::t,
     views::Route,
 };
 
 #[component]
 pub fn DashboardNavbar() -> Element {
-    let mut popup_state = use_popup_state();
+    let mut overlays = use_overlays();
     let nav = use_navigator();
 
     rsx! {
-        Navbar {
+        Navbar { label: t!("navbar-main"),
             NavbarButton {
+                label: t!("navbar-search"),
                 to: Route::Search {},
                 icon: |solid| rsx! {
                     SearchIcon { solid }
                 },
             }
             NavbarButton {
+                label: t!("navbar-feed"),
                 to: Route::Feed {},
                 icon: |solid| rsx! {
                     QueueIcon { solid }
                 },
             }
             NavbarButtonWithoutRoute {
+                label: t!("navbar-add"),
+                expanded: overlays.is_open(),
                 onclick: move |_| {
-                    match popup_state() {
-                        PopupState::Open(_) => {
-                            popup_state.set(PopupState::Close);
-                        }
-                        PopupState::Close => popup_state.set(PopupState::Open(
-                            rsx! { PopupList {
+                    if overlays.is_open() {
+                        overlays.close_all();
+                    } else {
+                        overlays.sheet(rsx! {
+                            PopupList { label: t!("navbar-add"),
                                 PopupListItem {
                                     icon: rsx! { QueueIcon {} },
-                                    title: "Add New Feed",
+                                    title: t!("navbar-add-feed"),
                                     onclick: move |_| { nav.push(Route::AddFeed {}); },
                                 }
                                 PopupListItem {
                                     icon: rsx! { BookmarkIcon {} },
-                                    title: "Create New List",
+                                    title: t!("navbar-create-list"),
                                     onclick: move |_| { nav.push(Route::AddFeed {}); },
                                 }
                                 PopupListItem {
                                     icon: rsx! { SearchIcon {} },
-                                    title: "Create New Index",
+                                    title: t!("navbar-create-index"),
                                     onclick: move |_| { nav.push(Route::AddFeed {}); },
                                 }
-                            } }
-                        )),
+                                PopupListItem {
+                                    icon: rsx! { ArrowDownTrayIcon {} },
+                                    title: t!("navbar-downloads"),
+                                    onclick: move |_| { nav.push(Route::Downloads {}); },
+                                }
+                            }
+                        });
                     }
                 },
                 PlusIcon {},
             }
             NavbarButton {
+                label: t!("navbar-saved"),
                 to: Route::List { id: 1 },
                 icon: |solid| rsx! {
                     BookmarkIcon { solid }
                 },
             }
             NavbarButton {
+                label: t!("navbar-lists"),
                 to: Route::List { id: 2 },
                 icon: |solid| rsx! {
                     Bars3Icon { solid }
@@ -0,0 +1,210 @@
+use dioxus::prelude::*;
+use ui::{
+    forms::button::{TransparentButton, UnstyledButton},
+    layout::*,
+};
+
+use crate::{
+    api::types::list::UserList,
+    components::enclosure::format_size,
+    i18n::t,
+    offline::{use_downloads, AutoDownloadRule, DownloadEntry, DownloadSettings, DownloadStatus},
+    store::use_lists,
+    views::{dashboard::components::Header, Route},
+};
+
+/// Storage quotas to choose from, in bytes
+const QUOTAS: [u64; 4] = [100_000_000, 500_000_000, 1_000_000_000, 5_000_000_000];
+
+/// Enclosure size caps to choose from, in bytes
+const ENCLOSURE_SIZE_CAPS: [u64; 3] = [50_000_000, 100_000_000, 500_000_000];
+
+#[component]
+pub fn Downloads() -> Element {
+    let downloads = use_downloads();
+    let library = downloads.library();
+    let lists = use_lists(0);
+
+    let library = library.read();
+    let used = format_size(library.used() as i64);
+    let quota = format_size(library.settings.quota as i64);
+
+    rsx! {
+        Header { title: t!("settings-downloads"), additional: t!("downloads-usage", used = used, quota = quota) }
+
+        Column { gap: "16px", padding: "16px 0px",
+            DownloadSettingsForm { settings: library.settings.clone() }
+
+            Column { gap: "8px", padding: "0px 16px",
+                span { font_size: "14px", color: "var(--text-secondary)",
+                    {t!("downloads-auto")}
+                }
+                if let Some(err) = &lists.read().error {
+                    span { color: "var(--text-error)", "{err.message()}" }
+                }
+                for list in lists.read().data.clone().unwrap_or_default() {
+                    AutoDownloadToggle {
+                        key: "{list.id}",
+                        enabled: library.has_rule(list.id),
+                        list,
+                    }
+                }
+            }
+
+            Column { border_top: "1px solid var(--text)",
+                if library.entries.is_empty() {
+                    span { padding: "16px", color: "var(--text-secondary)",
+                        {t!("downloads-empty")}
+                    }
+                }
+                for entry in library.entries.iter().rev().cloned() {
+                    DownloadListItem { key: "{entry.item_id}", entry }
+                }
+            }
+        }
+    }
+}
+
+#[component]
+fn DownloadSettingsForm(settings: DownloadSettings) -> Element {
+    let mut downloads = use_downloads();
+
+    rsx! {
+        Column { gap: "8px", padding: "0px 16px",
+            label { display: "flex", gap: "8px", align_items: "center",
+                input {
+                    r#type: "checkbox",
+                    checked: settings.enclosures,
+                    onchange: {
+                        let settings = settings.clone();
+                        move |event: FormEvent| {
+                            downloads.set_settings(DownloadSettings {
+                                enclosures: event.checked(),
+                                ..settings.clone()
+                            });
+                        }
+                    },
+                }
+                {t!("downloads-enclosures")}
+            }
+            if settings.enclosures {
+                Row { gap: "8px", cross_align: "center",
+                    span { color: "var(--text-secondary)", {t!("downloads-size-cap")} }
+                    for cap in ENCLOSURE_SIZE_CAPS {
+                        UnstyledButton {
+                            padding: "4px",
+                            text_decoration: if cap == settings.enclosure_size_cap { "underline" } else { "none" },
+                            onclick: {
+                                let settings = settings.clone();
+                                move |_| {
+                                    downloads.set_settings(DownloadSettings {
+                                        enclosure_size_cap: cap,
+                                        ..settings.clone()
+                                    });
+                                }
+                            },
+                            "{format_size(cap as i64)}"
+                        }
+                    }
+                }
+            }
+            Row { gap: "8px", cross_align: "center",
+                span { color: "var(--text-secondary)", {t!("downloads-quota")} }
+                for quota in QUOTAS {
+                    UnstyledButton {
+                        padding: "4px",
+                        text_decoration: if quota == settings.quota { "underline" } else { "none" },
+                        onclick: {
+                            let settings = settings.clone();
+                            move |_| {
+                                downloads.set_settings(DownloadSettings {
+                                    quota,
+                                    ..settings.clone()
+                                });
+                            }
+                        },
+                        "{format_size(quota as i64)}"
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[component]
+fn AutoDownloadToggle(list: UserList, enabled: bool) -> Element {
+    let mut downloads = use_downloads();
+
+    rsx! {
+        label { display: "flex", gap: "8px", align_items: "center",
+            input {
+                r#type: "checkbox",
+                checked: enabled,
+                onchange: {
+                    let list = list.clone();
+                    move |event: FormEvent| {
+                        let rule = AutoDownloadRule {
+                            list_id: list.id,
+                            title: list.title.clone(),
+                        };
+                        downloads.set_rule(rule, event.checked());
+                    }
+                },
+            }
+            "{list.title}"
+        }
+    }
+}
+
+#[component]
+fn DownloadListItem(entry: DownloadEntry) -> Element {
+    let mut downloads = use_downloads();
+    let item_id = entry.item_id;
+
+    let title = if entry.title.is_empty() {
+        t!("downloads-item", id = item_id)
+    } else {
+        entry.title.clone()
+    };
+    let status = match &entry.status {
+        DownloadStatus::Queued => t!("downloads-waiting"),
+        DownloadStatus::Downloading => t!(
+            "downloads-progress",
+            percent = (entry.progress() * 100.).round()
+        ),
+        DownloadStatus::Complete => format_size(entry.size() as i64),
+        DownloadStatus::Failed { message } => t!("downloads-failed", message = message.clone()),
+    };
+    let failed = matches!(entry.status, DownloadStatus::Failed { .. });
+
+    rsx! {
+        Row {
+            padding: "10px 16px",
+            border_bottom: "1px solid var(--text-secondary)",
+            gap: "8px",
+            cross_align: "center",
+            align: "space-between",
+
+            div {
+                flex: "1",
+                onclick: move |_| {
+                    navigator().push(Route::FeedReader { id: item_id });
+                },
+                Column { gap: "2px",
+                    span { font_size: "16px", "{title}" }
+                    span {
+                        font_size: "12px",
+                        color: if failed { "var(--text-error)" } else { "var(--text-secondary)" },
+                        "{status}"
+                    }
+                }
+            }
+            Row { gap: "4px",
+                if failed {
+                    TransparentButton { onclick: move |_| downloads.retry(item_id), {t!("downloads-retry")} }
+                }
+                TransparentButton { onclick: move |_| downloads.remove(item_id), {t!("remove")} }
+            }
+        }
+    }
+}
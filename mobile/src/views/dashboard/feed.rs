@@ -1,22 +1,133 @@
+use chrono::Utc;
 use dioxus::prelude::*;
+use ui::{
+    gesture::PullToRefresh,
+    overlay::{use_overlays, Toast},
+};
 
 use super::components::FeedItemList;
-use crate::{hooks::use_token, views::dashboard::components::Header};
+use crate::{
+    api::types::feed::{Feed as FeedData, FeedItem},
+    components::time::format_relative_time,
+    i18n::t,
+    read_state::{use_read_store, ReadState},
+    store::{use_store, QueryKey},
+    views::{dashboard::components::Header, Route},
+};
 
+/// Number of items added to the timeline each time its end is reached
+const TIMELINE_PAGE_LENGTH: usize = 50;
+
+/// Timeline of the most recent items from the feeds in the user's tags
 #[component]
 pub fn Feed() -> Element {
-    let mut token = use_token();
+    let store = use_store();
+    let mut read_store = use_read_store();
+    let mut overlays = use_overlays();
+    let mut refreshing = use_signal(|| false);
+    // Pages of each feed loaded into the timeline
+    let mut pages = use_signal(|| 1);
+
+    let mut timeline = use_resource(move || async move {
+        let tags = store.tags(0).await?;
+        let mut feed_ids: Vec<i32> = tags.iter().flat_map(ReadState::tag_feeds).collect();
+        feed_ids.sort();
+        feed_ids.dedup();
+
+        let mut feeds: Vec<FeedData> = vec![];
+        let mut items: Vec<FeedItem> = vec![];
+        for feed_id in feed_ids {
+            for page in 0..pages() {
+                let feed_items = store.feed_items(feed_id, page).await?;
+                read_store.add_feed_items(feed_id, &feed_items);
+                if feed_items.is_empty() {
+                    break;
+                }
+                items.extend(feed_items);
+            }
+            feeds.push(store.feed(feed_id).await?);
+        }
+        items.sort_by(|a, b| b.published_at.cmp(&a.published_at));
+        items.truncate(pages() * TIMELINE_PAGE_LENGTH);
+        // The unread count covers every item of the feeds, not only the pages shown
+        let feed_ids: Vec<i32> = feeds.iter().map(|feed| feed.id).collect();
+        spawn(async move { read_store.load_feeds(feed_ids).await });
+
+        Ok::<_, crate::api::ApiClientError>((feeds, items))
+    });
+
+    let refresh = move |_| {
+        refreshing.set(true);
+        spawn(async move {
+            let result = store
+                .refresh(|key| {
+                    matches!(
+                        key,
+                        QueryKey::Tags { .. } | QueryKey::Feed(_) | QueryKey::FeedItems { .. }
+                    )
+                })
+                .await;
+            if let Err(err) = result {
+                overlays.toast(Toast::new(err.message()));
+            }
+            timeline.restart();
+            refreshing.set(false);
+        });
+    };
+
+    let updated = store
+        .updated_at(|key| matches!(key, QueryKey::FeedItems { .. }))
+        .map(|updated_at| format_relative_time(updated_at, Utc::now()));
+    let unread = match &*timeline.read() {
+        Some(Ok((feeds, _))) => {
+            let unread: usize = feeds
+                .iter()
+                .filter_map(|feed| read_store.feed_unread(feed.id))
+                .sum();
+            Some(match &updated {
+                Some(updated) => t!(
+                    "feed-unread-updated",
+                    count = unread,
+                    updated = updated.clone()
+                ),
+                None => t!("feed-unread", count = unread),
+            })
+        }
+        _ => None,
+    };
 
     rsx! {
         Header {
-            title: "Some Feed",
-            additional: "(Updated 12 mins ago)",
+            title: t!("feed-title"),
+            additional: unread,
             onsettings: move |_| {
-                tracing::info!("Settings clicked");
-                token.set(None);
+                navigator().push(Route::Settings {});
             },
         }
 
-        FeedItemList { num: 1 }
+        match &*timeline.read() {
+            Some(Ok((feeds, items))) => {
+                // A shorter timeline means the feeds have no more items
+                let complete = items.len() < pages() * TIMELINE_PAGE_LENGTH;
+                rsx! {
+                    PullToRefresh {
+                        refreshing: refreshing(),
+                        status: updated.map(|updated| t!("refresh-updated", updated = updated)),
+                        onrefresh: refresh,
+                        FeedItemList {
+                            items: items.clone(),
+                            feeds: feeds.clone(),
+                            onendreached: move |_| {
+                                if !complete {
+                                    pages += 1;
+                                }
+                            },
+                        }
+                    }
+                }
+            }
+            Some(Err(err)) => return Err(err.clone().into()),
+            None => rsx! {},
+        }
     }
 }
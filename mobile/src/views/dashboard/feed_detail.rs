@@ -0,0 +1,338 @@
+use chrono::{Duration, Utc};
+use dioxus::prelude::*;
+use ui::{
+    forms::button::TransparentButton,
+    layout::*,
+    overlay::{use_overlays, Confirm, Toast},
+};
+
+use crate::{
+    api::{
+        types::{
+            feed::{Feed, FeedStatus},
+            tag::{TagChild, TagChildType, UpdateTagRequest, UserTag},
+        },
+        ApiClientError,
+    },
+    components::{
+        activity::FeedActivity,
+        popup::{PopupList, PopupListItem},
+        time::{format_relative_time, RelativeTime},
+    },
+    i18n::t,
+    read_state::{use_read_store, ReadState},
+    store::{use_feed, use_feed_items, use_store, use_tags},
+    views::{
+        dashboard::components::{FeedItemList, Header},
+        Route,
+    },
+};
+
+/// Ages in days offered when marking older items as read
+const MARK_READ_AGES: [i64; 3] = [1, 7, 30];
+
+#[component]
+pub fn FeedDetail(id: i32) -> Element {
+    let store = use_store();
+    let mut overlays = use_overlays();
+    let mut error = use_signal(|| None::<String>);
+
+    let feed = use_feed(id);
+    let items = use_feed_items(id, 0);
+    let mut read_store = use_read_store();
+    use_effect(move || {
+        if let Some(items) = &items.read().data {
+            read_store.add_feed_items(id, items);
+        }
+    });
+    // The unread count covers every item of the feed, not only the first page
+    use_future(move || async move { read_store.load_feeds([id]).await });
+
+    let unsubscribe = use_callback(move |_| {
+        spawn(async move {
+            match store.delete_feed(id).await {
+                Ok(_) => {
+                    navigator().push(Route::Feed {});
+                }
+                Err(err) => error.set(Some(err.message())),
+            }
+        });
+    });
+
+    let open_tags = use_callback(move |_| {
+        overlays.sheet(rsx! {
+            TagsPopup { feed_id: id }
+        });
+    });
+
+    let open_mark_read = use_callback(move |_| {
+        spawn(async move {
+            // Tags are offered too, so everything in them can be caught up on at once
+            let tags = match store.tags(0).await {
+                Ok(tags) => tags,
+                Err(err) => {
+                    error.set(Some(err.message()));
+                    return;
+                }
+            };
+            let tags: Vec<UserTag> = tags
+                .into_iter()
+                .filter(|tag| ReadState::tag_feeds(tag).any(|feed_id| feed_id == id))
+                .collect();
+            read_store.load_feeds([id]).await;
+            read_store.load_tags(&tags).await;
+
+            overlays.sheet(rsx! {
+                MarkReadPopup { feed_id: id, tags }
+            });
+        });
+    });
+
+    let feed = feed.read();
+    let items = items.read();
+    // A missing feed is expected after unsubscribing elsewhere, anything else is left to the
+    // error boundary
+    match feed.error.as_ref().or(items.error.as_ref()) {
+        Some(ApiClientError::NotFound(_)) | None => {}
+        Some(err) => return Err(err.clone().into()),
+    }
+    let title = match (&feed.data, &feed.error) {
+        (Some(feed), _) => feed.title.clone(),
+        (None, Some(_)) => t!("feed-detail-title"),
+        (None, None) => t!("loading"),
+    };
+    let unread = read_store
+        .feed_unread(id)
+        .map(|unread| t!("feed-unread", count = unread));
+
+    let leading = rsx! {
+        Column { gap: "16px", padding: "16px 0px", border_bottom: "1px solid var(--text)",
+            if let Some(feed) = &feed.data {
+                FeedSummary { feed: feed.clone() }
+                FeedHealth { feed: feed.clone() }
+            } else if feed.error.is_some() {
+                span { padding: "0px 16px", color: "var(--text-secondary)",
+                    {t!("feed-detail-unsubscribed")}
+                }
+            }
+
+            if items.data.is_some() {
+                FeedDetailActivity { feed_id: id }
+                Row { gap: "8px", padding: "0px 16px",
+                    TransparentButton { onclick: move |_| open_mark_read.call(()), {t!("feed-detail-mark-read")} }
+                    TransparentButton { onclick: move |_| open_tags.call(()), {t!("feed-detail-tags")} }
+                    TransparentButton {
+                        onclick: move |_| {
+                            overlays
+                                .confirm(
+                                    Confirm::new(
+                                            t!("feed-detail-unsubscribe-title"),
+                                            t!("feed-detail-unsubscribe-message"),
+                                            move |_| unsubscribe.call(()),
+                                        )
+                                        .confirm_label(t!("feed-detail-unsubscribe"))
+                                        .destructive(),
+                                );
+                        },
+                        {t!("feed-detail-unsubscribe")}
+                    }
+                }
+                if let Some(error) = error() {
+                    span { padding: "0px 16px", color: "var(--text-error)", "{error}" }
+                }
+            }
+        }
+    };
+
+    rsx! {
+        Header { title, additional: unread }
+
+        FeedItemList {
+            items: items.data.clone().unwrap_or_default(),
+            feeds: feed.data.clone().into_iter().collect::<Vec<_>>(),
+            leading,
+        }
+    }
+}
+
+/// Toggles the feed in each of the user's tags. Reads the tags from the store, so changes show
+/// immediately.
+#[component]
+fn TagsPopup(feed_id: i32) -> Element {
+    let store = use_store();
+    let mut read_store = use_read_store();
+    let tags = use_tags(0);
+    let mut error = use_signal(|| None::<String>);
+
+    use_effect(move || {
+        let Some(tags) = tags.read().data.clone() else {
+            return;
+        };
+        spawn(async move { read_store.load_tags(&tags).await });
+    });
+
+    let child = TagChild {
+        type_: TagChildType::Feed,
+        id: feed_id,
+    };
+    let tags = tags.read();
+
+    rsx! {
+        if let Some(err) = tags.error.as_ref().map(ApiClientError::message).or(error()) {
+            span { padding: "16px", color: "var(--text-error)", "{err}" }
+        }
+        PopupList { label: t!("feed-detail-tags"),
+            for tag in tags.data.clone().unwrap_or_default() {
+                PopupListItem {
+                    key: "{tag.id}",
+                    title: tag.title.clone(),
+                    subtitle: match (tag.children.contains(&child), read_store.tag_unread(&tag)) {
+                        (true, unread) => t!("tags-tagged-unread", count = unread),
+                        (false, unread) => t!("tags-unread", count = unread),
+                    },
+                    onclick: {
+                        let child = child.clone();
+                        let tagged = tag.children.contains(&child);
+                        move |_| {
+                            let request = UpdateTagRequest {
+                                title: None,
+                                children_to_add: (!tagged).then(|| vec![child.clone()]),
+                                children_to_remove: tagged.then(|| vec![child.clone()]),
+                            };
+                            spawn(async move {
+                                if let Err(err) = store.update_tag(tag.id, &request).await {
+                                    error.set(Some(err.message()));
+                                }
+                            });
+                        }
+                    },
+                }
+            }
+        }
+    }
+}
+
+#[component]
+fn FeedSummary(feed: Feed) -> Element {
+    let domain = feed.domain.clone().unwrap_or(feed.link.clone());
+
+    rsx! {
+        Row { gap: "12px", padding: "0px 16px", cross_align: "center",
+            if let Some(icon) = feed.icon {
+                img { src: icon, max_width: "48px", max_height: "48px", width: "100%" }
+            }
+            Column { gap: "2px",
+                span { font_size: "18px", "{feed.title}" }
+                span { font_size: "14px", color: "var(--text-secondary)", "{domain}" }
+                if let Some(language) = feed.language {
+                    span { font_size: "12px", color: "var(--text-tertiary)", text_transform: "uppercase",
+                        "{language}"
+                    }
+                }
+            }
+        }
+        if !feed.description.is_empty() {
+            p { margin: "0px", padding: "0px 16px", font_size: "14px", "{feed.description}" }
+        }
+    }
+}
+
+#[component]
+fn FeedHealth(feed: Feed) -> Element {
+    let now = Utc::now();
+    let status = match feed.status {
+        FeedStatus::Active => t!("feed-status-active"),
+        FeedStatus::Completed => t!("feed-status-completed"),
+        FeedStatus::Suspended => t!("feed-status-suspended"),
+        FeedStatus::Broken => t!("feed-status-broken"),
+    };
+    let last_success = format_relative_time(feed.successful_fetch_at, now);
+
+    rsx! {
+        Column { gap: "4px", padding: "0px 16px",
+            if feed.status == FeedStatus::Broken {
+                span { color: "var(--text-error)",
+                    {t!("feed-health-broken", updated = last_success)}
+                }
+            }
+            Row { gap: "8px",
+                span { color: "var(--text-secondary)", {t!("feed-health-status")} }
+                span { "{status}" }
+            }
+            Row { gap: "8px",
+                span { color: "var(--text-secondary)", {t!("feed-health-last-fetched")} }
+                RelativeTime { time: feed.fetched_at }
+            }
+            Row { gap: "8px",
+                span { color: "var(--text-secondary)", {t!("feed-health-last-success")} }
+                RelativeTime { time: feed.successful_fetch_at }
+            }
+            if feed.status == FeedStatus::Active {
+                Row { gap: "8px",
+                    span { color: "var(--text-secondary)", {t!("feed-health-next-fetch")} }
+                    RelativeTime { time: feed.next_fetch_at }
+                }
+            }
+        }
+    }
+}
+
+#[component]
+fn FeedDetailActivity(feed_id: i32) -> Element {
+    rsx! {
+        Row { padding: "0px 16px", gap: "8px", cross_align: "center",
+            span { font_size: "14px", color: "var(--text-secondary)", {t!("feed-detail-activity")} }
+            FeedActivity { feed_id, width: 96, horizontal: true }
+        }
+    }
+}
+
+/// Options for marking the feed's items, or everything in one of its tags, as read
+#[component]
+fn MarkReadPopup(feed_id: i32, tags: Vec<UserTag>) -> Element {
+    let mut read_store = use_read_store();
+    let mut overlays = use_overlays();
+
+    let mut mark_read = move |marked: Vec<i64>| {
+        overlays.toast(Toast::with_undo(
+            t!("mark-read-done", count = marked.len()),
+            move |_| read_store.mark_unread(marked.clone()),
+        ));
+    };
+    let mark_on_scroll = read_store.mark_on_scroll();
+
+    rsx! {
+        PopupList { label: t!("feed-detail-mark-read"),
+            PopupListItem {
+                title: t!("mark-read-all"),
+                onclick: move |_| mark_read(read_store.mark_feed_read(feed_id, None)),
+            }
+            for days in MARK_READ_AGES {
+                PopupListItem {
+                    key: "{days}",
+                    title: t!("mark-read-older-than", days = days),
+                    onclick: move |_| {
+                        let before = Utc::now() - Duration::days(days);
+                        mark_read(read_store.mark_feed_read(feed_id, Some(before)));
+                    },
+                }
+            }
+            for tag in tags {
+                PopupListItem {
+                    key: "tag-{tag.id}",
+                    title: t!("mark-read-tag", tag = tag.title.clone()),
+                    subtitle: t!("tags-unread", count = read_store.tag_unread(&tag)),
+                    onclick: {
+                        let tag = tag.clone();
+                        move |_| mark_read(read_store.mark_tag_read(&tag, None))
+                    },
+                }
+            }
+            PopupListItem {
+                title: t!("mark-read-on-scroll"),
+                subtitle: if mark_on_scroll { t!("on") } else { t!("off") },
+                onclick: move |_| read_store.set_mark_on_scroll(!mark_on_scroll),
+            }
+        }
+    }
+}
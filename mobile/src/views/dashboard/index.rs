@@ -0,0 +1,90 @@
+use dioxus::prelude::*;
+use ui::layout::*;
+
+use crate::{
+    api::types::{feed::Feed, search::SearchRequest},
+    hooks::use_api,
+    i18n::t,
+    read_state::use_read_store,
+    store::use_index,
+    views::{
+        dashboard::components::{Header, UnreadBadge},
+        Route,
+    },
+};
+
+/// Feeds matching the query of an index
+#[component]
+pub fn Index(id: i32) -> Element {
+    let api = use_api();
+    let index = use_index(id);
+    let mut read_store = use_read_store();
+    use_future(move || async move { read_store.load_indexes([id]).await });
+
+    let feeds = use_resource(move || async move {
+        let request = index.read().data.as_ref().map(|index| SearchRequest {
+            query: index.query.clone(),
+            sort: index.sort.clone().into(),
+        })?;
+        Some(api.search(&request, 0).await)
+    });
+
+    let index = index.read();
+    if let Some(err) = &index.error {
+        return Err(err.clone().into());
+    }
+    let title = match &index.data {
+        Some(index) => format!("{} {}", index.icon.icon, index.title),
+        None => t!("loading"),
+    };
+    let unread = read_store
+        .index_unread(id)
+        .map(|unread| t!("feed-unread", count = unread));
+
+    rsx! {
+        Header { title, additional: unread }
+
+        if let Some(description) = index.data.as_ref().and_then(|index| index.description.clone()) {
+            p { margin: "0px", padding: "16px 16px 0px 16px", font_size: "14px", "{description}" }
+        }
+
+        Column { padding: "16px 0px",
+            match &*feeds.read() {
+                Some(Some(Ok(feeds))) => rsx! {
+                    if feeds.is_empty() {
+                        span { padding: "0px 16px", color: "var(--text-secondary)", {t!("index-no-feeds")} }
+                    }
+                    for feed in feeds.clone() {
+                        IndexFeed { key: "{feed.id}", feed }
+                    }
+                },
+                Some(Some(Err(err))) => return Err(err.clone().into()),
+                _ => rsx! {},
+            }
+        }
+    }
+}
+
+#[component]
+fn IndexFeed(feed: Feed) -> Element {
+    let id = feed.id;
+    let domain = feed.domain.clone().unwrap_or(feed.link.clone());
+
+    rsx! {
+        div { onclick: move |_| { navigator().push(Route::FeedDetail { id }); },
+            Row { padding: "10px 16px", gap: "12px", cross_align: "center",
+                border_bottom: "1px solid var(--text-secondary)",
+                if let Some(icon) = feed.icon {
+                    img { src: icon, width: "32px", height: "32px" }
+                }
+                Column { gap: "2px",
+                    Row { gap: "8px", cross_align: "center",
+                        span { font_size: "16px", "{feed.title}" }
+                        UnreadBadge { feed_id: id }
+                    }
+                    span { font_size: "12px", color: "var(--text-secondary)", "{domain}" }
+                }
+            }
+        }
+    }
+}
@@ -1,22 +1,82 @@
+use chrono::Utc;
 use dioxus::prelude::*;
+use ui::{
+    gesture::PullToRefresh,
+    overlay::{use_overlays, Toast},
+};
 
 use super::components::FeedItemList;
-use crate::{hooks::use_token, views::dashboard::components::Header};
+use crate::{
+    components::time::format_relative_time,
+    i18n::t,
+    store::{use_list, use_list_items, use_store, QueryKey},
+    views::{dashboard::components::Header, Route},
+};
 
 #[component]
 pub fn List(id: usize) -> Element {
-    let mut token = use_token();
+    let store = use_store();
+    let mut overlays = use_overlays();
+    let list = use_list(id as i32);
+    let items = use_list_items(id as i32, 0);
+    let mut refreshing = use_signal(|| false);
+
+    let refresh = move |_| {
+        refreshing.set(true);
+        spawn(async move {
+            let list_id = id as i32;
+            let result = store
+                .refresh(|key| {
+                    *key == QueryKey::List(list_id)
+                        || matches!(key, QueryKey::ListItems { list_id: other, .. } if *other == list_id)
+                })
+                .await;
+            if let Err(err) = result {
+                overlays.toast(Toast::new(err.message()));
+            }
+            refreshing.set(false);
+        });
+    };
+
+    let list = list.read();
+    let items = items.read();
+    if let Some(err) = list.error.as_ref().or(items.error.as_ref()) {
+        return Err(err.clone().into());
+    }
+    let title = match &list.data {
+        Some(list) => list.title.clone(),
+        None => t!("loading"),
+    };
+    let updated = store
+        .updated_at(
+            |key| matches!(key, QueryKey::ListItems { list_id, .. } if *list_id == id as i32),
+        )
+        .map(|updated_at| format_relative_time(updated_at, Utc::now()));
+    let count = items.data.as_ref().map(|items| match &updated {
+        Some(updated) => t!(
+            "list-items-updated",
+            count = items.len(),
+            updated = updated.clone()
+        ),
+        None => t!("list-items", count = items.len()),
+    });
 
     rsx! {
         Header {
-            title: "Read Later",
-            additional: "(16 items)",
+            title,
+            additional: count,
             onsettings: move |_| {
-                tracing::info!("Settings clicked");
-                token.set(None);
+                navigator().push(Route::Settings {});
             },
         }
 
-        FeedItemList { num: 0 }
+        if let Some(items) = &items.data {
+            PullToRefresh {
+                refreshing: refreshing(),
+                status: updated.map(|updated| t!("refresh-updated", updated = updated)),
+                onrefresh: refresh,
+                FeedItemList { items: items.clone(), feeds: vec![], list_id: id as i32 }
+            }
+        }
     }
 }
@@ -2,39 +2,67 @@ use dioxus::prelude::*;
 
 use crate::{
     components::{
+        error::AppErrorBoundary,
         navbar::{Navbar, NavbarButton, NavbarButtonWithoutRoute},
-        popup::{use_popup_state, Popup, PopupList, PopupListItem, PopupState},
+        player::MiniPlayer,
     },
     hooks::{use_keyboard_open, use_token},
     platform::init_back_press_listener,
+    store::use_store,
     views::{dashboard::components::DashboardNavbar, Route},
 };
-use ui::icons::{Bars3Icon, BookmarkIcon, PlusIcon, QueueIcon, SearchIcon};
+use ui::{
+    icons::{Bars3Icon, BookmarkIcon, PlusIcon, QueueIcon, SearchIcon},
+    overlay::{use_overlays, OverlayHost},
+};
 
 mod add_feed;
 mod components;
+mod downloads;
 mod feed;
+mod feed_detail;
+mod index;
 mod list;
 mod search;
+mod settings;
+mod shared_link;
+mod up_next;
 
 use components::Header;
 
 pub use add_feed::AddFeed;
+pub use downloads::Downloads;
 pub use feed::Feed;
+pub use feed_detail::FeedDetail;
+pub use index::Index;
 pub use list::List;
 pub use search::Search;
+pub use settings::{Accounts, ChangePassword, ImportSubscriptions, Settings};
+pub use shared_link::SharedLink;
+pub use up_next::UpNext;
 
 #[component]
 pub fn DashboardLayout() -> Element {
     let keyboard_open = use_keyboard_open();
     let mut token = use_token();
+    let accounts = use_store().accounts();
+    let route = use_route::<Route>();
+    let mut overlays = use_overlays();
+
+    // Overlays belong to the screen they were opened on
+    use_effect(use_reactive((&route,), move |_| overlays.close_all()));
 
     tracing::info!("Dashboard screen");
 
     // TODO: better way to redirect to sign up if not logged in?
     use_effect(move || {
         if token().is_none() {
-            navigator().push(Route::SignUp {});
+            // Logging in is more likely when the user is already logged in to other accounts
+            if accounts.peek().is_empty() {
+                navigator().push(Route::SignUp {});
+            } else {
+                navigator().push(Route::Login {});
+            }
         }
     });
 
@@ -43,11 +71,13 @@ pub fn DashboardLayout() -> Element {
             id: "dashboard",
 
             main {
-                Outlet::<Route> {}
-                Popup {}
+                // Keyed so an error on one screen doesn't follow the user to the next
+                AppErrorBoundary { key: "{route}", Outlet::<Route> {} }
+                OverlayHost {}
             }
 
             if !keyboard_open() {
+                MiniPlayer {}
                 DashboardNavbar {}
             }
         }
@@ -1,12 +1,13 @@
 use dioxus::prelude::*;
 
 use super::components::FeedItemList;
+use crate::i18n::t;
 use crate::views::dashboard::components::Header;
 
 #[allow(non_snake_case)]
 pub fn Search() -> Element {
     rsx! {
-        Header { title: "Search" }
-        FeedItemList { num: 6 }
+        Header { title: t!("navbar-search") }
+        FeedItemList { items: vec![], feeds: vec![] }
     }
 }
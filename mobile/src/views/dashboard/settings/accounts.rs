@@ -0,0 +1,67 @@
+use dioxus::prelude::*;
+use ui::{
+    forms::button::{SolidButton, TransparentButton},
+    layout::*,
+};
+
+use crate::{
+    i18n::t,
+    store::{use_store, Account},
+    views::dashboard::components::Header,
+};
+
+/// Accounts the user is logged in to, to switch between them, add another or log out
+#[component]
+pub fn Accounts() -> Element {
+    let store = use_store();
+    let accounts = store.accounts();
+    let active = store.active_account();
+
+    rsx! {
+        Header { title: t!("accounts-title") }
+
+        Column {
+            for account in accounts() {
+                AccountListItem {
+                    key: "{account.id}",
+                    active: active.read().as_ref().is_some_and(|active| active.id == account.id),
+                    account,
+                }
+            }
+        }
+
+        Column { gap: "12px", padding: "16px", align: "stretch",
+            SolidButton { onclick: move |_| store.add_account(), {t!("accounts-add")} }
+            TransparentButton { onclick: move |_| store.logout(), {t!("settings-log-out")} }
+        }
+    }
+}
+
+#[component]
+fn AccountListItem(account: Account, active: bool) -> Element {
+    let store = use_store();
+    let id = account.id.clone();
+
+    rsx! {
+        Row {
+            padding: "10px 16px",
+            border_bottom: "1px solid var(--text-secondary)",
+            gap: "8px",
+            cross_align: "center",
+            align: "space-between",
+
+            Column { gap: "2px",
+                span { font_size: "16px", "{account.username}" }
+                span { font_size: "12px", color: "var(--text-secondary)", "{account.email}" }
+                if account.is_self_hosted() {
+                    span { font_size: "12px", color: "var(--text-secondary)", "{account.server}" }
+                }
+            }
+            if active {
+                span { font_size: "14px", color: "var(--text-secondary)", {t!("accounts-active")} }
+            } else {
+                TransparentButton { onclick: move |_| store.switch_account(&id), {t!("accounts-switch")} }
+            }
+        }
+    }
+}
@@ -0,0 +1,104 @@
+use dioxus::prelude::*;
+use ui::{
+    forms::{
+        button::TransparentButton,
+        form::{use_field, use_form, FieldOptions, SubmitButton},
+        input::PasswordInput,
+    },
+    layout::Column,
+    overlay::{use_overlays, Toast},
+};
+
+use crate::{
+    components::password_strength::{new_password_options, PasswordStrengthMeter},
+    hooks::use_api,
+    i18n::t,
+    store::use_auth_user,
+    views::{auth::validation::required, dashboard::components::Header},
+};
+
+#[component]
+pub fn ChangePassword() -> Element {
+    let api = use_api();
+    let user = use_auth_user();
+    let mut overlays = use_overlays();
+
+    let form = use_form();
+    let current = use_signal(String::new);
+    let mut current = use_field(form, "current_password", current, || {
+        FieldOptions::new()
+            .validate(|current: &String| required(current, t!("change-password-enter-current")))
+    });
+    let new = use_signal(String::new);
+    let strength = use_signal(|| None);
+    let mut new = use_field(form, "new_password", new, move || {
+        new_password_options(strength, move || {
+            user.peek()
+                .iter()
+                .flat_map(|user| [user.email.clone(), user.username.clone()])
+                .collect()
+        })
+    });
+    let confirmation = use_signal(String::new);
+    let mut confirmation = use_field(form, "confirmation", confirmation, move || {
+        FieldOptions::new().validate(move |confirmation: &String| {
+            if *confirmation != new.value() {
+                return Err(t!("change-password-mismatch"));
+            }
+            Ok(())
+        })
+    });
+
+    let change_password = move |_| {
+        form.submit(move || async move {
+            api.change_password(&current.value(), &new.value()).await?;
+            overlays.toast(Toast::new(t!("change-password-done")));
+            navigator().go_back();
+            Ok(())
+        });
+    };
+
+    rsx! {
+        Header { title: t!("settings-change-password") }
+
+        Column { gap: "24px", padding: "24px 16px", align: "stretch",
+            PasswordInput {
+                title: t!("change-password-current"),
+                placeholder: t!("change-password-current"),
+                autocomplete: "current-password",
+                value: current.value(),
+                error: current.error(),
+                onchange: move |value| current.set(value),
+                onblur: move |_| current.touch(),
+            }
+            PasswordInput {
+                title: t!("change-password-new"),
+                placeholder: t!("change-password-new"),
+                autocomplete: "new-password",
+                value: new.value(),
+                error: new.error(),
+                onchange: move |value| new.set(value),
+                oninput: move |value| new.set(value),
+                onblur: move |_| new.touch(),
+            }
+            PasswordStrengthMeter { password: new.value(), strength, estimating: new.is_validating() }
+            PasswordInput {
+                title: t!("change-password-confirm"),
+                placeholder: t!("change-password-confirm"),
+                autocomplete: "new-password",
+                value: confirmation.value(),
+                error: confirmation.error(),
+                onchange: move |value| confirmation.set(value),
+                onblur: move |_| confirmation.touch(),
+            }
+
+            Column { gap: "12px", align: "stretch",
+                SubmitButton { form, onclick: change_password, {t!("settings-change-password")} }
+                TransparentButton { onclick: move |_| navigator().go_back(), {t!("ui-cancel")} }
+                if let Some(error) = form.error() {
+                    span { color: "var(--text-error)", text_align: "center", "{error}" }
+                }
+            }
+        }
+    }
+}
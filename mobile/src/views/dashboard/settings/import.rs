@@ -0,0 +1,133 @@
+use dioxus::prelude::*;
+use ui::{
+    forms::button::{SolidButton, TransparentButton},
+    layout::*,
+};
+
+use crate::{
+    i18n::t,
+    opml::{self, Outline},
+    store::use_store,
+    views::dashboard::components::Header,
+};
+
+#[derive(Clone, Debug, PartialEq)]
+enum ImportStatus {
+    Waiting,
+    Subscribed,
+    Failed(String),
+}
+
+/// Subscribes to the feeds in an OPML file exported from another feed reader
+#[component]
+pub fn ImportSubscriptions() -> Element {
+    let store = use_store();
+    let mut outlines = use_signal(Vec::<(Outline, ImportStatus)>::new);
+    let mut error = use_signal(|| None::<String>);
+    let mut importing = use_signal(|| false);
+
+    let read_file = move |event: FormEvent| async move {
+        let Some(files) = event.files() else {
+            return;
+        };
+        let Some(name) = files.files().into_iter().next() else {
+            return;
+        };
+        match files.read_file_to_string(&name).await {
+            Some(contents) => {
+                let parsed = opml::parse(&contents);
+                error.set(parsed.is_empty().then(|| t!("import-no-feeds")));
+                outlines.set(
+                    parsed
+                        .into_iter()
+                        .map(|outline| (outline, ImportStatus::Waiting))
+                        .collect(),
+                );
+            }
+            None => error.set(Some(t!("import-read-failed", name = name))),
+        }
+    };
+
+    let import = move |_| {
+        importing.set(true);
+        spawn(async move {
+            let count = outlines.peek().len();
+            for index in 0..count {
+                let (outline, status) = outlines.peek()[index].clone();
+                if status == ImportStatus::Subscribed {
+                    continue;
+                }
+                let status = match store.create_feed(&outline.xml_url).await {
+                    Ok(_) => ImportStatus::Subscribed,
+                    Err(err) => ImportStatus::Failed(err.message()),
+                };
+                outlines.write()[index].1 = status;
+            }
+            importing.set(false);
+        });
+    };
+
+    let subscribed = outlines
+        .read()
+        .iter()
+        .filter(|(_, status)| *status == ImportStatus::Subscribed)
+        .count();
+    let total = outlines.read().len();
+
+    rsx! {
+        Header { title: t!("import-title"), additional: (total > 0).then(|| t!("import-progress", subscribed = subscribed, total = total)) }
+
+        Column { gap: "12px", padding: "16px",
+            span { color: "var(--text-secondary)",
+                {t!("import-description")}
+            }
+            input {
+                r#type: "file",
+                accept: ".opml,.xml,text/xml,text/x-opml",
+                disabled: importing(),
+                onchange: read_file,
+            }
+            if let Some(error) = error() {
+                span { color: "var(--text-error)", "{error}" }
+            }
+            if total > 0 {
+                Row { gap: "8px",
+                    SolidButton {
+                        disabled: importing() || subscribed == total,
+                        onclick: import,
+                        if importing() {
+                            {t!("import-subscribing")}
+                        } else {
+                            {t!("import-subscribe", count = total - subscribed)}
+                        }
+                    }
+                    TransparentButton { onclick: move |_| navigator().go_back(), {t!("import-done")} }
+                }
+            }
+        }
+
+        Column { border_top: "1px solid var(--text)",
+            for (outline, status) in outlines() {
+                Column {
+                    key: "{outline.xml_url}",
+                    padding: "10px 16px",
+                    border_bottom: "1px solid var(--text-secondary)",
+                    gap: "2px",
+
+                    span { font_size: "16px", {outline.title.clone().unwrap_or(outline.xml_url.clone())} }
+                    match status {
+                        ImportStatus::Waiting => rsx! {
+                            span { font_size: "12px", color: "var(--text-secondary)", "{outline.xml_url}" }
+                        },
+                        ImportStatus::Subscribed => rsx! {
+                            span { font_size: "12px", color: "var(--text-secondary)", "Subscribed" }
+                        },
+                        ImportStatus::Failed(message) => rsx! {
+                            span { font_size: "12px", color: "var(--text-error)", "{message}" }
+                        },
+                    }
+                }
+            }
+        }
+    }
+}
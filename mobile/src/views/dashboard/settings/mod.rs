@@ -0,0 +1,344 @@
+use std::collections::BTreeSet;
+
+use dioxus::prelude::*;
+use ui::{
+    forms::button::UnstyledButton,
+    layout::*,
+    overlay::{use_overlays, Confirm, Toast},
+};
+
+use crate::{
+    api::ApiClientError,
+    components::enclosure::format_size,
+    i18n::{t, Language},
+    offline::use_downloads,
+    opml,
+    read_state::{use_read_store, ReadState},
+    settings::{use_settings, ItemAction, ReaderMode, TextSize, Theme},
+    store::{use_auth_user, use_store, Store},
+    views::{dashboard::components::Header, Route},
+};
+
+mod accounts;
+mod change_password;
+mod import;
+
+pub use accounts::Accounts;
+pub use change_password::ChangePassword;
+pub use import::ImportSubscriptions;
+
+const VERSION: &str = env!("CARGO_PKG_VERSION");
+
+#[component]
+pub fn Settings() -> Element {
+    rsx! {
+        Header { title: t!("settings-title") }
+
+        Column { padding: "0px 0px 16px",
+            AccountSection {}
+            AppearanceSection {}
+            ReaderSection {}
+            NotificationSection {}
+            StorageSection {}
+            SubscriptionSection {}
+
+            Section { title: t!("settings-about"),
+                span { "Bind {VERSION}" }
+                span { font_size: "14px", color: "var(--text-secondary)",
+                    {t!("settings-about-description")}
+                }
+            }
+        }
+    }
+}
+
+#[component]
+fn AccountSection() -> Element {
+    let store = use_store();
+    let user = use_auth_user();
+    let account = store.active_account();
+    let accounts = store.accounts();
+    let mut overlays = use_overlays();
+
+    rsx! {
+        Section { title: t!("settings-account"),
+            if let Some(user) = user() {
+                Column { gap: "2px",
+                    span { font_size: "16px", "{user.username}" }
+                    span { font_size: "14px", color: "var(--text-secondary)", "{user.email}" }
+                    if let Some(account) = account().filter(|account| account.is_self_hosted()) {
+                        span { font_size: "14px", color: "var(--text-secondary)", "{account.server}" }
+                    }
+                }
+            }
+            LinkRow { to: Route::ChangePassword {}, {t!("settings-change-password")} }
+            LinkRow { to: Route::Accounts {},
+                if accounts.read().len() > 1 {
+                    {t!("settings-switch-account", count = accounts.read().len())}
+                } else {
+                    {t!("settings-add-account")}
+                }
+            }
+
+            UnstyledButton {
+                text_align: "start",
+                color: "var(--text-error)",
+                onclick: move |_| {
+                    overlays
+                        .confirm(
+                            Confirm::new(
+                                    t!("settings-log-out-title"),
+                                    t!("settings-log-out-message"),
+                                    move |_| store.logout(),
+                                )
+                                .confirm_label(t!("settings-log-out"))
+                                .destructive(),
+                        );
+                },
+                {t!("settings-log-out")}
+            }
+        }
+    }
+}
+
+#[component]
+fn AppearanceSection() -> Element {
+    let mut settings = use_settings();
+
+    rsx! {
+        Section { title: t!("settings-appearance"),
+            Choices {
+                label: t!("settings-language"),
+                for language in Language::ALL {
+                    Choice {
+                        selected: (settings.language)() == language,
+                        onclick: move |_| settings.language.set(language),
+                        // Read in the language itself, whichever the app is in
+                        span { lang: language.code(), "{language.label()}" }
+                    }
+                }
+            }
+            Choices {
+                label: t!("settings-theme"),
+                for theme in Theme::ALL {
+                    Choice {
+                        selected: (settings.theme)() == theme,
+                        onclick: move |_| settings.theme.set(theme),
+                        "{theme.label()}"
+                    }
+                }
+            }
+            Choices {
+                label: t!("settings-text-size"),
+                for size in TextSize::ALL {
+                    Choice {
+                        selected: (settings.text_size)() == size,
+                        onclick: move |_| settings.text_size.set(size),
+                        "{size.label()}"
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[component]
+fn ReaderSection() -> Element {
+    let mut settings = use_settings();
+    let mut read_store = use_read_store();
+
+    rsx! {
+        Section { title: t!("settings-reader"),
+            Choices {
+                label: t!("settings-open-items-in"),
+                for mode in ReaderMode::ALL {
+                    Choice {
+                        selected: (settings.open_items_in)() == mode,
+                        onclick: move |_| settings.open_items_in.set(mode),
+                        "{mode.label()}"
+                    }
+                }
+            }
+            Choices {
+                label: t!("settings-swipe-left"),
+                for action in ItemAction::ALL {
+                    Choice {
+                        selected: (settings.swipe_left)() == action,
+                        onclick: move |_| settings.swipe_left.set(action),
+                        "{action.label()}"
+                    }
+                }
+            }
+            Choices {
+                label: t!("settings-swipe-right"),
+                for action in ItemAction::ALL {
+                    Choice {
+                        selected: (settings.swipe_right)() == action,
+                        onclick: move |_| settings.swipe_right.set(action),
+                        "{action.label()}"
+                    }
+                }
+            }
+            Toggle {
+                checked: read_store.mark_on_scroll(),
+                onchange: move |checked| read_store.set_mark_on_scroll(checked),
+                {t!("settings-mark-on-scroll")}
+            }
+        }
+    }
+}
+
+#[component]
+fn NotificationSection() -> Element {
+    let mut settings = use_settings();
+
+    rsx! {
+        Section { title: t!("settings-notifications"),
+            Toggle {
+                checked: (settings.notifications)(),
+                onchange: move |checked| settings.notifications.set(checked),
+                {t!("settings-notify-new-items")}
+            }
+            Toggle {
+                checked: (settings.auto_sync)(),
+                onchange: move |checked| settings.auto_sync.set(checked),
+                {t!("settings-auto-sync")}
+            }
+        }
+    }
+}
+
+#[component]
+fn StorageSection() -> Element {
+    let store = use_store();
+    let downloads = use_downloads();
+    let mut overlays = use_overlays();
+    let mut cache_size = use_signal(|| store.api().cache_size());
+
+    let downloaded = format_size(downloads.library().read().used() as i64);
+
+    rsx! {
+        Section { title: t!("settings-storage"),
+            Row { align: "space-between",
+                span { {t!("settings-downloads")} }
+                span { color: "var(--text-secondary)", "{downloaded}" }
+            }
+            Row { align: "space-between",
+                span { {t!("settings-cache")} }
+                span { color: "var(--text-secondary)", {format_size(cache_size() as i64)} }
+            }
+            LinkRow { to: Route::Downloads {}, {t!("settings-manage-downloads")} }
+            UnstyledButton {
+                text_align: "start",
+                onclick: move |_| {
+                    store.clear_cache();
+                    cache_size.set(store.api().cache_size());
+                    overlays.toast(Toast::new(t!("settings-cache-cleared")));
+                },
+                {t!("settings-clear-cache")}
+            }
+        }
+    }
+}
+
+#[component]
+fn SubscriptionSection() -> Element {
+    let store = use_store();
+    let mut overlays = use_overlays();
+
+    let export = move |_| {
+        spawn(async move {
+            match export_opml(store).await {
+                Ok(opml) => {
+                    let opml = serde_json::to_string(&opml).unwrap_or_default();
+                    document::eval(&format!("navigator.clipboard.writeText({opml});"));
+                    overlays.toast(Toast::new(t!("settings-opml-copied")));
+                }
+                Err(err) => overlays.toast(Toast::new(err.message())),
+            }
+        });
+    };
+
+    rsx! {
+        Section { title: t!("settings-subscriptions"),
+            LinkRow { to: Route::ImportSubscriptions {}, {t!("settings-import-opml")} }
+            UnstyledButton { text_align: "start", onclick: export, {t!("settings-export-opml")} }
+        }
+    }
+}
+
+/// The feeds in the user's tags, as an OPML file
+async fn export_opml(store: Store) -> Result<String, ApiClientError> {
+    let tags = store.tags(0).await?;
+    let feed_ids: BTreeSet<i32> = tags.iter().flat_map(ReadState::tag_feeds).collect();
+    let mut feeds = Vec::with_capacity(feed_ids.len());
+    for id in feed_ids {
+        feeds.push(store.feed(id).await?);
+    }
+    Ok(opml::build("Bind subscriptions", &feeds))
+}
+
+#[component]
+fn Section(title: String, children: Element) -> Element {
+    rsx! {
+        Column {
+            gap: "12px",
+            padding: "16px",
+            border_bottom: "1px solid var(--text-secondary)",
+            align: "stretch",
+
+            span { font_size: "14px", font_weight: 600, color: "var(--text-secondary)", "{title}" }
+            {children}
+        }
+    }
+}
+
+#[component]
+fn LinkRow(to: Route, children: Element) -> Element {
+    rsx! {
+        UnstyledButton {
+            text_align: "start",
+            onclick: move |_| {
+                navigator().push(to.clone());
+            },
+            {children}
+        }
+    }
+}
+
+/// Options to pick one from, in a row after the label
+#[component]
+fn Choices(label: String, children: Element) -> Element {
+    rsx! {
+        Row { gap: "8px", cross_align: "center", wrap: true,
+            span { color: "var(--text-secondary)", "{label}" }
+            {children}
+        }
+    }
+}
+
+#[component]
+fn Choice(selected: bool, onclick: EventHandler<MouseEvent>, children: Element) -> Element {
+    rsx! {
+        UnstyledButton {
+            padding: "4px",
+            text_decoration: if selected { "underline" } else { "none" },
+            onclick: move |event| onclick.call(event),
+            {children}
+        }
+    }
+}
+
+#[component]
+fn Toggle(checked: bool, onchange: EventHandler<bool>, children: Element) -> Element {
+    rsx! {
+        label { display: "flex", gap: "8px", align_items: "center",
+            input {
+                r#type: "checkbox",
+                checked,
+                onchange: move |event: FormEvent| onchange.call(event.checked()),
+            }
+            {children}
+        }
+    }
+}
@@ -0,0 +1,155 @@
+use dioxus::prelude::*;
+use ui::{
+    forms::button::SolidButton,
+    layout::*,
+    overlay::{use_overlays, Toast},
+};
+
+use crate::{
+    api::{
+        types::{
+            feed::FeedInformation,
+            list::{CreateListItemRequest, UserList},
+        },
+        ApiClientError,
+    },
+    i18n::t,
+    store::{use_lists, use_store},
+    views::{dashboard::components::Header, Route},
+};
+
+/// Offers to subscribe to the site of a link shared with the app, or to save the page to a list
+#[component]
+pub fn SharedLink(url: ReadOnlySignal<String>) -> Element {
+    let store = use_store();
+    let mut overlays = use_overlays();
+    let lists = use_lists(0);
+    let mut error = use_signal(|| None::<ApiClientError>);
+    let mut busy = use_signal(|| false);
+
+    let discovered = use_resource(move || async move { store.api().discover_feeds(&url()).await });
+
+    let mut subscribe = move |information: FeedInformation| {
+        busy.set(true);
+        spawn(async move {
+            match store.create_feed(&information.url).await {
+                Ok(feed) => {
+                    navigator().replace(Route::FeedDetail { id: feed.id });
+                }
+                Err(err) => error.set(Some(err)),
+            }
+            busy.set(false);
+        });
+    };
+
+    let mut save = move |list: UserList| {
+        let Some(user) = store.user() else {
+            return;
+        };
+        busy.set(true);
+        let url = url();
+        spawn(async move {
+            let result = async {
+                let item = store.item_for_link(&url).await?;
+                let request = CreateListItemRequest {
+                    index: 0,
+                    owner: user.id,
+                    item: item.id,
+                };
+                store.create_list_item(list.id, &request).await
+            };
+            match result.await {
+                Ok(_) => {
+                    overlays.toast(Toast::new(t!(
+                        "item-saved-to-list",
+                        list = list.title.clone()
+                    )));
+                    navigator().replace(Route::List {
+                        id: list.id as usize,
+                    });
+                }
+                Err(err) => error.set(Some(err)),
+            }
+            busy.set(false);
+        });
+    };
+
+    rsx! {
+        Header { title: t!("shared-link-title") }
+
+        Column { gap: "16px", padding: "16px",
+            span { font_size: "14px", color: "var(--text-secondary)", word_break: "break-all", "{url}" }
+
+            if let Some(err) = error() {
+                span { color: "var(--text-error)", "{err.message()}" }
+            }
+
+            Column { gap: "8px",
+                span { font_size: "16px", font_weight: 600, {t!("shared-link-subscribe-title")} }
+                match &*discovered.read() {
+                    Some(Ok(feeds)) if feeds.is_empty() => rsx! {
+                        span { color: "var(--text-secondary)", {t!("shared-link-no-feeds")} }
+                    },
+                    Some(Ok(feeds)) => rsx! {
+                        for information in feeds.clone() {
+                            Row { key: "{information.url}", gap: "12px", cross_align: "center", align: "space-between",
+                                span { font_size: "14px", word_break: "break-all", "{information.url}" }
+                                SolidButton {
+                                    onclick: move |_| {
+                                        if !busy() {
+                                            subscribe(information.clone());
+                                        }
+                                    },
+                                    {t!("shared-link-subscribe")}
+                                }
+                            }
+                        }
+                    },
+                    Some(Err(err)) => rsx! {
+                        span { color: "var(--text-error)", "{err.message()}" }
+                    },
+                    None => rsx! {
+                        span { color: "var(--text-secondary)", {t!("shared-link-looking")} }
+                    },
+                }
+            }
+
+            Column { gap: "8px",
+                span { font_size: "16px", font_weight: 600, {t!("item-save-to-list")} }
+                if let Some(lists) = lists.read().data.clone() {
+                    if lists.is_empty() {
+                        span { color: "var(--text-secondary)", {t!("lists-empty")} }
+                    }
+                    for list in lists {
+                        ListButton {
+                            key: "{list.id}",
+                            list,
+                            onclick: move |list| {
+                                if !busy() {
+                                    save(list);
+                                }
+                            },
+                        }
+                    }
+                } else if let Some(err) = &lists.read().error {
+                    span { color: "var(--text-error)", "{err.message()}" }
+                }
+            }
+        }
+    }
+}
+
+#[component]
+fn ListButton(list: UserList, onclick: EventHandler<UserList>) -> Element {
+    let icon = list.icon.as_ref().map(|icon| icon.icon.clone());
+    let title = list.title.clone();
+
+    rsx! {
+        SolidButton { onclick: move |_| onclick.call(list.clone()),
+            if let Some(icon) = icon {
+                "{icon} "
+            }
+            "{title}"
+        }
+    }
+}
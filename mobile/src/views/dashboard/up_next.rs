@@ -0,0 +1,79 @@
+use dioxus::prelude::*;
+use ui::{
+    forms::button::TransparentButton,
+    layout::{Column, Row},
+};
+
+use crate::{
+    components::player::format_duration,
+    i18n::t,
+    playback::{use_playback, QueueEntry},
+    views::dashboard::components::Header,
+};
+
+#[component]
+pub fn UpNext() -> Element {
+    let playback = use_playback();
+    let queue = playback.queue();
+    let up_next = queue.read().up_next.clone();
+    let count = up_next.len();
+
+    rsx! {
+        Header { title: t!("up-next-title"), additional: t!("up-next-count", count = count) }
+
+        Column {
+            if up_next.is_empty() {
+                span { padding: "16px", color: "var(--text-secondary)",
+                    {t!("up-next-empty")}
+                }
+            }
+            for (index, entry) in up_next.into_iter().enumerate() {
+                UpNextItem { key: "{entry.item_id}", index, entry }
+            }
+        }
+    }
+}
+
+#[component]
+fn UpNextItem(index: usize, entry: QueueEntry) -> Element {
+    let mut playback = use_playback();
+    let item_id = entry.item_id;
+
+    let details = match entry.duration {
+        Some(duration) if entry.position > 0. => format!(
+            "{} / {}",
+            format_duration(entry.position),
+            format_duration(duration)
+        ),
+        Some(duration) => format_duration(duration),
+        None => String::new(),
+    };
+
+    rsx! {
+        Row {
+            padding: "10px 16px",
+            border_bottom: "1px solid var(--text-secondary)",
+            gap: "8px",
+            cross_align: "center",
+            align: "space-between",
+
+            Column { gap: "2px",
+                span { font_size: "16px", "{entry.title}" }
+                span { font_size: "12px", color: "var(--text-secondary)", "{details}" }
+            }
+            Row { gap: "4px",
+                TransparentButton {
+                    onclick: {
+                        let entry = entry.clone();
+                        move |_| playback.play(entry.clone())
+                    },
+                    {t!("up-next-play")}
+                }
+                if index > 0 {
+                    TransparentButton { onclick: move |_| playback.reorder(index, index - 1), {t!("up-next-move-up")} }
+                }
+                TransparentButton { onclick: move |_| playback.remove(item_id), {t!("remove")} }
+            }
+        }
+    }
+}
@@ -1,5 +1,9 @@
 use dioxus::prelude::*;
 use dioxus_router::prelude::*;
+use ui::{
+    gesture::use_haptics_provider,
+    overlay::{use_overlays_provider, Toast},
+};
 
 pub mod auth;
 pub mod dashboard;
@@ -10,8 +14,14 @@ use dashboard::*;
 use reader::*;
 
 use crate::{
-    components::popup::{use_popup_state_provider, PopupState},
-    platform::init_back_press_listener,
+    deep_link::use_deep_links_provider,
+    i18n::t,
+    offline::use_downloads_provider,
+    platform::{haptic_feedback, init_back_press_listener, init_share_listener},
+    playback::use_playback_provider,
+    read_state::use_read_store_provider,
+    settings::use_settings_provider,
+    store::use_store,
 };
 
 #[derive(Debug, Clone, Routable, PartialEq)]
@@ -32,42 +42,68 @@ pub enum Route {
             },
             #[route("/auth/login")]
             Login {},
+            #[route("/auth/oauth/callback?:code&:state&:token&:error")]
+            OAuthCallback {
+                code: String,
+                state: String,
+                token: String,
+                error: String,
+            },
             #[route("/auth/reset-password")]
             ResetPassword {},
-            #[route("/auth/reset-password-confirm?:email")]
-            ResetPasswordConfirm { email: String },
+            #[route("/auth/reset-password-confirm?:email&:code")]
+            ResetPasswordConfirm { email: String, code: String },
 
         // Dashboard
         #[layout(DashboardLayout)]
             #[route("/")]
             Feed {},
+            #[route("/feed/:id")]
+            FeedDetail { id: i32 },
             #[route("/list/:id")]
             List { id: usize },
+            #[route("/index/:id")]
+            Index { id: i32 },
             #[route("/search")]
             Search {},
+            #[route("/share?:url")]
+            SharedLink { url: String },
             #[route("/add-feed")]
             AddFeed {},
+            #[route("/up-next")]
+            UpNext {},
+            #[route("/downloads")]
+            Downloads {},
+            #[route("/settings")]
+            Settings {},
+            #[route("/settings/accounts")]
+            Accounts {},
+            #[route("/settings/password")]
+            ChangePassword {},
+            #[route("/settings/import")]
+            ImportSubscriptions {},
 
         // Reader
         #[layout(ReaderLayout)]
-            #[route("/reader")]
-            FeedReader {},
-            #[route("/reader/parsed")]
-            ParsedReader {},
+            #[route("/reader/:id")]
+            FeedReader { id: i64 },
+            #[route("/reader/:id/parsed")]
+            ParsedReader { id: i64 },
 }
 
 #[component]
 fn RootLayout() -> Element {
-    let mut popup_state = use_popup_state_provider();
+    let mut overlays = use_overlays_provider();
+    use_haptics_provider(haptic_feedback);
+    let active_account = use_store().active_account();
 
     // Handle back events
     use_future(move || async move {
         let mut rx = init_back_press_listener();
         loop {
             if let Ok(()) = rx.try_recv() {
-                if matches!(*popup_state.read(), PopupState::Open(_)) {
-                    popup_state.set(PopupState::Close);
-                } else {
+                // Closes the topmost overlay before going back
+                if !overlays.close_top() {
                     navigator().go_back();
                 }
             }
@@ -75,6 +111,46 @@ fn RootLayout() -> Element {
         }
     });
 
+    // Handle links the app was opened with
+    let mut deep_links = use_deep_links_provider();
+
+    // Handle links shared with the app, waiting for the user to log in like deep links
+    use_future(move || async move {
+        let rx = init_share_listener();
+        loop {
+            if let Ok(share) = rx.try_recv() {
+                match share.url() {
+                    Some(url) => deep_links.open(Route::SharedLink {
+                        url: url.to_string(),
+                    }),
+                    None => overlays.toast(Toast::new(t!("share-links-only"))),
+                }
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+        }
+    });
+
+    let account = active_account
+        .read()
+        .as_ref()
+        .map(|account| account.id.clone())
+        .unwrap_or_default();
+
+    rsx! {
+        AccountLayout { key: "{account}" }
+    }
+}
+
+/// Provides what belongs to the active account to the views. Keyed by the account, so it is
+/// loaded again when the user switches to another.
+#[component]
+fn AccountLayout() -> Element {
+    use_settings_provider();
+    use_read_store_provider();
+    // Playback plays downloaded enclosures from their files
+    use_downloads_provider();
+    use_playback_provider();
+
     rsx! {
         Outlet::<Route> {}
     }
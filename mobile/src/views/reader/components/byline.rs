@@ -1,6 +1,8 @@
 use dioxus::prelude::*;
 use ui::layout::{Column, Row};
 
+use crate::i18n::t;
+
 #[derive(Props, Clone, PartialEq)]
 pub struct Props {
     author: Option<String>,
@@ -18,7 +20,8 @@ pub fn Byline(props: Props) -> Element {
                     font_family: "IBM Plex Mono",
                     text_transform: "uppercase",
                     font_size: "12px",
-                    "By "
+                    {t!("reader-by")}
+                    " "
                     a {
                         rel: "author",
                         // href: "https://www.example.com",
@@ -1,6 +1,8 @@
 use dioxus::prelude::*;
 use ui::layout::{Column, Row};
 
+use crate::i18n::t;
+
 #[derive(Props, Clone, PartialEq)]
 pub struct Props {
     title: String,
@@ -44,7 +46,8 @@ pub fn Header(props: Props) -> Element {
                         font_family: "IBM Plex Mono",
                         text_transform: "uppercase",
                         font_size: "12px",
-                        "By "
+                        {t!("reader-by")}
+                        " "
                         a {
                             rel: "author",
                             // href: "https://www.example.com",
@@ -1,22 +1,55 @@
 use dioxus::prelude::*;
 use ui::layout::Column;
 
-use super::components::{Byline, ReaderDocument, Title};
+use super::{
+    components::{html_to_paragraphs, Byline, ReaderDocument, Title},
+    use_reader_item,
+};
+use crate::{
+    components::{enclosure::Enclosure, time::format_date},
+    i18n::t,
+};
+
+/// Average reading speed used for the reading time estimate
+const WORDS_PER_MINUTE: usize = 200;
 
 #[component]
-pub fn FeedReader() -> Element {
+pub fn FeedReader(id: i64) -> Element {
+    let (item, feed) = use_reader_item(id);
+
+    let item = item.read();
+    let item = match &*item {
+        Some(Ok(item)) => item.clone(),
+        // Not downloaded either, so there is nothing to show
+        Some(Err(err)) => return Err(err.clone().into()),
+        None => return rsx! {},
+    };
+
+    let source = match &*feed.read() {
+        Some(Some(feed)) => feed.title.clone(),
+        _ => String::new(),
+    };
+    let paragraphs = item
+        .content
+        .as_deref()
+        .or(item.description.as_deref())
+        .map(html_to_paragraphs)
+        .unwrap_or_default();
+    let words = paragraphs
+        .iter()
+        .map(|paragraph| paragraph.split_whitespace().count())
+        .sum::<usize>();
+    let minutes = words.div_ceil(WORDS_PER_MINUTE).max(1);
+
     rsx! {
         Column { cross_align: "center", gap: "12px",
-            Title {
-                title: "Is it Lunacy to Put a Data Center on the Moon?",
-                source: "Ieee Spectrum",
-            }
+            Title { title: item.title.clone(), source }
             Byline {
-                author: "Dina Genkina",
-                published_at: "26 Feb 2025",
-                duration: "4 min read",
+                published_at: item.published_at.map(format_date),
+                duration: t!("reader-minutes", count = minutes),
             }
-            ReaderDocument {}
+            Enclosure { item: item.clone() }
+            ReaderDocument { paragraphs }
         }
     }
 }
@@ -4,24 +4,77 @@ mod feed_reader;
 mod parsed_reader;
 
 use crate::{
-    components::navbar::{Navbar, NavbarButton, NavbarButtonWithoutRoute},
+    api::{
+        types::feed::{Feed, FeedItem},
+        ApiClientError,
+    },
+    components::{
+        error::AppErrorBoundary,
+        navbar::{Navbar, NavbarButton, NavbarButtonWithoutRoute},
+        player::MiniPlayer,
+        popup::{PopupList, PopupListItem},
+    },
     hooks::use_token,
+    i18n::t,
+    offline::{use_downloads, DownloadStatus},
     platform::share_feed_item,
+    read_state::use_read_store,
+    settings::{use_settings, TextSize},
+    store::use_store,
     views::Route,
 };
-use ui::icons::{
-    ArrowTopRightOnSquareIcon, Bars3Icon, BookmarkIcon, NewspaperIcon, PlusIcon, QueueIcon,
-    SearchIcon, ShareIcon, TextSettingsIcon,
+use ui::{
+    icons::{
+        ArrowDownTrayIcon, ArrowTopRightOnSquareIcon, Bars3Icon, BookmarkIcon, NewspaperIcon,
+        PlusIcon, QueueIcon, SearchIcon, ShareIcon, TextSettingsIcon,
+    },
+    overlay::use_overlays,
 };
 
 pub use feed_reader::FeedReader;
 pub use parsed_reader::ParsedReader;
 
+/// Fetches the item shown in the reader, along with the feed it belongs to, and marks it as read.
+/// Falls back to the downloaded copy of the item when it can't be fetched.
+fn use_reader_item(
+    id: i64,
+) -> (
+    Resource<Result<FeedItem, ApiClientError>>,
+    Resource<Option<Feed>>,
+) {
+    let store = use_store();
+    let mut downloads = use_downloads();
+    let mut read_store = use_read_store();
+    let item = use_resource(use_reactive!(|id| async move {
+        let item = match store.item(id).await {
+            Ok(item) => Ok(item),
+            Err(err) => downloads.item(id).ok_or(err),
+        };
+        if item.is_ok() {
+            read_store.mark_read([id]);
+        }
+        item
+    }));
+    let feed = use_resource(move || async move {
+        let feed_id = match &*item.read() {
+            Some(Ok(item)) => item.feed_id,
+            _ => return None,
+        };
+        store.feed(feed_id).await.ok()
+    });
+
+    (item, feed)
+}
+
 #[component]
 pub fn ReaderLayout() -> Element {
     // TODO: better way to redirect to sign up if not logged in?
     let nav = use_navigator();
     let token = use_token();
+    let store = use_store();
+    let mut downloads = use_downloads();
+    let mut overlays = use_overlays();
+    let text_size = use_settings().text_size;
 
     use_effect(move || {
         if token().is_none() {
@@ -29,48 +82,119 @@ pub fn ReaderLayout() -> Element {
         }
     });
 
+    let id = match use_route::<Route>() {
+        Route::FeedReader { id } | Route::ParsedReader { id } => id,
+        _ => return rsx! { Outlet::<Route> {} },
+    };
+    let download_status = downloads
+        .library()
+        .read()
+        .get(id)
+        .map(|entry| entry.status.clone());
+
     rsx! {
         div {
             display: "grid",
-            grid_template_rows: "1fr auto",
+            grid_template_rows: "1fr auto auto",
             height: "100vh",
             width: "100vw",
 
-            main { overflow: "auto", padding: "16px", Outlet::<Route> {} }
+            main { overflow: "auto", padding: "16px", font_size: text_size().css(),
+                AppErrorBoundary { key: "{id}", Outlet::<Route> {} }
+            }
 
-            Navbar {
-                NavbarButtonWithoutRoute { onclick: move |_| {},
+            MiniPlayer {}
+
+            Navbar { label: t!("reader-navbar"),
+                NavbarButtonWithoutRoute {
+                    label: t!("reader-text-size"),
+                    expanded: overlays.is_open(),
+                    onclick: move |_| {
+                        if overlays.is_open() {
+                            overlays.close_all();
+                        } else {
+                            overlays.sheet(rsx! { TextSizePopup {} });
+                        }
+                    },
                     span {
                         font_family: "IBM Plex Mono",
                         font_size: "24px",
                         line_height: "20px",
+                        aria_hidden: "true",
                         "Aa"
                     }
                 }
                 NavbarButton {
-                    to: Route::FeedReader {},
+                    label: t!("reader-feed-content"),
+                    to: Route::FeedReader { id },
                     icon: |solid| rsx! {
                         NewspaperIcon { solid }
                     },
                 }
                 NavbarButton {
-                    to: Route::ParsedReader {},
+                    label: t!("reader-web-page"),
+                    to: Route::ParsedReader { id },
                     icon: |solid| rsx! {
                         ArrowTopRightOnSquareIcon { solid }
                     },
                 }
                 NavbarButton {
-                    to: Route::ParsedReader {},
+                    label: t!("reader-bookmark"),
+                    to: Route::ParsedReader { id },
                     icon: |solid| rsx! {
                         BookmarkIcon { solid }
                     },
                 }
                 NavbarButtonWithoutRoute {
+                    label: match download_status {
+                        Some(DownloadStatus::Failed { .. }) => t!("reader-retry-download"),
+                        Some(_) => t!("reader-downloads"),
+                        None => t!("reader-download"),
+                    },
+                    onclick: {
+                        let download_status = download_status.clone();
+                        move |_| match download_status {
+                            Some(DownloadStatus::Failed { .. }) => downloads.retry(id),
+                            Some(_) => {
+                                navigator().push(Route::Downloads {});
+                            }
+                            None => downloads.download(id),
+                        }
+                    },
+                    ArrowDownTrayIcon { solid: download_status == Some(DownloadStatus::Complete) }
+                }
+                NavbarButtonWithoutRoute {
+                    label: t!("reader-share"),
                     onclick: move |_| {
-                        share_feed_item("https://example.com".to_string(), "testing".to_string());
+                        spawn(async move {
+                            match store.item(id).await {
+                                Ok(item) => {
+                                    let link = item.link.unwrap_or_default();
+                                    share_feed_item(link, item.title);
+                                }
+                                Err(err) => tracing::error!("Failed to share item: {}", err.message()),
+                            }
+                        });
                     },
-                    ShareIcon {
-                    }
+                    ShareIcon {}
+                }
+            }
+        }
+    }
+}
+
+/// Changes the size of the text of the article
+#[component]
+fn TextSizePopup() -> Element {
+    let mut settings = use_settings();
+
+    rsx! {
+        PopupList { label: t!("reader-text-size"),
+            for size in TextSize::ALL {
+                PopupListItem {
+                    title: size.label(),
+                    subtitle: ((settings.text_size)() == size).then(|| t!("reader-text-size-current")),
+                    onclick: move |_| settings.text_size.set(size),
                 }
             }
         }
@@ -1,21 +1,37 @@
 use dioxus::prelude::*;
 use ui::layout::Column;
 
-use super::components::{Byline, ReaderDocumentHtml, Title};
+use super::{
+    components::{Byline, ReaderDocumentHtml, Title},
+    use_reader_item,
+};
+use crate::{components::time::format_date, i18n::t};
 
 #[component]
-pub fn ParsedReader() -> Element {
+pub fn ParsedReader(id: i64) -> Element {
+    let (item, feed) = use_reader_item(id);
+
+    let item = item.read();
+    let item = match &*item {
+        Some(Ok(item)) => item.clone(),
+        // Not downloaded either, so there is nothing to show
+        Some(Err(err)) => return Err(err.clone().into()),
+        None => return rsx! {},
+    };
+
+    let source = match &*feed.read() {
+        Some(Some(feed)) => feed.title.clone(),
+        _ => String::new(),
+    };
+
     rsx! {
         Column { cross_align: "center", gap: "12px",
-            Title {
-                title: "Is it Lunacy to Put a Data Center on the Moon?",
-                source: "Ieee Spectrum",
-            }
+            Title { title: item.title.clone(), source }
             Byline {
-                author: "Dina Genkina",
-                published_at: "26 Feb 2025",
-                duration: "4 min read",
+                published_at: item.published_at.map(format_date),
+                duration: t!("reader-minutes", count = 4),
             }
+            // TODO: render the page extracted from `item.link`
             ReaderDocumentHtml {}
         }
     }
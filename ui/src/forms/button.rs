@@ -5,6 +5,9 @@ pub struct ButtonProps {
     #[props(extends = GlobalAttributes, extends = button)]
     pub attributes: Vec<Attribute>,
     pub onclick: EventHandler<MouseEvent>,
+    /// Disables the button while what it started is in progress
+    #[props(default)]
+    pub pending: bool,
     pub children: Element,
 }
 
@@ -13,6 +16,8 @@ pub fn SolidButton(props: ButtonProps) -> Element {
     rsx! {
         button {
             class: "solid",
+            disabled: props.pending,
+            aria_busy: props.pending,
             onclick: move |evt| props.onclick.call(evt),
             ..props.attributes,
             {props.children}
@@ -38,3 +43,28 @@ pub fn TransparentButton(props: ButtonProps) -> Element {
         }
     }
 }
+
+#[derive(Props, PartialEq, Clone)]
+pub struct IconButtonProps {
+    #[props(extends = GlobalAttributes, extends = button)]
+    pub attributes: Vec<Attribute>,
+    /// Names the button for screen readers and in its tooltip, since its icon has no text
+    #[props(into)]
+    pub label: String,
+    pub onclick: EventHandler<MouseEvent>,
+    pub children: Element,
+}
+
+/// Button showing only an icon, named by its label
+#[component]
+pub fn IconButton(props: IconButtonProps) -> Element {
+    rsx! {
+        button {
+            aria_label: props.label.clone(),
+            title: props.label,
+            onclick: move |evt| props.onclick.call(evt),
+            ..props.attributes,
+            {props.children}
+        }
+    }
+}
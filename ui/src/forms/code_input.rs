@@ -1,69 +1,461 @@
 use dioxus::prelude::*;
 use std::{collections::HashMap, rc::Rc};
 
-use crate::layout::Row;
+use crate::{
+    layout::Row,
+    text::{use_texts, UiText},
+};
+
+/// Characters a code is made of
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum CodeMode {
+    #[default]
+    Numeric,
+    /// Letters and digits. Letters are uppercased, as codes are shown in capitals.
+    Alphanumeric,
+}
+
+impl CodeMode {
+    /// The character as it's kept in the code, or `None` if codes can't contain it
+    fn accept(self, c: char) -> Option<char> {
+        match self {
+            CodeMode::Numeric => c.is_ascii_digit().then_some(c),
+            CodeMode::Alphanumeric => c.is_ascii_alphanumeric().then(|| c.to_ascii_uppercase()),
+        }
+    }
+
+    /// Keyboard shown on touch screens
+    fn input_mode(self) -> &'static str {
+        match self {
+            CodeMode::Numeric => "numeric",
+            CodeMode::Alphanumeric => "text",
+        }
+    }
+}
 
 #[derive(Props, Clone, PartialEq)]
 pub struct Props {
     // number of characters in the input field
     length: usize,
+    // code the input starts with, e.g. from a link
+    #[props(default)]
+    initial_value: String,
+    #[props(default)]
+    mode: CodeMode,
+    // shows the code as invalid, e.g. after the server rejected it
+    #[props(default)]
+    error: bool,
+    #[props(default)]
+    disabled: bool,
+    // called with the code whenever it changes, see `code`
     onchange: Callback<String>,
+    // called with the code once the user fills in the last empty box
+    oncomplete: Option<Callback<String>>,
 }
 
+/// Input for one-time codes, with a box per character.
+///
+/// Codes can be typed, pasted into any box, or filled in by the platform from an SMS or email.
 #[allow(non_snake_case)]
 pub fn CodeInput(props: Props) -> Element {
-    let mut code = use_signal(String::new);
-    let mut input_refs =
-        use_signal(|| HashMap::<usize, Rc<MountedData>>::with_capacity(props.length));
+    let length = props.length;
+    let mode = props.mode;
+    let texts = use_texts();
+    let mut cells = use_signal(|| {
+        let mut cells = vec![None; length];
+        enter(&mut cells, 0, &props.initial_value, mode);
+        cells
+    });
+    let mut input_refs = use_signal(|| HashMap::<usize, Rc<MountedData>>::with_capacity(length));
+    // A prefilled code isn't submitted on its own, only once the user changes it
+    let mut edited = use_signal(|| false);
+
+    // Keeps a box per character when the length changes, dropping the characters past the end
+    use_effect(use_reactive!(|length| {
+        if cells.peek().len() != length {
+            cells.write().resize(length, None);
+        }
+    }));
 
     use_effect(move || {
-        props.onchange.call(code().clone());
+        let (code, length) = {
+            let cells = cells.read();
+            (code(&cells), cells.len())
+        };
+        props.onchange.call(code.clone());
+        if *edited.peek() && is_complete(&code, length) {
+            if let Some(oncomplete) = props.oncomplete {
+                oncomplete.call(code);
+            }
+        }
     });
 
+    let focus = move |idx: usize| {
+        if let Some(input) = input_refs.peek().get(&idx).cloned() {
+            spawn(async move {
+                let _ = input.set_focus(true).await;
+            });
+        }
+    };
+
+    // The boxes are resized after a render with the new length, so they may be missing then
+    let value = move |idx: usize| {
+        let cell = cells.read().get(idx).copied().flatten();
+        cell.map(String::from).unwrap_or_default()
+    };
+
+    let border = if props.error {
+        "var(--text-error)"
+    } else {
+        "var(--text)"
+    };
+
     rsx! {
         Row {
             gap: "12px",
             height: "80px",
             align_self: "center",
 
-            for idx in 0..props.length {
+            for idx in 0..length {
                 input {
-                    type: "number",
+                    r#type: "text",
+                    inputmode: mode.input_mode(),
+                    // Lets the platform offer the code from an SMS or email, into the first box
+                    autocomplete: if idx == 0 { "one-time-code" } else { "off" },
+                    autocapitalize: "characters",
+                    spellcheck: false,
+                    disabled: props.disabled,
+                    aria_label: texts.get(UiText::CodeCharacter { index: idx, length }),
+                    aria_invalid: props.error,
 
-                    value: code().get(idx..idx + 1),
+                    value: value(idx),
 
-                    onkeydown: move |e| {
-                        match e.key() {
-                            Key::Backspace => {
-                                code.set(code()[0..code().len() - 1].to_string());
-                            },
-                            Key::Character(c) => {
-                                if let Some(c) = c.parse::<u32>().ok() {
-                                    if code().len() < 5 && c >= 0 && c <= 9 {
-                                        code.set(format!("{}{}", code(), c));
-                                    }
-                                }
-                            }
-                            _ => {}
+                    oninput: move |e| {
+                        edited.set(true);
+                        if let Some(next) = input(&mut cells.write(), idx, &e.value(), mode) {
+                            focus(next);
                         }
+                    },
 
-                        if let Some(input_to_focus) = input_refs().get(&code().len()).cloned() {
-                            input_to_focus.set_focus(true);
+                    onkeydown: move |e| {
+                        let mut changed = cells.peek().clone();
+                        let Some(next) = press(&mut changed, idx, &e.key()) else {
+                            return;
+                        };
+                        e.prevent_default();
+                        if changed != *cells.peek() {
+                            edited.set(true);
+                            cells.set(changed);
                         }
+                        focus(next);
                     },
 
                     onmounted: move |e| {
                         input_refs.write().insert(idx, e.data());
                     },
 
-                    style: "border: 1px solid var(--text); background: rgba(255, 255, 255, 0.2); width: 60px; height: 80px; font-size: 48px; text-align: center; outline: none;",
-
-                    min: 0,
-                    max: 9
+                    style: "border: 1px solid {border}; background: rgba(255, 255, 255, 0.2); width: 60px; height: 80px; font-size: 48px; text-align: center; text-transform: uppercase;",
+                    opacity: if props.disabled { "0.5" } else { "1" },
                 }
             }
+        }
+    }
+}
+
+/// Stands in for an empty box in the code
+const EMPTY: char = ' ';
 
+/// The code entered so far. Empty boxes are spaces, so the characters keep their positions,
+/// except at the end, e.g. `"1 3"` for the first and third of five boxes.
+fn code(cells: &[Option<char>]) -> String {
+    let code: String = cells.iter().map(|cell| cell.unwrap_or(EMPTY)).collect();
+    code.trim_end_matches(EMPTY).to_string()
+}
+
+/// Whether every box of the code is filled in
+pub fn is_complete(code: &str, length: usize) -> bool {
+    code.chars().count() == length && !code.contains(EMPTY)
+}
+
+/// What was entered into a box, given its value after the input and the character it held.
+/// Typing into a filled box adds to its character, which the new one replaces.
+fn typed(previous: Option<char>, value: &str) -> String {
+    match previous {
+        Some(previous) if value.chars().count() > 1 => match value.strip_prefix(previous) {
+            Some(entered) => entered.to_string(),
+            // Entered in front of the character
+            None => value.trim_end_matches(previous).to_string(),
+        },
+        _ => value.to_string(),
+    }
+}
+
+/// Enters the value of the box at `idx` after an input into it, returning the box to focus next
+/// when something was entered
+fn input(cells: &mut [Option<char>], idx: usize, value: &str, mode: CodeMode) -> Option<usize> {
+    let text = typed(cells.get(idx).copied().flatten(), value);
+    let next = enter(cells, idx, &text, mode);
+    (!text.is_empty()).then(|| next.min(cells.len().saturating_sub(1)))
+}
+
+/// Applies a key pressed in the box at `idx`, returning the box to focus next, or `None` for
+/// keys left to the box itself
+fn press(cells: &mut [Option<char>], idx: usize, key: &Key) -> Option<usize> {
+    match key {
+        // Deletes the previous character from an empty box
+        Key::Backspace if idx > 0 && cells.get(idx).is_some_and(Option::is_none) => {
+            cells[idx - 1] = None;
+            Some(idx - 1)
+        }
+        Key::ArrowLeft if idx > 0 => Some(idx - 1),
+        Key::ArrowRight if idx + 1 < cells.len() => Some(idx + 1),
+        Key::Home => Some(0),
+        Key::End => Some(cells.len().saturating_sub(1)),
+        _ => None,
+    }
+}
+
+/// Enters text into the boxes from `idx` on, clearing the box for empty text. Text longer than a
+/// character was pasted or filled in, so it continues into the following boxes. Returns the
+/// index of the box after the last one entered.
+fn enter(cells: &mut [Option<char>], idx: usize, text: &str, mode: CodeMode) -> usize {
+    if idx >= cells.len() {
+        return idx;
+    }
+    if text.is_empty() {
+        cells[idx] = None;
+        return idx;
+    }
+
+    let mut next = idx;
+    for c in text.chars().filter_map(|c| mode.accept(c)) {
+        if next >= cells.len() {
+            break;
         }
+        cells[next] = Some(c);
+        next += 1;
+    }
+    next
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        cell::RefCell,
+        future::Future,
+        task::{Context, Waker},
+    };
+
+    use dioxus::dioxus_core::NoOpMutations;
+
+    use super::*;
+
+    fn cells(code: &str) -> Vec<Option<char>> {
+        code.chars().map(|c| (c != EMPTY).then_some(c)).collect()
+    }
+
+    #[test]
+    fn code_keeps_positions_of_empty_boxes() {
+        assert_eq!(code(&cells("1 3  ")), "1 3");
+        assert_eq!(code(&cells("  345")), "  345");
+        assert_eq!(code(&cells("     ")), "");
+        assert_eq!(code(&cells("12345")), "12345");
+    }
+
+    #[test]
+    fn code_is_complete_once_every_box_is_filled() {
+        assert!(is_complete("12345", 5));
+        assert!(!is_complete("1234", 5));
+        assert!(!is_complete("12 45", 5));
+        assert!(!is_complete("", 5));
+    }
+
+    #[test]
+    fn enters_pasted_text_into_following_boxes() {
+        let mut cells = vec![None; 5];
+        assert_eq!(enter(&mut cells, 1, "9-8 7", CodeMode::Numeric), 4);
+        assert_eq!(code(&cells), " 987");
+
+        // Longer than the boxes left
+        assert_eq!(enter(&mut cells, 3, "123456", CodeMode::Numeric), 5);
+        assert_eq!(code(&cells), " 9812");
+
+        assert_eq!(enter(&mut cells, 2, "", CodeMode::Numeric), 2);
+        assert_eq!(code(&cells), " 9 12");
+    }
+
+    #[test]
+    fn alphanumeric_codes_are_uppercased() {
+        let mut cells = vec![None; 4];
+        enter(&mut cells, 0, "a1-b2", CodeMode::Alphanumeric);
+        assert_eq!(code(&cells), "A1B2");
+
+        let mut cells = vec![None; 4];
+        enter(&mut cells, 0, "a1b2", CodeMode::Numeric);
+        assert_eq!(code(&cells), "12");
+    }
+
+    #[test]
+    fn typing_into_a_filled_box_replaces_its_character() {
+        assert_eq!(typed(Some('1'), "12"), "2");
+        assert_eq!(typed(Some('1'), "21"), "2");
+        assert_eq!(typed(Some('1'), ""), "");
+        assert_eq!(typed(None, "123"), "123");
+    }
+
+    #[test]
+    fn pasting_fills_the_boxes_and_focuses_the_last() {
+        let mut cells = vec![None; 5];
+        assert_eq!(input(&mut cells, 0, "12-345", CodeMode::Numeric), Some(4));
+        assert!(is_complete(&code(&cells), 5));
+        assert_eq!(code(&cells), "12345");
+
+        // Pasted into a later box, only the boxes from there on are filled
+        let mut cells = vec![None; 5];
+        assert_eq!(input(&mut cells, 3, "6789", CodeMode::Numeric), Some(4));
+        assert_eq!(code(&cells), "   67");
+        assert!(!is_complete(&code(&cells), 5));
+    }
+
+    #[test]
+    fn backspace_in_an_empty_box_deletes_the_previous_character() {
+        let mut boxes = cells("123  ");
+        assert_eq!(press(&mut boxes, 3, &Key::Backspace), Some(2));
+        assert_eq!(code(&boxes), "12");
+
+        // In a filled box, the box deletes its own character
+        let mut boxes = cells("123  ");
+        assert_eq!(press(&mut boxes, 2, &Key::Backspace), None);
+        assert_eq!(input(&mut boxes, 2, "", CodeMode::Numeric), None);
+        assert_eq!(code(&boxes), "12");
+
+        let mut boxes = cells("     ");
+        assert_eq!(press(&mut boxes, 0, &Key::Backspace), None);
+    }
+
+    #[test]
+    fn arrows_move_between_boxes() {
+        let mut boxes = cells("123  ");
+        assert_eq!(press(&mut boxes, 2, &Key::ArrowLeft), Some(1));
+        assert_eq!(press(&mut boxes, 2, &Key::ArrowRight), Some(3));
+        assert_eq!(press(&mut boxes, 0, &Key::ArrowLeft), None);
+        assert_eq!(press(&mut boxes, 4, &Key::ArrowRight), None);
+        assert_eq!(press(&mut boxes, 2, &Key::Home), Some(0));
+        assert_eq!(press(&mut boxes, 2, &Key::End), Some(4));
+        assert_eq!(code(&boxes), "123");
+    }
+
+    #[test]
+    fn code_is_completed_after_correcting_a_box() {
+        let mut cells = vec![None; 4];
+        let mut focused = 0;
+        for typed in ["1", "2", "4"] {
+            focused = input(&mut cells, focused, typed, CodeMode::Numeric).unwrap();
+        }
+        assert_eq!(code(&cells), "124");
+
+        // Back to the third box, typing over its character
+        focused = press(&mut cells, focused, &Key::ArrowLeft).unwrap();
+        focused = input(&mut cells, focused, "43", CodeMode::Numeric).unwrap();
+        assert_eq!(code(&cells), "123");
+        assert!(!is_complete(&code(&cells), 4));
+
+        input(&mut cells, focused, "4", CodeMode::Numeric);
+        assert_eq!(code(&cells), "1234");
+        assert!(is_complete(&code(&cells), 4));
+    }
+
+    thread_local! {
+        static CHANGES: RefCell<Vec<String>> = const { RefCell::new(Vec::new()) };
+        static COMPLETED: RefCell<Vec<String>> = const { RefCell::new(Vec::new()) };
+    }
+
+    /// Renders the dom and runs its effects until there's nothing left to do
+    fn settle(dom: &mut VirtualDom) {
+        let mut context = Context::from_waker(Waker::noop());
+        for _ in 0..10 {
+            dom.render_immediate(&mut NoOpMutations);
+            let work = std::pin::pin!(dom.wait_for_work());
+            if work.poll(&mut context).is_pending() {
+                break;
+            }
+        }
+    }
+
+    #[component]
+    fn Prefilled(initial_value: String) -> Element {
+        rsx! {
+            CodeInput {
+                length: 4,
+                initial_value: initial_value.clone(),
+                mode: CodeMode::Alphanumeric,
+                onchange: |code: String| CHANGES.with_borrow_mut(|changes| changes.push(code)),
+                oncomplete: |code: String| COMPLETED.with_borrow_mut(|completed| completed.push(code)),
+            }
+        }
+    }
+
+    /// Mounts the input with the code, returning the codes it reported as changed and completed
+    fn mount(initial_value: &str) -> (Vec<String>, Vec<String>) {
+        let props = PrefilledProps {
+            initial_value: initial_value.to_string(),
+        };
+        let mut dom = VirtualDom::new_with_props(Prefilled, props);
+        dom.rebuild_in_place();
+        settle(&mut dom);
+
+        (CHANGES.take(), COMPLETED.take())
+    }
+
+    #[test]
+    fn prefilled_code_is_not_completed_on_mount() {
+        let (changes, completed) = mount("ab12");
+        assert_eq!(changes, ["AB12"]);
+        assert!(completed.is_empty());
+    }
+
+    #[test]
+    fn partial_prefilled_code_is_reported() {
+        let (changes, completed) = mount("a");
+        assert_eq!(changes, ["A"]);
+        assert!(completed.is_empty());
+    }
+
+    /// Input whose length changes once it's mounted
+    #[component]
+    fn Resized(from: usize, to: usize) -> Element {
+        let mut length = use_signal(|| from);
+        use_effect(move || length.set(to));
+
+        rsx! {
+            CodeInput {
+                length: length(),
+                initial_value: "ab12cd",
+                mode: CodeMode::Alphanumeric,
+                onchange: |code: String| CHANGES.with_borrow_mut(|changes| changes.push(code)),
+            }
+        }
+    }
+
+    fn resize(from: usize, to: usize) -> Vec<String> {
+        let mut dom = VirtualDom::new_with_props(Resized, ResizedProps { from, to });
+        dom.rebuild_in_place();
+        settle(&mut dom);
+
+        CHANGES.take()
+    }
+
+    #[test]
+    fn boxes_are_added_when_the_length_grows() {
+        let changes = resize(4, 6);
+        assert_eq!(changes.first().map(String::as_str), Some("AB12"));
+        assert_eq!(changes.last().map(String::as_str), Some("AB12"));
+    }
 
+    #[test]
+    fn characters_past_the_end_are_dropped_when_the_length_shrinks() {
+        let changes = resize(6, 4);
+        assert_eq!(changes.first().map(String::as_str), Some("AB12CD"));
+        assert_eq!(changes.last().map(String::as_str), Some("AB12"));
     }
 }
@@ -0,0 +1,534 @@
+//! Form state with field-level validation.
+//!
+//! A [Form] is created with [use_form] and its fields with [use_field], each from a signal holding
+//! its value and the [FieldOptions] it's validated with. Fields show their errors once they're
+//! touched, and [Form::submit] touches them all before running the action, only when they're all
+//! valid, waiting for the requests checking fields first. Errors the server returns for fields are
+//! shown on them until they change.
+
+use std::{collections::HashMap, future::Future, pin::Pin, rc::Rc};
+
+use dioxus::prelude::*;
+
+use super::button::SolidButton;
+
+type SyncValidator<T> = Rc<dyn Fn(&T) -> Result<(), String>>;
+type AsyncValidator<T> = Rc<dyn Fn(T) -> Pin<Box<dyn Future<Output = Result<(), String>>>>>;
+
+/// How a field is validated
+pub struct FieldOptions<T> {
+    validators: Vec<SyncValidator<T>>,
+    async_validator: Option<AsyncValidator<T>>,
+}
+
+impl<T: 'static> FieldOptions<T> {
+    pub fn new() -> Self {
+        Self {
+            validators: Vec::new(),
+            async_validator: None,
+        }
+    }
+
+    /// Checks the value whenever it changes, returning the message to show when it's invalid
+    pub fn validate(mut self, validator: impl Fn(&T) -> Result<(), String> + 'static) -> Self {
+        self.validators.push(Rc::new(validator));
+        self
+    }
+
+    /// Checks the value with a request, e.g. whether a username is taken, once the other
+    /// validators pass. Checks of earlier values are cancelled, so the validator can wait a
+    /// moment first to only check once the user stops typing.
+    pub fn validate_async<F>(mut self, validator: impl Fn(T) -> F + 'static) -> Self
+    where
+        F: Future<Output = Result<(), String>> + 'static,
+    {
+        self.async_validator = Some(Rc::new(move |value| Box::pin(validator(value))));
+        self
+    }
+}
+
+impl<T: 'static> Default for FieldOptions<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// State of a field which doesn't depend on the type of its value, kept by the form
+#[derive(Clone, Copy, PartialEq)]
+struct FieldState {
+    name: &'static str,
+    /// The user left the field, or tried to submit the form
+    touched: Signal<bool>,
+    errors: Memo<Vec<String>>,
+    async_error: Signal<Option<String>>,
+    validating: Signal<bool>,
+    /// Error the server returned for the field, until the value changes
+    server_error: Signal<Option<String>>,
+}
+
+impl FieldState {
+    fn error(&self) -> Option<String> {
+        (self.server_error)()
+            .or_else(|| self.errors.read().first().cloned())
+            .or_else(|| (self.async_error)())
+    }
+}
+
+#[derive(PartialEq)]
+pub struct Field<T: 'static> {
+    form: Form,
+    value: Signal<T>,
+    initial: CopyValue<T>,
+    state: FieldState,
+    async_validator: CopyValue<Option<AsyncValidator<T>>>,
+    validation: Signal<Option<Task>>,
+}
+
+// Copy even when the value isn't, as it only holds signals
+impl<T: 'static> Clone for Field<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T: 'static> Copy for Field<T> {}
+
+impl<T: Clone + PartialEq + 'static> Field<T> {
+    pub fn value(&self) -> T {
+        self.value.read().clone()
+    }
+
+    pub fn set(&mut self, value: T) {
+        if *self.value.peek() == value {
+            return;
+        }
+        self.value.set(value.clone());
+        self.state.server_error.set(None);
+        self.validate_async(value);
+    }
+
+    /// Shows the field's errors from now on, e.g. when the user leaves it
+    pub fn touch(&mut self) {
+        self.state.touched.set(true);
+    }
+
+    pub fn is_touched(&self) -> bool {
+        (self.state.touched)()
+    }
+
+    /// Whether the value differs from the one the field started with
+    pub fn is_dirty(&self) -> bool {
+        *self.value.read() != *self.initial.read()
+    }
+
+    /// A request is checking the value
+    pub fn is_validating(&self) -> bool {
+        (self.state.validating)()
+    }
+
+    pub fn is_valid(&self) -> bool {
+        self.state.error().is_none()
+    }
+
+    /// Every message for why the value is invalid
+    pub fn errors(&self) -> Vec<String> {
+        let mut errors = self.state.errors.read().clone();
+        errors.extend((self.state.async_error)());
+        errors.extend((self.state.server_error)());
+        errors
+    }
+
+    /// The error to show next to the field, once it's touched. Errors of async validators are
+    /// shown right away, as they're only checked for otherwise valid values.
+    pub fn error(&self) -> Option<String> {
+        match self.is_touched() {
+            true => self.state.error(),
+            false => (self.state.async_error)(),
+        }
+    }
+
+    fn validate_async(&mut self, value: T) {
+        if let Some(task) = self.validation.take() {
+            task.cancel();
+        }
+        self.state.async_error.set(None);
+
+        let validator = self.async_validator.read().clone();
+        let Some(validator) = validator else {
+            return;
+        };
+        if !self.state.errors.peek().is_empty() {
+            self.state.validating.set(false);
+            self.form.resume();
+            return;
+        }
+
+        let mut form = self.form;
+        let mut state = self.state;
+        state.validating.set(true);
+        let task = spawn(async move {
+            let result = validator(value).await;
+            state.async_error.set(result.err());
+            state.validating.set(false);
+            form.resume();
+        });
+        self.validation.set(Some(task));
+    }
+}
+
+/// Creates a field of the form, named like the field in the server's validation errors
+pub fn use_field<T: Clone + PartialEq + 'static>(
+    form: Form,
+    name: &'static str,
+    value: Signal<T>,
+    options: impl FnOnce() -> FieldOptions<T>,
+) -> Field<T> {
+    let options = use_hook(|| Rc::new(options()));
+    let validators = options.validators.clone();
+    let errors = use_memo(move || {
+        let value = value.read();
+        validators
+            .iter()
+            .filter_map(|validator| validator(&value).err())
+            .collect()
+    });
+
+    let state = FieldState {
+        name,
+        touched: use_signal(|| false),
+        errors,
+        async_error: use_signal(|| None),
+        validating: use_signal(|| false),
+        server_error: use_signal(|| None),
+    };
+    let initial = use_hook(|| CopyValue::new(value.peek().clone()));
+    let async_validator = use_hook(|| CopyValue::new(options.async_validator.clone()));
+    let validation = use_signal(|| None);
+
+    use_hook(move || {
+        let mut fields = form.fields;
+        fields.write().push(state);
+    });
+
+    Field {
+        form,
+        value,
+        initial,
+        state,
+        async_validator,
+        validation,
+    }
+}
+
+/// Why submitting a form failed
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct SubmitError {
+    /// Shown for the whole form
+    pub message: Option<String>,
+    /// Messages for invalid fields, by name
+    pub fields: HashMap<String, String>,
+}
+
+impl SubmitError {
+    pub fn new(message: impl Into<String>) -> Self {
+        Self {
+            message: Some(message.into()),
+            fields: HashMap::new(),
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub struct Form {
+    fields: Signal<Vec<FieldState>>,
+    pending: Signal<bool>,
+    error: Signal<Option<String>>,
+    /// Submission waiting for the requests checking fields
+    queued: CopyValue<Option<Box<dyn FnOnce()>>>,
+}
+
+impl Form {
+    /// The form is being submitted
+    pub fn is_pending(&self) -> bool {
+        (self.pending)()
+    }
+
+    /// Error of the last submission which doesn't belong to a field
+    pub fn error(&self) -> Option<String> {
+        (self.error)()
+    }
+
+    pub fn set_error(&mut self, error: Option<String>) {
+        self.error.set(error);
+    }
+
+    pub fn is_valid(&self) -> bool {
+        self.fields
+            .read()
+            .iter()
+            .all(|field| field.error().is_none() && !(field.validating)())
+    }
+
+    /// Runs the action once every field is valid, showing the errors of the fields otherwise.
+    /// While requests are checking fields, the form is pending until they're done. Errors the
+    /// action returns for fields are shown on them, the others for the whole form.
+    pub fn submit<F>(&self, action: impl FnOnce() -> F + 'static)
+    where
+        F: Future<Output = Result<(), SubmitError>> + 'static,
+    {
+        if *self.pending.peek() {
+            return;
+        }
+        for field in self.fields.peek().iter() {
+            let mut touched = field.touched;
+            touched.set(true);
+        }
+
+        let mut form = *self;
+        form.pending.set(true);
+        form.error.set(None);
+        form.queued.set(Some(Box::new(move || form.run(action))));
+        form.resume();
+    }
+
+    /// Runs the queued submission once no request is checking a field, unless a field is invalid
+    fn resume(&mut self) {
+        let fields = self.fields.peek().clone();
+        if fields.iter().any(|field| *field.validating.peek()) {
+            return;
+        }
+        let Some(submit) = self.queued.take() else {
+            return;
+        };
+
+        let invalid = fields.iter().any(|field| {
+            field.server_error.peek().is_some()
+                || !field.errors.peek().is_empty()
+                || field.async_error.peek().is_some()
+        });
+        match invalid {
+            true => self.pending.set(false),
+            false => submit(),
+        }
+    }
+
+    fn run<F>(mut self, action: impl FnOnce() -> F + 'static)
+    where
+        F: Future<Output = Result<(), SubmitError>> + 'static,
+    {
+        spawn(async move {
+            let result = action().await;
+            self.pending.set(false);
+            if let Err(err) = result {
+                self.show_error(err);
+            }
+        });
+    }
+
+    fn show_error(&mut self, err: SubmitError) {
+        let mut unmatched = Vec::new();
+        for (name, message) in err.fields {
+            let field = self
+                .fields
+                .peek()
+                .iter()
+                .find(|field| field.name == name)
+                .copied();
+            match field {
+                Some(mut field) => field.server_error.set(Some(message)),
+                None => unmatched.push(message),
+            }
+        }
+
+        let message = err.message.into_iter().chain(unmatched).collect::<Vec<_>>();
+        self.error
+            .set((!message.is_empty()).then(|| message.join(". ")));
+    }
+}
+
+pub fn use_form() -> Form {
+    Form {
+        fields: use_signal(Vec::new),
+        pending: use_signal(|| false),
+        error: use_signal(|| None),
+        queued: use_hook(|| CopyValue::new(None)),
+    }
+}
+
+/// Submits the form, disabled while it's being submitted, which includes waiting for its fields
+/// to be checked
+#[component]
+pub fn SubmitButton(form: Form, onclick: EventHandler<MouseEvent>, children: Element) -> Element {
+    rsx! {
+        SolidButton { pending: form.is_pending(), onclick, {children} }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        cell::{Cell, RefCell},
+        task::{Context, Poll, Waker},
+    };
+
+    use dioxus::dioxus_core::NoOpMutations;
+
+    use super::*;
+
+    /// Pending once, like a request which doesn't answer right away
+    struct Delay(bool);
+
+    impl Future for Delay {
+        type Output = ();
+
+        fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+            if self.0 {
+                return Poll::Ready(());
+            }
+            self.0 = true;
+            cx.waker().wake_by_ref();
+            Poll::Pending
+        }
+    }
+
+    #[derive(Clone, Debug, PartialEq)]
+    struct Snapshot {
+        pending: bool,
+        form_error: Option<String>,
+        username_error: Option<String>,
+    }
+
+    thread_local! {
+        static RUNS: Cell<usize> = const { Cell::new(0) };
+        static SNAPSHOTS: RefCell<Vec<Snapshot>> = const { RefCell::new(Vec::new()) };
+    }
+
+    /// Form with a username, which is required and checked with a request for whether it's
+    /// taken. Once mounted, the username is entered and the form submitted as many times.
+    #[component]
+    fn SignUp(username: String, submits: usize, result: Result<(), SubmitError>) -> Element {
+        let form = use_form();
+        let value = use_signal(String::new);
+        let mut field = use_field(form, "username", value, || {
+            FieldOptions::new()
+                .validate(|username: &String| match username.is_empty() {
+                    true => Err("Required".to_string()),
+                    false => Ok(()),
+                })
+                .validate_async(|username: String| async move {
+                    Delay(false).await;
+                    match username == "taken" {
+                        true => Err("Taken".to_string()),
+                        false => Ok(()),
+                    }
+                })
+        });
+
+        use_effect(move || {
+            field.set(username.clone());
+            for _ in 0..submits {
+                let result = result.clone();
+                form.submit(move || async move {
+                    RUNS.set(RUNS.get() + 1);
+                    result
+                });
+            }
+        });
+
+        let snapshot = Snapshot {
+            pending: form.is_pending(),
+            form_error: form.error(),
+            username_error: field.error(),
+        };
+        SNAPSHOTS.with_borrow_mut(|snapshots| snapshots.push(snapshot));
+        rsx! {}
+    }
+
+    /// Mounts the form and runs it until there's nothing left to do, returning the number of
+    /// times the action ran and the state of the form at each render
+    fn submit(
+        username: &str,
+        submits: usize,
+        result: Result<(), SubmitError>,
+    ) -> (usize, Vec<Snapshot>) {
+        let props = SignUpProps {
+            username: username.to_string(),
+            submits,
+            result,
+        };
+        let mut dom = VirtualDom::new_with_props(SignUp, props);
+        dom.rebuild_in_place();
+
+        let mut context = Context::from_waker(Waker::noop());
+        for _ in 0..20 {
+            dom.render_immediate(&mut NoOpMutations);
+            let work = std::pin::pin!(dom.wait_for_work());
+            if work.poll(&mut context).is_pending() {
+                break;
+            }
+        }
+
+        (RUNS.take(), SNAPSHOTS.take())
+    }
+
+    #[test]
+    fn valid_form_is_submitted_once_checked() {
+        let (runs, snapshots) = submit("free", 1, Ok(()));
+        assert_eq!(runs, 1);
+        // Pending while the username is checked, then while submitting
+        assert!(snapshots.iter().any(|snapshot| snapshot.pending));
+        assert_eq!(
+            snapshots.last(),
+            Some(&Snapshot {
+                pending: false,
+                form_error: None,
+                username_error: None,
+            })
+        );
+    }
+
+    #[test]
+    fn invalid_form_shows_field_errors_instead_of_submitting() {
+        let (runs, snapshots) = submit("", 1, Ok(()));
+        assert_eq!(runs, 0);
+        let last = snapshots.last().unwrap();
+        assert!(!last.pending);
+        assert_eq!(last.username_error.as_deref(), Some("Required"));
+    }
+
+    #[test]
+    fn form_is_not_submitted_when_the_check_fails() {
+        let (runs, snapshots) = submit("taken", 1, Ok(()));
+        assert_eq!(runs, 0);
+        let last = snapshots.last().unwrap();
+        assert!(!last.pending);
+        assert_eq!(last.username_error.as_deref(), Some("Taken"));
+    }
+
+    #[test]
+    fn form_is_submitted_once_while_pending() {
+        let (runs, _) = submit("free", 3, Ok(()));
+        assert_eq!(runs, 1);
+    }
+
+    #[test]
+    fn server_errors_are_shown_on_their_fields_and_the_rest_on_the_form() {
+        let err = SubmitError {
+            message: Some("Couldn't sign up".to_string()),
+            fields: HashMap::from([
+                ("username".to_string(), "Already taken".to_string()),
+                ("invite".to_string(), "Expired".to_string()),
+            ]),
+        };
+        let (runs, snapshots) = submit("free", 1, Err(err));
+        assert_eq!(runs, 1);
+        assert_eq!(
+            snapshots.last(),
+            Some(&Snapshot {
+                pending: false,
+                form_error: Some("Couldn't sign up. Expired".to_string()),
+                username_error: Some("Already taken".to_string()),
+            })
+        );
+    }
+}
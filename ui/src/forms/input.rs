@@ -1,6 +1,11 @@
 use std::rc::Rc;
 
-use dioxus::{logger::tracing, prelude::*};
+use dioxus::prelude::*;
+
+use crate::{
+    icons::{EyeIcon, EyeSlashIcon},
+    text::{use_texts, UiText},
+};
 
 #[derive(Clone, Debug, Copy, PartialEq)]
 pub enum IconPosition {
@@ -17,59 +22,57 @@ pub struct Props {
     icon: Option<Element>,
     icon_position: Option<IconPosition>,
     value: String,
+    /// Shown under the input, which is outlined in red
+    error: Option<String>,
     onchange: Callback<String>,
+    /// Called on every keystroke, while `onchange` is called once the input is committed
+    oninput: Option<Callback<String>>,
+    onblur: Option<EventHandler<FocusEvent>>,
 }
 
 #[allow(non_snake_case)]
 pub fn Input(props: Props) -> Element {
-    tracing::info!("value: {:?}", props.value);
     let mut element_ref = use_signal(|| None::<Rc<MountedData>>);
 
     let title = props.title.map(|title| -> Element {
         rsx! {
             label {
-                position: "absolute",
-
-                top: "0",
-                transform: "translateY(-50%)",
-
-                left: "14px",
-                padding_left: "2px",
-                padding_right: "2px",
-
-                z_index: "1",
-
-                background: "var(--bg)",
+                // Placed from the start of the line, which is on the right in right to left text
+                style: "position: absolute; top: 0; inset-inline-start: 14px; transform: translateY(-50%); padding: 0 2px; z-index: 1; background: var(--bg);",
 
                 {title}
             }
         }
     });
 
+    // Left and right are the start and end of the line, so they swap in right to left text
+    let side = match props.icon_position {
+        Some(IconPosition::Left) => "inset-inline-start",
+        None | Some(IconPosition::Right) => "inset-inline-end",
+    };
     let icon = props.icon.map(|icon| -> Element {
         rsx! {
             div {
-                position: "absolute",
-
-                top: "50%",
                 // TODO: why +2px?
-                transform: "translateY(calc(-50% + 2px))",
-
-                left: match props.icon_position {
-                    Some(IconPosition::Left) => "16px",
-                    None | Some(IconPosition::Right) => "unset",
-                },
-                right: match props.icon_position {
-                    Some(IconPosition::Left) => "unset",
-                    None | Some(IconPosition::Right) => "16px",
-                },
+                style: "position: absolute; top: 50%; transform: translateY(calc(-50% + 2px)); {side}: 16px;",
 
                 {icon}
             }
         }
     });
 
-    rsx! {
+    let padding_x = match props.icon_position {
+        Some(_) => "calc(16px + 24px + 12px)",
+        None => "16px",
+    };
+
+    let border = if props.error.is_some() {
+        "var(--text-error)"
+    } else {
+        "var(--text)"
+    };
+
+    let input = rsx! {
         div { display: "flex", position: "relative",
 
             {title}
@@ -77,27 +80,99 @@ pub fn Input(props: Props) -> Element {
 
             input {
                 padding: "12px",
-                padding_left: match props.icon_position {
-                    Some(IconPosition::Left) => "calc(16px + 24px + 12px)",
-                    _ => "16px",
-                },
-                padding_right: match props.icon_position {
-                    Some(IconPosition::Right) => "calc(16px + 24px + 12px)",
-                    _ => "16px",
-                },
+                // Room for the icon on both sides, as its side swaps in right to left text
+                padding_left: padding_x,
+                padding_right: padding_x,
                 flex_grow: "1",
                 height: "48px",
                 background_color: "transparent",
                 color: "#000000",
-                border: "1px solid var(--text)",
-                outline: "none",
+                border: "1px solid {border}",
 
                 type: props.input_type,
                 value: props.value,
+                aria_invalid: props.error.is_some(),
                 onchange: move |ev| props.onchange.call(ev.value().clone()),
+                oninput: move |ev| {
+                    if let Some(oninput) = props.oninput {
+                        oninput.call(ev.value());
+                    }
+                },
+                onblur: move |ev| {
+                    if let Some(onblur) = props.onblur {
+                        onblur.call(ev);
+                    }
+                },
 
                 ..props.attributes,
             }
         }
+    };
+
+    match props.error {
+        Some(error) => rsx! {
+            div { display: "flex", flex_direction: "column", gap: "4px",
+                {input}
+                span { color: "var(--text-error)", font_size: "14px", role: "alert", {error} }
+            }
+        },
+        None => input,
+    }
+}
+
+#[derive(Props, Clone, PartialEq)]
+pub struct PasswordProps {
+    title: Option<String>,
+    placeholder: Option<String>,
+    /// `new-password` when choosing a password, so the platform can suggest one
+    #[props(default = "current-password".to_string(), into)]
+    autocomplete: String,
+    value: String,
+    error: Option<String>,
+    onchange: Callback<String>,
+    oninput: Option<Callback<String>>,
+    onblur: Option<EventHandler<FocusEvent>>,
+}
+
+/// Input for a password, with a button to show it while typing
+#[allow(non_snake_case)]
+pub fn PasswordInput(props: PasswordProps) -> Element {
+    let texts = use_texts();
+    let mut visible = use_signal(|| false);
+
+    let toggle = rsx! {
+        button {
+            r#type: "button",
+            display: "flex",
+            padding: "0",
+            background: "none",
+            border: "none",
+            color: "var(--text)",
+            aria_label: texts.get(if visible() { UiText::HidePassword } else { UiText::ShowPassword }),
+            aria_pressed: visible(),
+            onclick: move |_| visible.toggle(),
+
+            if visible() {
+                EyeSlashIcon {}
+            } else {
+                EyeIcon {}
+            }
+        }
+    };
+
+    rsx! {
+        Input {
+            title: props.title,
+            input_type: if visible() { "text" } else { "password" },
+            icon: toggle,
+            icon_position: IconPosition::Right,
+            value: props.value,
+            error: props.error,
+            onchange: props.onchange,
+            oninput: props.oninput,
+            onblur: props.onblur,
+            placeholder: props.placeholder,
+            autocomplete: props.autocomplete,
+        }
     }
 }
@@ -1,3 +1,4 @@
 pub mod button;
 pub mod code_input;
+pub mod form;
 pub mod input;
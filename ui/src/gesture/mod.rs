@@ -0,0 +1,45 @@
+//! Touch gestures, with mouse and trackpad equivalents for desktop.
+//!
+//! Gestures give haptic feedback through [Haptics], which the app provides with
+//! [use_haptics_provider] since only it can reach the platform's vibration APIs.
+
+use dioxus::prelude::*;
+
+mod pull_to_refresh;
+mod swipe;
+
+pub use pull_to_refresh::PullToRefresh;
+pub use swipe::{SwipeAction, SwipeActions};
+
+pub(crate) use pull_to_refresh::PullArea;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Haptic {
+    /// A gesture moved past the point where releasing it takes its action, or back before it
+    Threshold,
+    /// The action of a gesture was taken
+    Action,
+}
+
+/// Plays haptic feedback, see [use_haptics]
+#[derive(Clone, Copy, PartialEq)]
+pub struct Haptics(Option<Callback<Haptic>>);
+
+impl Haptics {
+    pub fn play(&self, haptic: Haptic) {
+        if let Some(play) = self.0 {
+            play.call(haptic);
+        }
+    }
+}
+
+/// Plays the haptic feedback of the gestures below with `play`
+pub fn use_haptics_provider(play: impl FnMut(Haptic) + 'static) -> Haptics {
+    let play = use_callback(play);
+    use_context_provider(|| Haptics(Some(play)))
+}
+
+/// The haptics provided by the app, which do nothing when it provides none
+pub fn use_haptics() -> Haptics {
+    try_use_context::<Haptics>().unwrap_or(Haptics(None))
+}
@@ -0,0 +1,159 @@
+use dioxus::prelude::*;
+
+use super::{use_haptics, Haptic};
+use crate::text::{use_texts, UiText};
+
+/// Distance the content has to be pulled down for releasing it to refresh
+const REFRESH_DISTANCE: f64 = 64.0;
+/// Furthest the content is pulled down
+const MAX_PULL: f64 = 112.0;
+/// Space kept for the indicator while refreshing
+const INDICATOR_HEIGHT: f64 = 48.0;
+
+/// Whether the list scrolled inside a [PullToRefresh] is at its top, where pulling it down
+/// refreshes it rather than scrolling it. Kept up to date by the list, see
+/// [VirtualList](crate::list::VirtualList).
+#[derive(Clone, Copy)]
+pub(crate) struct PullArea {
+    pub at_top: Signal<bool>,
+}
+
+/// Refreshes its content when it's pulled down from the top, or scrolled further up with the
+/// mouse wheel or trackpad once it's at the top.
+#[component]
+pub fn PullToRefresh(
+    /// The content is being refreshed, e.g. while its data is requested again
+    refreshing: bool,
+    /// Shown with the indicator, e.g. when the content was last updated
+    status: Option<String>,
+    onrefresh: Callback,
+    children: Element,
+) -> Element {
+    let haptics = use_haptics();
+    let texts = use_texts();
+    let area = use_context_provider(|| PullArea {
+        at_top: Signal::new(true),
+    });
+    let mut touch_start = use_signal(|| None::<f64>);
+    let mut pull = use_signal(|| 0.0);
+    // Distance scrolled up with the mouse wheel or trackpad while at the top
+    let mut overscroll = use_signal(|| 0.0);
+
+    let mut pull_to = move |distance: f64| {
+        // Follows the pointer at half its speed, so the content feels held back
+        let next = (distance / 2.0).clamp(0.0, MAX_PULL);
+        if (*pull.peek() >= REFRESH_DISTANCE) != (next >= REFRESH_DISTANCE) {
+            haptics.play(Haptic::Threshold);
+        }
+        pull.set(next);
+    };
+    let mut release = move || {
+        if *pull.peek() >= REFRESH_DISTANCE && !refreshing {
+            haptics.play(Haptic::Action);
+            onrefresh.call(());
+        }
+        pull.set(0.0);
+        overscroll.set(0.0);
+    };
+
+    let dragging = touch_start().is_some() || overscroll() > 0.0;
+    let offset = if refreshing {
+        pull().max(INDICATOR_HEIGHT)
+    } else {
+        pull()
+    };
+    let label = texts.get(if refreshing {
+        UiText::Refreshing
+    } else if pull() >= REFRESH_DISTANCE {
+        UiText::ReleaseToRefresh
+    } else {
+        UiText::PullToRefresh
+    });
+
+    rsx! {
+        div {
+            position: "relative",
+            height: "100%",
+            overflow: "hidden",
+
+            ontouchstart: move |ev| {
+                if (area.at_top)() && !refreshing {
+                    touch_start.set(ev.touches().first().map(|touch| touch.client_coordinates().y));
+                }
+            },
+            ontouchmove: move |ev| {
+                let touch = ev.touches().first().map(|touch| touch.client_coordinates().y);
+                let (Some(start), Some(y)) = (touch_start(), touch) else {
+                    return;
+                };
+                let distance = y - start;
+                // Moving up is scrolling the content
+                if distance <= 0.0 && pull() == 0.0 {
+                    touch_start.set(None);
+                    return;
+                }
+                pull_to(distance);
+            },
+            ontouchend: move |_| {
+                if touch_start.take().is_some() {
+                    release();
+                }
+            },
+            ontouchcancel: move |_| {
+                touch_start.set(None);
+                pull.set(0.0);
+            },
+            onwheel: move |ev| {
+                let delta = ev.delta().strip_units().y;
+                if !(area.at_top)() || refreshing || delta >= 0.0 {
+                    if overscroll() > 0.0 {
+                        overscroll.set(0.0);
+                        pull.set(0.0);
+                    }
+                    return;
+                }
+                overscroll += -delta;
+                pull_to(overscroll());
+                if pull() >= REFRESH_DISTANCE {
+                    release();
+                }
+            },
+            onmouseleave: move |_| {
+                if overscroll() > 0.0 {
+                    overscroll.set(0.0);
+                    pull.set(0.0);
+                }
+            },
+
+            div {
+                position: "absolute",
+                top: "0",
+                left: "0",
+                right: "0",
+                height: "{offset}px",
+                display: "flex",
+                flex_direction: "column",
+                align_items: "center",
+                justify_content: "center",
+                gap: "2px",
+                overflow: "hidden",
+                font_size: "12px",
+                color: "var(--text-secondary)",
+                role: "status",
+
+                if offset > 0.0 {
+                    span { {label} }
+                    if let Some(status) = status {
+                        span { color: "var(--text-tertiary)", {status} }
+                    }
+                }
+            }
+            div {
+                height: "100%",
+                transform: "translateY({offset}px)",
+                transition: if dragging { "none" } else { "transform 160ms ease-out" },
+                {children}
+            }
+        }
+    }
+}
@@ -0,0 +1,210 @@
+use dioxus::prelude::*;
+
+use super::{use_haptics, Haptic};
+use crate::overlay::{use_overlays, MenuItem};
+
+/// Distance a row has to be swiped for releasing it to take the action
+const SWIPE_DISTANCE: f64 = 88.0;
+/// Furthest a row is swiped
+const MAX_SWIPE: f64 = 132.0;
+/// Distance a touch moves before it's known to be a swipe rather than a scroll
+const SWIPE_SLOP: f64 = 10.0;
+
+/// An action taken by swiping a row, see [SwipeActions]
+#[derive(Clone, PartialEq)]
+pub struct SwipeAction {
+    pub label: String,
+    pub icon: Option<Element>,
+    /// Color revealed under the row while it's swiped
+    pub background: String,
+    pub onaction: Callback,
+}
+
+impl SwipeAction {
+    pub fn new(
+        label: impl Into<String>,
+        background: impl Into<String>,
+        onaction: impl FnMut(()) + 'static,
+    ) -> Self {
+        Self {
+            label: label.into(),
+            icon: None,
+            background: background.into(),
+            onaction: Callback::new(onaction),
+        }
+    }
+
+    pub fn icon(mut self, icon: Element) -> Self {
+        self.icon = Some(icon);
+        self
+    }
+}
+
+/// Row which takes an action when it's swiped left or right, by touch or horizontally with a
+/// trackpad. Right clicking or long pressing it opens a menu, so its actions can be found and
+/// taken with a mouse too.
+#[component]
+pub fn SwipeActions(
+    /// Taken by swiping the row to the left
+    swipe_left: Option<SwipeAction>,
+    /// Taken by swiping the row to the right
+    swipe_right: Option<SwipeAction>,
+    /// Actions of the context menu, usually including the swipe actions
+    #[props(default)]
+    menu: Vec<MenuItem>,
+    children: Element,
+) -> Element {
+    let haptics = use_haptics();
+    let mut overlays = use_overlays();
+    let mut touch_start = use_signal(|| None::<(f64, f64)>);
+    // The touch moved sideways far enough to be a swipe
+    let mut swiping = use_signal(|| false);
+    let mut offset = use_signal(|| 0.0_f64);
+    // Distance scrolled sideways with a trackpad, and whether the action was taken since it
+    // started, so its momentum doesn't take it again
+    let mut wheel = use_signal(|| 0.0);
+    let mut wheel_taken = use_signal(|| false);
+
+    let (has_left, has_right) = (swipe_left.is_some(), swipe_right.is_some());
+    let mut swipe_to = move |distance: f64| {
+        let next = match distance {
+            distance if distance > 0.0 && has_right => distance.min(MAX_SWIPE),
+            distance if distance < 0.0 && has_left => distance.max(-MAX_SWIPE),
+            _ => 0.0,
+        };
+        if (offset.peek().abs() >= SWIPE_DISTANCE) != (next.abs() >= SWIPE_DISTANCE) {
+            haptics.play(Haptic::Threshold);
+        }
+        offset.set(next);
+    };
+    let on_left = swipe_left.as_ref().map(|action| action.onaction);
+    let on_right = swipe_right.as_ref().map(|action| action.onaction);
+    let mut release = move || {
+        let onaction = match *offset.peek() {
+            offset if offset >= SWIPE_DISTANCE => on_right,
+            offset if offset <= -SWIPE_DISTANCE => on_left,
+            _ => None,
+        };
+        if let Some(onaction) = onaction {
+            haptics.play(Haptic::Action);
+            onaction.call(());
+        }
+        offset.set(0.0);
+    };
+
+    // Swipes are physical, so the revealed action sticks to the same side in right to left text
+    let revealed = match offset() {
+        offset if offset > 0.0 => swipe_right.map(|action| (action, "left")),
+        offset if offset < 0.0 => swipe_left.map(|action| (action, "right")),
+        _ => None,
+    };
+    let dragging = swiping() || wheel() != 0.0;
+
+    rsx! {
+        div {
+            position: "relative",
+            overflow: "hidden",
+            // Vertical movement still scrolls the list the row is in
+            touch_action: "pan-y",
+
+            oncontextmenu: move |ev| {
+                if menu.is_empty() {
+                    return;
+                }
+                ev.prevent_default();
+                let point = ev.client_coordinates();
+                overlays.menu(point.x, point.y, menu.clone());
+            },
+            ontouchstart: move |ev| {
+                let Some(touch) = ev.touches().first().map(|touch| touch.client_coordinates()) else {
+                    return;
+                };
+                touch_start.set(Some((touch.x, touch.y)));
+                swiping.set(false);
+            },
+            ontouchmove: move |ev| {
+                let touch = ev.touches().first().map(|touch| touch.client_coordinates());
+                let (Some((start_x, start_y)), Some(touch)) = (touch_start(), touch) else {
+                    return;
+                };
+                let (dx, dy) = (touch.x - start_x, touch.y - start_y);
+                if !swiping() {
+                    if dy.abs() > SWIPE_SLOP {
+                        touch_start.set(None);
+                        return;
+                    }
+                    if dx.abs() <= SWIPE_SLOP {
+                        return;
+                    }
+                    swiping.set(true);
+                }
+                swipe_to(dx);
+            },
+            ontouchend: move |_| {
+                if touch_start.take().is_some() && swiping() {
+                    release();
+                }
+                swiping.set(false);
+            },
+            ontouchcancel: move |_| {
+                touch_start.set(None);
+                swiping.set(false);
+                offset.set(0.0);
+            },
+            onwheel: move |ev| {
+                let delta = ev.delta().strip_units();
+                // Momentum dies down to tiny movements, which ends the swipe
+                if delta.x.abs() <= delta.y.abs() || delta.x.abs() < 1.0 {
+                    wheel.set(0.0);
+                    wheel_taken.set(false);
+                    if offset() != 0.0 {
+                        offset.set(0.0);
+                    }
+                    return;
+                }
+                if wheel_taken() {
+                    return;
+                }
+                wheel -= delta.x;
+                swipe_to(wheel());
+                if offset().abs() >= SWIPE_DISTANCE {
+                    release();
+                    wheel_taken.set(true);
+                }
+            },
+            onmouseleave: move |_| {
+                wheel.set(0.0);
+                wheel_taken.set(false);
+                offset.set(0.0);
+            },
+
+            if let Some((action, justify)) = revealed {
+                div {
+                    position: "absolute",
+                    top: "0",
+                    bottom: "0",
+                    left: "0",
+                    right: "0",
+                    display: "flex",
+                    align_items: "center",
+                    justify_content: justify,
+                    gap: "8px",
+                    padding: "0 24px",
+                    background: action.background,
+                    color: "var(--bg)",
+                    opacity: if offset().abs() >= SWIPE_DISTANCE { "1" } else { "0.6" },
+                    aria_hidden: "true",
+                    {action.icon}
+                    span { {action.label} }
+                }
+            }
+            div {
+                position: "relative",
+                background: "var(--bg)",
+                transform: "translateX({offset}px)",
+                transition: if dragging { "none" } else { "transform 160ms ease-out" },
+                {children}
+            }
+        }
+    }
+}
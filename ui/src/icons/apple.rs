@@ -6,6 +6,9 @@ pub fn AppleIcon(props: IconProps) -> Element {
     rsx! {
         svg {
             class: "icon",
+            role: props.role(),
+            "aria-label": props.title.clone(),
+            "aria-hidden": props.title.is_none().then_some("true"),
             xmlns: "http://www.w3.org/2000/svg",
             view_box: "0 0 18 20",
             width: props.size,
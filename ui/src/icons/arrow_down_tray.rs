@@ -0,0 +1,26 @@
+use super::IconProps;
+use dioxus::prelude::*;
+
+#[component]
+pub fn ArrowDownTrayIcon(props: IconProps) -> Element {
+    rsx! {
+        svg {
+            class: "icon",
+            role: props.role(),
+            "aria-label": props.title.clone(),
+            "aria-hidden": props.title.is_none().then_some("true"),
+            xmlns: "http://www.w3.org/2000/svg",
+            view_box: "0 0 24 24",
+            width: props.size,
+            height: props.size,
+            fill: props.fill(),
+            stroke: props.stroke(),
+            stroke_width: 1.5,
+            path {
+                stroke_linecap: "round",
+                stroke_linejoin: "round",
+                d: "M3 16.5v2.25A2.25 2.25 0 0 0 5.25 21h13.5A2.25 2.25 0 0 0 21 18.75V16.5M16.5 12 12 16.5m0 0L7.5 12m4.5 4.5V3",
+            }
+        }
+    }
+}
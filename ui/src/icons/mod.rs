@@ -8,9 +8,17 @@ pub struct IconProps {
     pub color: String,
     #[props(default = false)]
     pub solid: bool,
+    /// Names the icon for screen readers. Icons without a title are hidden from them, since
+    /// they decorate a control which is named by its label.
+    #[props(into)]
+    pub title: Option<String>,
 }
 
 impl IconProps {
+    pub fn role(&self) -> Option<&'static str> {
+        self.title.as_ref().map(|_| "img")
+    }
+
     pub fn fill(&self) -> String {
         if self.solid {
             self.color.clone()
@@ -30,6 +38,7 @@ impl IconProps {
 
 mod apple;
 mod archive_box;
+mod arrow_down_tray;
 mod arrow_top_right_on_square;
 mod bars3;
 mod beaker;
@@ -39,10 +48,13 @@ mod checkbox;
 mod cog;
 mod envelope;
 mod envelope_open;
+mod eye;
 mod google;
 mod information;
 mod lock;
 mod newspaper;
+mod pause;
+mod play;
 mod plus;
 mod queue;
 mod search;
@@ -53,6 +65,7 @@ mod user;
 
 pub use apple::AppleIcon;
 pub use archive_box::ArchiveBoxIcon;
+pub use arrow_down_tray::ArrowDownTrayIcon;
 pub use arrow_top_right_on_square::ArrowTopRightOnSquareIcon;
 pub use bars3::Bars3Icon;
 pub use beaker::BeakerIcon;
@@ -62,10 +75,13 @@ pub use checkbox::CheckboxIcon;
 pub use cog::Cog6Tooth;
 pub use envelope::EnvelopeIcon;
 pub use envelope_open::EnvelopeOpenIcon;
+pub use eye::{EyeIcon, EyeSlashIcon};
 pub use google::GoogleIcon;
 pub use information::InformationIcon;
 pub use lock::LockIcon;
 pub use newspaper::NewspaperIcon;
+pub use pause::PauseIcon;
+pub use play::PlayIcon;
 pub use plus::PlusIcon;
 pub use queue::QueueIcon;
 pub use search::SearchIcon;
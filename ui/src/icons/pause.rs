@@ -0,0 +1,26 @@
+use super::IconProps;
+use dioxus::prelude::*;
+
+#[component]
+pub fn PauseIcon(props: IconProps) -> Element {
+    rsx! {
+        svg {
+            class: "icon",
+            role: props.role(),
+            "aria-label": props.title.clone(),
+            "aria-hidden": props.title.is_none().then_some("true"),
+            xmlns: "http://www.w3.org/2000/svg",
+            view_box: "0 0 24 24",
+            width: props.size,
+            height: props.size,
+            fill: "none",
+            stroke: props.color,
+            stroke_width: 1.5,
+            path {
+                stroke_linecap: "round",
+                stroke_linejoin: "round",
+                d: "M15.75 5.25v13.5m-7.5-13.5v13.5",
+            }
+        }
+    }
+}
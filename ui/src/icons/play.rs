@@ -0,0 +1,26 @@
+use super::IconProps;
+use dioxus::prelude::*;
+
+#[component]
+pub fn PlayIcon(props: IconProps) -> Element {
+    rsx! {
+        svg {
+            class: "icon",
+            role: props.role(),
+            "aria-label": props.title.clone(),
+            "aria-hidden": props.title.is_none().then_some("true"),
+            xmlns: "http://www.w3.org/2000/svg",
+            view_box: "0 0 24 24",
+            width: props.size,
+            height: props.size,
+            fill: props.fill(),
+            stroke: props.color,
+            stroke_width: 1.5,
+            path {
+                stroke_linecap: "round",
+                stroke_linejoin: "round",
+                d: "M5.25 5.653c0-.856.917-1.398 1.667-.986l11.54 6.347a1.125 1.125 0 0 1 0 1.972l-11.54 6.347a1.125 1.125 0 0 1-1.667-.986V5.653Z",
+            }
+        }
+    }
+}
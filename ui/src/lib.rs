@@ -1,5 +1,9 @@
 //! This crate contains all shared UI for the workspace.
 
 pub mod forms;
+pub mod gesture;
 pub mod icons;
 pub mod layout;
+pub mod list;
+pub mod overlay;
+pub mod text;
@@ -0,0 +1,4 @@
+pub mod virtual_list;
+
+// components
+pub use virtual_list::{use_virtual_list, VirtualList, VirtualListHandle};
@@ -0,0 +1,318 @@
+use std::{collections::HashMap, rc::Rc};
+
+use dioxus::{html::geometry::PixelsVector2D, prelude::*};
+
+use crate::gesture::PullArea;
+
+/// Measured heights which differ by less than this are treated as unchanged, so rounding
+/// doesn't move the rows around
+const HEIGHT_EPSILON: f64 = 0.5;
+
+/// Keys and offsets of the rows, kept between renders so that scrolling doesn't go through
+/// every row. The keys are computed again when the rows change, and the offsets when a key or a
+/// height does.
+#[derive(Default)]
+struct Layout {
+    /// Number of rows and the callback the keys were computed with
+    rows: Option<(usize, Callback<usize, String>)>,
+    keys: Rc<Vec<String>>,
+    /// Offset of the top of each row, followed by the bottom of the last one. Emptied when a
+    /// height is measured, to be computed again.
+    offsets: Rc<Vec<f64>>,
+    estimated_height: f64,
+}
+
+/// Scrolls a [VirtualList] from outside of it, see [use_virtual_list]
+#[derive(Clone, Copy, PartialEq)]
+pub struct VirtualListHandle {
+    scroll_request: Signal<Option<usize>>,
+}
+
+impl VirtualListHandle {
+    /// Scrolls the list until the row at the index is at its top
+    pub fn scroll_to_index(&mut self, index: usize) {
+        self.scroll_request.set(Some(index));
+    }
+}
+
+pub fn use_virtual_list() -> VirtualListHandle {
+    use_hook(|| VirtualListHandle {
+        scroll_request: Signal::new(None),
+    })
+}
+
+#[derive(Props, Clone, PartialEq)]
+pub struct VirtualListProps {
+    /// Number of rows
+    pub len: usize,
+    /// Identifies the row at an index, to keep its measured height and its position on screen
+    /// while rows are added before it
+    pub key_of: Callback<usize, String>,
+    /// Renders the row at an index
+    pub row: Callback<usize, Element>,
+    /// Height assumed for the rows which weren't rendered yet
+    #[props(default = 80.0)]
+    pub estimated_height: f64,
+    /// Number of rows rendered beyond each edge of the viewport, so they're ready when scrolling
+    #[props(default = 4)]
+    pub overscan: usize,
+    /// Section of the row at an index, e.g. the day it's from. A header is shown before the
+    /// first row of each section, and the header of the section at the top sticks there.
+    pub section_of: Option<Callback<usize, String>>,
+    pub section_header: Option<Callback<String, Element>>,
+    /// Called once the last rows are rendered, to load more. It isn't called again until the
+    /// number of rows changed.
+    pub onendreached: Option<Callback>,
+    pub handle: Option<VirtualListHandle>,
+    /// Shown before the rows and scrolled with them, e.g. details on what's listed
+    pub leading: Option<Element>,
+}
+
+/// List which only renders the rows on screen, for lists too long to render at once.
+///
+/// The list scrolls by itself, so it should be given a height, e.g. by its parent's layout.
+/// Rows may have any height, which is measured once they're rendered. While rows are added
+/// or resized above the row at the top, that row is kept in place, except when the list is
+/// scrolled to its very top, so that new rows show there.
+///
+/// The arrow keys move the focus to the row above or below, scrolling it into view.
+#[component]
+pub fn VirtualList(props: VirtualListProps) -> Element {
+    let mut heights = use_signal(HashMap::<String, f64>::new);
+    let mut leading_height = use_signal(|| 0.0);
+    let mut scroll_top = use_signal(|| 0.0);
+    let mut viewport_height = use_signal(|| 0.0);
+    let mut container = use_signal(|| None::<Rc<MountedData>>);
+    // Row at the top of the viewport when last rendered, with its offset back then
+    let mut anchor = use_hook(|| CopyValue::new(None::<(String, f64)>));
+    let mut layout = use_hook(|| CopyValue::new(Layout::default()));
+    // Number of rows when `onendreached` was last called
+    let mut end_reached_at = use_hook(|| CopyValue::new(None::<usize>));
+    let pull_area = try_use_context::<PullArea>();
+
+    let len = props.len;
+    let (keys, offsets) = {
+        // Read every render, so the list is rendered again when a height changes
+        let heights = heights.read();
+        let leading_height = leading_height();
+        let mut layout = layout.write();
+        let rows = Some((len, props.key_of));
+        if layout.rows != rows {
+            layout.keys = Rc::new((0..len).map(|index| props.key_of.call(index)).collect());
+            layout.rows = rows;
+            layout.offsets = Rc::default();
+        }
+        if layout.offsets.is_empty() || layout.estimated_height != props.estimated_height {
+            let mut offset = leading_height;
+            let mut offsets = Vec::with_capacity(len + 1);
+            offsets.push(offset);
+            for key in layout.keys.iter() {
+                offset += heights.get(key).copied().unwrap_or(props.estimated_height);
+                offsets.push(offset);
+            }
+            layout.offsets = Rc::new(offsets);
+            layout.estimated_height = props.estimated_height;
+        }
+        (layout.keys.clone(), layout.offsets.clone())
+    };
+
+    let mut top = scroll_top();
+    let scroll_request = props
+        .handle
+        .map(|handle| handle.scroll_request)
+        .filter(|scroll_request| scroll_request.read().is_some());
+    if let Some(mut scroll_request) = scroll_request {
+        let index = scroll_request.peek().unwrap_or_default();
+        top = offsets[index.min(len)];
+        scroll_container(container, top);
+        spawn(async move { scroll_request.set(None) });
+    } else if top > 0.0 {
+        let moved = anchor.peek().as_ref().and_then(|(key, offset)| {
+            let index = keys.iter().position(|k| k == key)?;
+            let delta = offsets[index] - offset;
+            (delta.abs() > HEIGHT_EPSILON).then_some(delta)
+        });
+        if let Some(delta) = moved {
+            top += delta;
+            scroll_container(container, top);
+        }
+    }
+
+    // First row reaching into the viewport, and the row after the last one
+    let first = offsets[1..]
+        .partition_point(|bottom| *bottom <= top)
+        .min(len.saturating_sub(1));
+    let last = offsets[..len].partition_point(|row_top| *row_top < top + viewport_height());
+    let start = first.saturating_sub(props.overscan);
+    let end = (last + props.overscan).min(len);
+
+    anchor.set(keys.get(first).map(|key| (key.clone(), offsets[first])));
+
+    if let Some(onendreached) = props.onendreached {
+        if len > 0 && end == len && *end_reached_at.peek() != Some(len) {
+            end_reached_at.set(Some(len));
+            spawn(async move { onendreached.call(()) });
+        }
+    }
+
+    let onkeydown = move |ev: KeyboardEvent| {
+        let step = match ev.key() {
+            Key::ArrowDown => "nextElementSibling",
+            Key::ArrowUp => "previousElementSibling",
+            _ => return,
+        };
+        ev.prevent_default();
+        // Spacers sit between the rows at the edges, so siblings are skipped until a row
+        _ = document::eval(&format!(
+            r#"let row = document.activeElement?.closest("[data-ui-row]")?.{step};
+            while (row && !row.hasAttribute("data-ui-row")) row = row.{step};
+            let target = row?.querySelector("a, button, [tabindex]");
+            target?.focus({{ preventScroll: true }});
+            target?.scrollIntoView({{ block: "nearest" }});"#
+        ));
+    };
+
+    let section_of = |index: usize| props.section_of.map(|section_of| section_of.call(index));
+    // An empty list has no row to take the section from
+    let pinned_header = match (
+        props.section_header,
+        (len > 0).then(|| section_of(first)).flatten(),
+    ) {
+        (Some(header), Some(section)) if top > offsets[0] => Some(header.call(section)),
+        _ => None,
+    };
+    let pinned = pinned_header.is_some();
+
+    rsx! {
+        div {
+            height: "100%",
+            overflow_y: "auto",
+            // The list keeps its rows in place itself, which the browser would do twice, and
+            // pulling past its top is left to a surrounding pull to refresh
+            style: "overflow-anchor: none; overscroll-behavior-y: contain;",
+            onmounted: move |ev| container.set(Some(ev.data())),
+            onresize: move |ev| {
+                if let Ok(size) = ev.get_border_box_size() {
+                    viewport_height.set(size.height);
+                }
+            },
+            onscroll: move |ev| {
+                scroll_top.set(ev.scroll_top() as f64);
+                if let Some(mut pull_area) = pull_area {
+                    let at_top = ev.scroll_top() <= 0;
+                    if *pull_area.at_top.peek() != at_top {
+                        pull_area.at_top.set(at_top);
+                    }
+                }
+            },
+            onkeydown,
+
+            if let Some(leading) = props.leading {
+                div {
+                    onresize: move |ev| {
+                        if let Ok(size) = ev.get_border_box_size() {
+                            layout.write().offsets = Rc::default();
+                            leading_height.set(size.height);
+                        }
+                    },
+                    {leading}
+                }
+            }
+            // Takes no space, so the rows don't move when it's shown, and is drawn over them
+            if let Some(header) = pinned_header {
+                div {
+                    position: "sticky",
+                    top: "0",
+                    height: "0",
+                    overflow: "visible",
+                    z_index: "1",
+                    {header}
+                }
+            }
+            div { height: "{offsets[start] - offsets[0]}px" }
+            for index in start..end {
+                div {
+                    key: "{keys[index]}",
+                    "data-ui-row": "{index}",
+                    onresize: {
+                        let key = keys[index].clone();
+                        move |ev: Event<ResizeData>| {
+                            let Ok(size) = ev.get_border_box_size() else {
+                                return;
+                            };
+                            let changed = heights
+                                .peek()
+                                .get(&key)
+                                .is_none_or(|height| (height - size.height).abs() > HEIGHT_EPSILON);
+                            if changed {
+                                layout.write().offsets = Rc::default();
+                                heights.write().insert(key.clone(), size.height);
+                            }
+                        }
+                    },
+
+                    if let (Some(header), Some(section)) = (props.section_header, section_of(index)) {
+                        if index == 0 || section_of(index - 1).as_ref() != Some(&section) {
+                            // The pinned header is drawn over the first row's own, which is
+                            // hidden but keeps its space so the row doesn't change height
+                            div { visibility: if pinned && index == first { "hidden" } else { "visible" },
+                                {header.call(section)}
+                            }
+                        }
+                    }
+                    {props.row.call(index)}
+                }
+            }
+            div { height: "{offsets[len] - offsets[end]}px" }
+        }
+    }
+}
+
+fn scroll_container(container: Signal<Option<Rc<MountedData>>>, top: f64) {
+    let Some(container) = container.peek().clone() else {
+        return;
+    };
+    spawn(async move {
+        _ = container
+            .scroll(PixelsVector2D::new(0.0, top), ScrollBehavior::Instant)
+            .await;
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[component]
+    fn Sectioned(days: Vec<String>) -> Element {
+        rsx! {
+            VirtualList {
+                len: days.len(),
+                key_of: |index: usize| index.to_string(),
+                section_of: {
+                    let days = days.clone();
+                    move |index: usize| days[index].clone()
+                },
+                section_header: |day: String| rsx! { "{day}" },
+                row: |index: usize| rsx! { "{index}" },
+            }
+        }
+    }
+
+    #[test]
+    fn empty_list_with_sections_renders() {
+        let mut dom = VirtualDom::new_with_props(Sectioned, SectionedProps { days: vec![] });
+        dom.rebuild_in_place();
+    }
+
+    #[test]
+    fn list_with_sections_renders() {
+        let days = vec![
+            "Today".to_string(),
+            "Today".to_string(),
+            "Yesterday".to_string(),
+        ];
+        let mut dom = VirtualDom::new_with_props(Sectioned, SectionedProps { days });
+        dom.rebuild_in_place();
+    }
+}
@@ -0,0 +1,87 @@
+use dioxus::prelude::*;
+
+use super::use_overlay;
+use crate::{
+    forms::button::{SolidButton, TransparentButton},
+    layout::{Column, Row},
+    text::{use_texts, UiText},
+};
+
+/// Content of a dialog, with its title and the buttons for what can be done with it
+#[component]
+pub fn Dialog(title: String, actions: Option<Element>, children: Element) -> Element {
+    let overlay = use_overlay();
+
+    rsx! {
+        Column { gap: "16px", padding: "24px",
+            h2 { id: overlay.title_id(), margin: "0", font_size: "20px", {title} }
+            {children}
+            if let Some(actions) = actions {
+                Row { gap: "8px", align: "end", {actions} }
+            }
+        }
+    }
+}
+
+/// An action the user is asked to confirm, see [Overlays::confirm](super::Overlays::confirm)
+#[derive(Clone, PartialEq)]
+pub struct Confirm {
+    pub title: String,
+    pub message: String,
+    /// Label of the button confirming the action, "Confirm" when it has none
+    pub confirm_label: Option<String>,
+    /// The action can't be undone, e.g. deleting something, so it's shown in red
+    pub destructive: bool,
+    pub onconfirm: Callback,
+}
+
+impl Confirm {
+    pub fn new(
+        title: impl Into<String>,
+        message: impl Into<String>,
+        onconfirm: impl FnMut(()) + 'static,
+    ) -> Self {
+        Self {
+            title: title.into(),
+            message: message.into(),
+            confirm_label: None,
+            destructive: false,
+            onconfirm: Callback::new(onconfirm),
+        }
+    }
+
+    pub fn confirm_label(mut self, label: impl Into<String>) -> Self {
+        self.confirm_label = Some(label.into());
+        self
+    }
+
+    pub fn destructive(mut self) -> Self {
+        self.destructive = true;
+        self
+    }
+}
+
+#[component]
+pub(super) fn ConfirmDialog(confirm: Confirm) -> Element {
+    let overlay = use_overlay();
+    let texts = use_texts();
+    let onconfirm = confirm.onconfirm;
+
+    rsx! {
+        Dialog {
+            title: confirm.title,
+            actions: rsx! {
+                TransparentButton { onclick: move |_| overlay.close(), {texts.get(UiText::Cancel)} }
+                SolidButton {
+                    color: confirm.destructive.then_some("var(--text-error)"),
+                    onclick: move |_| {
+                        overlay.close();
+                        onconfirm.call(());
+                    },
+                    {confirm.confirm_label.unwrap_or_else(|| texts.get(UiText::Confirm))}
+                }
+            },
+            span { {confirm.message} }
+        }
+    }
+}
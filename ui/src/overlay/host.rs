@@ -0,0 +1,264 @@
+use dioxus::{document::eval, prelude::*};
+
+use super::{toast::ToastHost, use_overlays, Layer, Overlay, OverlayKind};
+
+/// Dragging a sheet down further than this closes it, rather than moving it back up
+const SHEET_DISMISS_DISTANCE: f64 = 96.0;
+
+const STYLE: &str = r#"
+@keyframes ui-fade-in { from { opacity: 0; } }
+@keyframes ui-fade-out { to { opacity: 0; } }
+@keyframes ui-slide-up { from { transform: translateY(100%); } }
+@keyframes ui-slide-down { to { transform: translateY(100%); } }
+@keyframes ui-scale-in { from { opacity: 0; transform: scale(0.95); } }
+@keyframes ui-scale-out { to { opacity: 0; transform: scale(0.95); } }
+@keyframes ui-toast-life { from { visibility: visible; } to { visibility: visible; } }
+
+.ui-overlay { position: absolute; inset: 0; }
+.ui-overlay-backdrop {
+  position: absolute;
+  inset: 0;
+  background: rgba(0, 0, 0, 0.3);
+  animation: ui-fade-in 160ms ease-out;
+}
+.ui-overlay-backdrop.transparent { background: transparent; }
+
+.ui-overlay-dialog, .ui-overlay-alert {
+  position: absolute;
+  top: 50%;
+  left: 50%;
+  translate: -50% -50%;
+  width: min(calc(100% - 48px), 420px);
+  max-height: calc(100% - 48px);
+  overflow: auto;
+  background: var(--bg);
+  border: 1px solid var(--text);
+  animation: ui-scale-in 160ms ease-out;
+}
+
+.ui-overlay-sheet {
+  position: absolute;
+  left: 0;
+  right: 0;
+  bottom: 0;
+  max-height: 90%;
+  overflow: auto;
+  background: var(--bg);
+  border-top: 1px solid var(--text);
+  box-shadow: 0 -8px 16px rgba(0, 0, 0, 0.08);
+  animation: ui-slide-up 160ms ease-out;
+  touch-action: none;
+}
+.ui-overlay-sheet .ui-sheet-handle {
+  width: 40px;
+  height: 4px;
+  margin: 8px auto 0;
+  background: var(--text-tertiary);
+  cursor: grab;
+}
+
+.ui-overlay-menu {
+  position: fixed;
+  min-width: 180px;
+  background: var(--bg);
+  border: 1px solid var(--text);
+  box-shadow: 0 4px 16px rgba(0, 0, 0, 0.12);
+  animation: ui-fade-in 100ms ease-out;
+}
+
+.ui-overlay.closing .ui-overlay-backdrop { animation: ui-fade-out 120ms ease-in forwards; }
+.ui-overlay.closing .ui-overlay-dialog,
+.ui-overlay.closing .ui-overlay-alert { animation: ui-scale-out 120ms ease-in forwards; }
+.ui-overlay.closing .ui-overlay-sheet { animation: ui-slide-down 120ms ease-in forwards; }
+.ui-overlay.closing .ui-overlay-menu { animation: ui-fade-out 100ms ease-in forwards; }
+
+.ui-toast {
+  position: absolute;
+  left: 16px;
+  right: 16px;
+  bottom: 16px;
+  display: flex;
+  align-items: center;
+  justify-content: space-between;
+  gap: 8px;
+  padding: 0 0 0 16px;
+  min-height: 48px;
+  background: var(--text);
+  color: var(--bg);
+  animation: ui-slide-up 160ms ease-out, ui-toast-life var(--ui-toast-duration) linear;
+}
+.ui-toast button { color: var(--bg); }
+.ui-toast:hover, .ui-toast:focus-within { animation-play-state: paused; }
+
+@media (prefers-reduced-motion: reduce) {
+  .ui-overlay-backdrop, .ui-overlay-dialog, .ui-overlay-alert, .ui-overlay-sheet, .ui-overlay-menu,
+  .ui-overlay.closing .ui-overlay-backdrop, .ui-overlay.closing .ui-overlay-dialog,
+  .ui-overlay.closing .ui-overlay-alert, .ui-overlay.closing .ui-overlay-sheet,
+  .ui-overlay.closing .ui-overlay-menu {
+    animation-duration: 1ms;
+  }
+  .ui-toast { animation: ui-toast-life var(--ui-toast-duration) linear; }
+}
+"#;
+
+/// Renders the open overlays and the current toast, stacked in the order they were opened
+#[component]
+pub fn OverlayHost() -> Element {
+    let overlays = use_overlays();
+    let layers = overlays.layers.read().clone();
+
+    rsx! {
+        style { {STYLE} }
+        for (index, layer) in layers.into_iter().enumerate() {
+            OverlayLayer { key: "{layer.id.0}", layer, index }
+        }
+        ToastHost {}
+    }
+}
+
+#[component]
+fn OverlayLayer(layer: Layer, index: usize) -> Element {
+    let mut overlays = use_overlays();
+    let id = layer.id;
+    let overlay = use_context_provider(|| Overlay { overlays, id });
+
+    // Keeps focus inside the overlay while it's open, and gives it back once it's closed
+    let element_id = format!("ui-overlay-{}", id.0);
+    use_hook({
+        let element_id = element_id.clone();
+        move || {
+            _ = eval(&format!(
+                r#"
+                window.__uiOverlayFocus = window.__uiOverlayFocus || {{}};
+                window.__uiOverlayFocus["{element_id}"] = document.activeElement;
+                requestAnimationFrame(() => {{
+                    const el = document.getElementById("{element_id}");
+                    if (!el) return;
+                    const focusable = () => [...el.querySelectorAll(
+                        'a[href], button:not([disabled]), input:not([disabled]), select:not([disabled]), textarea:not([disabled]), [tabindex]:not([tabindex="-1"])'
+                    )];
+                    (focusable()[0] || el).focus();
+                    el.addEventListener("keydown", (e) => {{
+                        if (e.key !== "Tab") return;
+                        const items = focusable();
+                        if (items.length === 0) {{ e.preventDefault(); return; }}
+                        const first = items[0], last = items[items.length - 1];
+                        if (e.shiftKey && document.activeElement === first) {{
+                            e.preventDefault();
+                            last.focus();
+                        }} else if (!e.shiftKey && document.activeElement === last) {{
+                            e.preventDefault();
+                            first.focus();
+                        }}
+                    }});
+                }});
+                "#
+            ));
+        }
+    });
+    use_drop({
+        let element_id = element_id.clone();
+        move || {
+            _ = eval(&format!(
+                r#"
+                const focus = (window.__uiOverlayFocus || {{}})["{element_id}"];
+                if (focus) delete window.__uiOverlayFocus["{element_id}"];
+                if (focus && focus.isConnected) focus.focus();
+                "#
+            ));
+        }
+    });
+
+    // Distance the sheet is dragged down, from where the drag started
+    let mut drag_start = use_signal(|| None::<f64>);
+    let mut drag_offset = use_signal(|| 0.0);
+
+    let dismissible = layer.kind != OverlayKind::Alert;
+    let (class, role, position) = match layer.kind {
+        OverlayKind::Dialog => ("ui-overlay-dialog", "dialog", String::new()),
+        OverlayKind::Alert => ("ui-overlay-alert", "alertdialog", String::new()),
+        OverlayKind::Sheet => (
+            "ui-overlay-sheet",
+            "dialog",
+            format!("transform: translateY({}px);", drag_offset()),
+        ),
+        OverlayKind::Menu { x, y } => (
+            "ui-overlay-menu",
+            "presentation",
+            format!("left: min({x}px, calc(100vw - 200px)); top: min({y}px, calc(100vh - 240px));"),
+        ),
+    };
+    let is_sheet = layer.kind == OverlayKind::Sheet;
+    let is_menu = matches!(layer.kind, OverlayKind::Menu { .. });
+
+    rsx! {
+        div {
+            class: if layer.closing { "ui-overlay closing" } else { "ui-overlay" },
+            z_index: "{1000 + index}",
+
+            div {
+                class: if is_menu { "ui-overlay-backdrop transparent" } else { "ui-overlay-backdrop" },
+                onclick: move |ev| {
+                    ev.stop_propagation();
+                    if dismissible {
+                        overlay.close();
+                    }
+                },
+                oncontextmenu: move |ev| {
+                    ev.prevent_default();
+                    overlay.close();
+                },
+            }
+            div {
+                id: element_id,
+                class,
+                role,
+                aria_modal: (!is_menu).then_some("true"),
+                aria_labelledby: (!is_menu).then(|| overlay.title_id()),
+                tabindex: "-1",
+                style: position,
+
+                onkeydown: move |ev| {
+                    if ev.key() == Key::Escape && dismissible {
+                        ev.stop_propagation();
+                        overlay.close();
+                    }
+                },
+                onanimationend: move |_| {
+                    if layer.closing {
+                        overlays.remove(id);
+                    }
+                },
+                // Sheets are dragged from their handle, following the pointer anywhere on them
+                onpointermove: move |ev| {
+                    if let Some(start) = drag_start() {
+                        drag_offset.set((ev.client_coordinates().y - start).max(0.0));
+                    }
+                },
+                onpointerup: move |_| {
+                    if drag_start.take().is_some() {
+                        if drag_offset() > SHEET_DISMISS_DISTANCE {
+                            overlay.close();
+                        } else {
+                            drag_offset.set(0.0);
+                        }
+                    }
+                },
+                onpointercancel: move |_| {
+                    drag_start.set(None);
+                    drag_offset.set(0.0);
+                },
+
+                if is_sheet {
+                    div {
+                        class: "ui-sheet-handle",
+                        onpointerdown: move |ev| {
+                            drag_start.set(Some(ev.client_coordinates().y));
+                        },
+                    }
+                }
+                {layer.content}
+            }
+        }
+    }
+}
@@ -0,0 +1,82 @@
+use dioxus::{document::eval, prelude::*};
+
+use super::use_overlay;
+
+/// An action in a menu, see [Overlays::menu](super::Overlays::menu)
+#[derive(Clone, PartialEq)]
+pub struct MenuItem {
+    pub label: String,
+    pub icon: Option<Element>,
+    /// Shown in red, e.g. for deleting something
+    pub destructive: bool,
+    pub onclick: Callback,
+}
+
+impl MenuItem {
+    pub fn new(label: impl Into<String>, onclick: impl FnMut(()) + 'static) -> Self {
+        Self {
+            label: label.into(),
+            icon: None,
+            destructive: false,
+            onclick: Callback::new(onclick),
+        }
+    }
+
+    pub fn icon(mut self, icon: Element) -> Self {
+        self.icon = Some(icon);
+        self
+    }
+
+    pub fn destructive(mut self) -> Self {
+        self.destructive = true;
+        self
+    }
+}
+
+/// Menu of actions, which closes once one is chosen. Moved through with the arrow keys.
+#[component]
+pub(super) fn Menu(items: Vec<MenuItem>) -> Element {
+    let overlay = use_overlay();
+
+    let onkeydown = move |ev: KeyboardEvent| {
+        let step = match ev.key() {
+            Key::ArrowDown => "nextElementSibling",
+            Key::ArrowUp => "previousElementSibling",
+            _ => return,
+        };
+        ev.prevent_default();
+        _ = eval(&format!("document.activeElement?.{step}?.focus()"));
+    };
+
+    rsx! {
+        div {
+            role: "menu",
+            display: "flex",
+            flex_direction: "column",
+            padding: "4px 0",
+            onkeydown,
+
+            for (index, item) in items.into_iter().enumerate() {
+                button {
+                    key: "{index}",
+                    role: "menuitem",
+                    display: "flex",
+                    align_items: "center",
+                    gap: "12px",
+                    padding: "10px 16px",
+                    background: "none",
+                    border: "none",
+                    text_align: "start",
+                    font_size: "16px",
+                    color: if item.destructive { "var(--text-error)" } else { "var(--text)" },
+                    onclick: move |_| {
+                        overlay.close();
+                        item.onclick.call(());
+                    },
+                    {item.icon}
+                    {item.label}
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,193 @@
+//! Dialogs, bottom sheets, menus and toasts shown above the app.
+//!
+//! [use_overlays_provider] is called once near the root of the app and [OverlayHost] rendered
+//! where overlays should cover it. Views open overlays with the [Overlays] from [use_overlays],
+//! and content inside an overlay closes it with the [Overlay] from [use_overlay].
+//!
+//! Overlays are stacked in the order they're opened, and the topmost one is closed by Escape,
+//! or by the platform's back button when the app calls [Overlays::close_top] for it.
+
+use std::collections::VecDeque;
+
+use dioxus::prelude::*;
+
+mod dialog;
+mod host;
+mod menu;
+mod toast;
+
+pub use dialog::{Confirm, Dialog};
+pub use host::OverlayHost;
+pub use menu::MenuItem;
+pub use toast::Toast;
+
+use dialog::ConfirmDialog;
+use menu::Menu;
+
+/// Identifies an open overlay, to close it
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct OverlayId(u64);
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum OverlayKind {
+    /// Centered over a backdrop, dismissed by tapping it
+    Dialog,
+    /// Like a dialog, but only closed by its buttons, for questions which need an answer
+    Alert,
+    /// Slides up from the bottom, dismissed by tapping the backdrop or dragging it down
+    Sheet,
+    /// Opened at a point, e.g. where the user right clicked
+    Menu { x: f64, y: f64 },
+}
+
+#[derive(Clone, PartialEq)]
+struct Layer {
+    id: OverlayId,
+    kind: OverlayKind,
+    content: Element,
+    /// Playing the closing animation, after which it's removed
+    closing: bool,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub struct Overlays {
+    layers: Signal<Vec<Layer>>,
+    toasts: Signal<VecDeque<(u64, Toast)>>,
+    next_id: Signal<u64>,
+}
+
+impl Overlays {
+    pub fn dialog(&mut self, content: Element) -> OverlayId {
+        self.open(OverlayKind::Dialog, content)
+    }
+
+    pub fn sheet(&mut self, content: Element) -> OverlayId {
+        self.open(OverlayKind::Sheet, content)
+    }
+
+    /// Opens a menu at a point of the window, e.g. from `oncontextmenu`
+    pub fn menu(&mut self, x: f64, y: f64, items: Vec<MenuItem>) -> OverlayId {
+        self.open(
+            OverlayKind::Menu { x, y },
+            rsx! {
+                Menu { items }
+            },
+        )
+    }
+
+    /// Asks the user to confirm an action before it's taken
+    pub fn confirm(&mut self, confirm: Confirm) -> OverlayId {
+        self.open(
+            OverlayKind::Alert,
+            rsx! {
+                ConfirmDialog { confirm }
+            },
+        )
+    }
+
+    /// Shows a toast once the ones before it were dismissed
+    pub fn toast(&mut self, toast: Toast) {
+        let id = self.next_id();
+        self.toasts.write().push_back((id, toast));
+    }
+
+    pub fn close(&mut self, id: OverlayId) {
+        if let Some(layer) = self.layers.write().iter_mut().find(|layer| layer.id == id) {
+            layer.closing = true;
+        }
+    }
+
+    /// Closes the topmost overlay, returning whether there was one. Meant for the back button,
+    /// which only goes back when no overlay was open.
+    pub fn close_top(&mut self) -> bool {
+        let top = self
+            .layers
+            .peek()
+            .iter()
+            .rev()
+            .find(|layer| !layer.closing)
+            .map(|layer| layer.id);
+        match top {
+            Some(id) => {
+                self.close(id);
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn close_all(&mut self) {
+        if self.layers.peek().iter().all(|layer| layer.closing) {
+            return;
+        }
+        for layer in self.layers.write().iter_mut() {
+            layer.closing = true;
+        }
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.layers.read().iter().any(|layer| !layer.closing)
+    }
+
+    fn open(&mut self, kind: OverlayKind, content: Element) -> OverlayId {
+        let id = OverlayId(self.next_id());
+        self.layers.write().push(Layer {
+            id,
+            kind,
+            content,
+            closing: false,
+        });
+        id
+    }
+
+    /// Removes an overlay once it finished closing
+    fn remove(&mut self, id: OverlayId) {
+        self.layers.write().retain(|layer| layer.id != id);
+    }
+
+    fn dismiss_toast(&mut self, id: u64) {
+        self.toasts.write().retain(|(toast_id, _)| *toast_id != id);
+    }
+
+    fn next_id(&mut self) -> u64 {
+        let id = *self.next_id.peek();
+        self.next_id.set(id + 1);
+        id
+    }
+}
+
+pub fn use_overlays_provider() -> Overlays {
+    use_context_provider(|| Overlays {
+        layers: Signal::new(Vec::new()),
+        toasts: Signal::new(VecDeque::new()),
+        next_id: Signal::new(0),
+    })
+}
+
+pub fn use_overlays() -> Overlays {
+    use_context::<Overlays>()
+}
+
+/// The overlay the content is shown in
+#[derive(Clone, Copy, PartialEq)]
+pub struct Overlay {
+    overlays: Overlays,
+    id: OverlayId,
+}
+
+impl Overlay {
+    pub fn close(&self) {
+        let mut overlays = self.overlays;
+        overlays.close(self.id);
+    }
+
+    /// Id of the element naming the overlay, for `aria-labelledby`
+    pub fn title_id(&self) -> String {
+        format!("ui-overlay-{}-title", self.id.0)
+    }
+}
+
+/// The overlay of the content using it, which must be shown in one
+pub fn use_overlay() -> Overlay {
+    use_context::<Overlay>()
+}
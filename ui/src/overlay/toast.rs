@@ -0,0 +1,93 @@
+use std::time::Duration;
+
+use dioxus::prelude::*;
+
+use super::use_overlays;
+use crate::{
+    forms::button::TransparentButton,
+    text::{use_texts, UiText},
+};
+
+/// How long a toast is shown for, unless it's given another duration
+const TOAST_DURATION: Duration = Duration::from_secs(5);
+
+/// A short message shown at the bottom of the screen, see [Overlays::toast](super::Overlays::toast)
+#[derive(Clone, PartialEq)]
+pub struct Toast {
+    pub message: String,
+    /// Label of the button for the action, "Undo" when it has none
+    pub action: Option<(Option<String>, Callback)>,
+    pub duration: Duration,
+}
+
+impl Toast {
+    pub fn new(message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+            action: None,
+            duration: TOAST_DURATION,
+        }
+    }
+
+    pub fn with_action(
+        message: impl Into<String>,
+        label: impl Into<String>,
+        action: impl FnMut(()) + 'static,
+    ) -> Self {
+        Self {
+            action: Some((Some(label.into()), Callback::new(action))),
+            ..Self::new(message)
+        }
+    }
+
+    /// Reverts the action the toast is reporting
+    pub fn with_undo(message: impl Into<String>, undo: impl FnMut(()) + 'static) -> Self {
+        Self {
+            action: Some((None, Callback::new(undo))),
+            ..Self::new(message)
+        }
+    }
+
+    pub fn duration(mut self, duration: Duration) -> Self {
+        self.duration = duration;
+        self
+    }
+}
+
+/// Shows the toasts one after another. Each is dismissed once its duration passed, which
+/// pauses while it's hovered or focused.
+#[component]
+pub(super) fn ToastHost() -> Element {
+    let mut overlays = use_overlays();
+    let texts = use_texts();
+    let Some((id, toast)) = overlays.toasts.read().front().cloned() else {
+        return rsx! {};
+    };
+
+    rsx! {
+        div {
+            key: "{id}",
+            class: "ui-toast",
+            role: "status",
+            aria_live: "polite",
+            z_index: "1100",
+            style: "--ui-toast-duration: {toast.duration.as_millis()}ms;",
+            onanimationend: move |ev| {
+                if ev.animation_name() == "ui-toast-life" {
+                    overlays.dismiss_toast(id);
+                }
+            },
+
+            span { {toast.message} }
+            if let Some((label, action)) = toast.action {
+                TransparentButton {
+                    onclick: move |_| {
+                        action.call(());
+                        overlays.dismiss_toast(id);
+                    },
+                    {label.unwrap_or_else(|| texts.get(UiText::Undo))}
+                }
+            }
+        }
+    }
+}
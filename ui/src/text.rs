@@ -0,0 +1,65 @@
+//! Text shown by the components themselves, e.g. the labels of their buttons.
+//!
+//! The app translates it into the user's language with [use_texts_provider], since only it
+//! knows the language. It's shown in English otherwise.
+
+use dioxus::prelude::*;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum UiText {
+    Cancel,
+    Confirm,
+    Undo,
+    Refreshing,
+    ReleaseToRefresh,
+    PullToRefresh,
+    ShowPassword,
+    HidePassword,
+    /// Names one of the boxes of a code input, counting from 0
+    CodeCharacter {
+        index: usize,
+        length: usize,
+    },
+}
+
+impl UiText {
+    pub fn english(self) -> String {
+        match self {
+            UiText::Cancel => "Cancel".to_string(),
+            UiText::Confirm => "Confirm".to_string(),
+            UiText::Undo => "Undo".to_string(),
+            UiText::Refreshing => "Refreshing...".to_string(),
+            UiText::ReleaseToRefresh => "Release to refresh".to_string(),
+            UiText::PullToRefresh => "Pull to refresh".to_string(),
+            UiText::ShowPassword => "Show password".to_string(),
+            UiText::HidePassword => "Hide password".to_string(),
+            UiText::CodeCharacter { index, length } => {
+                format!("Character {} of {length}", index + 1)
+            }
+        }
+    }
+}
+
+/// Translates the text of the components, see [use_texts]
+#[derive(Clone, Copy, PartialEq)]
+pub struct Texts(Option<Callback<UiText, String>>);
+
+impl Texts {
+    pub fn get(&self, text: UiText) -> String {
+        match self.0 {
+            Some(translate) => translate.call(text),
+            None => text.english(),
+        }
+    }
+}
+
+/// Translates the text of the components below with `translate`
+pub fn use_texts_provider(translate: impl FnMut(UiText) -> String + 'static) -> Texts {
+    let translate = use_callback(translate);
+    use_context_provider(|| Texts(Some(translate)))
+}
+
+/// The translations provided by the app, which are English when it provides none
+pub fn use_texts() -> Texts {
+    try_use_context::<Texts>().unwrap_or(Texts(None))
+}